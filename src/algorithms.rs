@@ -0,0 +1,4 @@
+//! Generic algorithms built on top of the collection traits.
+
+pub mod diff;
+pub mod join;
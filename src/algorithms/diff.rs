@@ -0,0 +1,210 @@
+//! Structural differences between two maps or two sets, which can be applied to another collection.
+//!
+//! `map_diff`/`set_diff` work on any two maps/sets. `ordered_map_diff`/`ordered_set_diff` walk two ordered
+//! collections at once, and record the changes in key order.
+use crate::{
+    algorithms::join::{merge_outer_join, outer_join, EitherOrBoth},
+    associated::{AssociatedMap, AssociatedMapOrd, AssociatedSet, AssociatedSetOrd},
+    elem::Owned,
+    iter::AssociatedIterable,
+};
+use std::cmp::Ordering;
+
+/// Changes turning one map into another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapDiff<K, V> {
+    /// Entries only present in the new map.
+    pub added: Vec<(K, V)>,
+    /// Entries only present in the old map.
+    pub removed: Vec<(K, V)>,
+    /// Keys present in both maps with different values, as `(key, old, new)`.
+    pub modified: Vec<(K, V, V)>,
+}
+
+impl<K, V> Default for MapDiff<K, V> {
+    fn default() -> Self {
+        MapDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            modified: Vec::new(),
+        }
+    }
+}
+
+impl<K, V> MapDiff<K, V> {
+    /// Returns true if there is no change.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Returns the number of changed keys.
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len() + self.modified.len()
+    }
+
+    /// Returns the diff turning the new map back into the old one.
+    pub fn invert(self) -> Self {
+        MapDiff {
+            added: self.removed,
+            removed: self.added,
+            modified: self.modified.into_iter().map(|(k, old, new)| (k, new, old)).collect(),
+        }
+    }
+
+    /// Applies the changes to `target`.
+    ///
+    /// NOTE: `target` is not required to be equal to the old map, missing keys are simply inserted or ignored.
+    pub fn apply_diff<M>(&self, target: &mut M)
+    where
+        M: AssociatedMap<KeyType = K, ValueType = V> + Owned,
+        K: Clone,
+        V: Clone,
+    {
+        for (k, _) in &self.removed {
+            target.remove(k);
+        }
+        for (k, v) in &self.added {
+            target.insert(k.clone(), v.clone());
+        }
+        for (k, _, new) in &self.modified {
+            target.insert(k.clone(), new.clone());
+        }
+    }
+
+    fn record(&mut self, k: &K, e: EitherOrBoth<&V, &V>)
+    where
+        K: Clone,
+        V: Clone + PartialEq,
+    {
+        match e {
+            EitherOrBoth::Left(old) => self.removed.push((k.clone(), old.clone())),
+            EitherOrBoth::Right(new) => self.added.push((k.clone(), new.clone())),
+            EitherOrBoth::Both(old, new) if old != new => self.modified.push((k.clone(), old.clone(), new.clone())),
+            EitherOrBoth::Both(_, _) => {}
+        }
+    }
+}
+
+/// Changes turning one set into another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetDiff<T> {
+    /// Values only present in the new set.
+    pub added: Vec<T>,
+    /// Values only present in the old set.
+    pub removed: Vec<T>,
+}
+
+impl<T> Default for SetDiff<T> {
+    fn default() -> Self {
+        SetDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+impl<T> SetDiff<T> {
+    /// Returns true if there is no change.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Returns the number of changed values.
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len()
+    }
+
+    /// Returns the diff turning the new set back into the old one.
+    pub fn invert(self) -> Self {
+        SetDiff {
+            added: self.removed,
+            removed: self.added,
+        }
+    }
+
+    /// Applies the changes to `target`.
+    pub fn apply_diff<S>(&self, target: &mut S)
+    where
+        S: AssociatedSet<ElemType = T> + Owned,
+        T: Clone,
+    {
+        for v in &self.removed {
+            target.remove(v);
+        }
+        for v in &self.added {
+            target.insert(v.clone());
+        }
+    }
+}
+
+/// Computes the changes turning `old` into `new`.
+pub fn map_diff<L, R>(old: &L, new: &R) -> MapDiff<L::KeyType, L::ValueType>
+where
+    L: AssociatedIterable + AssociatedMap,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType, ValueType = L::ValueType>,
+    L::KeyType: Clone,
+    L::ValueType: Clone + PartialEq,
+{
+    let mut diff = MapDiff::default();
+    for (k, e) in outer_join(old, new) {
+        diff.record(k, e);
+    }
+    diff
+}
+
+/// Same as `map_diff`, but walks both ordered maps at once and records the changes in key order.
+pub fn ordered_map_diff<L, R>(old: &L, new: &R) -> MapDiff<L::KeyType, L::ValueType>
+where
+    L: AssociatedIterable + AssociatedMapOrd,
+    R: AssociatedIterable + AssociatedMapOrd<KeyType = L::KeyType, ValueType = L::ValueType>,
+    L::KeyType: Clone + Ord,
+    L::ValueType: Clone + PartialEq,
+{
+    let mut diff = MapDiff::default();
+    for (k, e) in merge_outer_join(old, new) {
+        diff.record(k, e);
+    }
+    diff
+}
+
+/// Computes the changes turning `old` into `new`.
+pub fn set_diff<L, R>(old: &L, new: &R) -> SetDiff<L::ElemType>
+where
+    L: AssociatedSet,
+    R: AssociatedSet<ElemType = L::ElemType>,
+    L::ElemType: Clone,
+{
+    SetDiff {
+        added: new.iter().filter(|v| !old.contains(v)).cloned().collect(),
+        removed: old.iter().filter(|v| !new.contains(v)).cloned().collect(),
+    }
+}
+
+/// Same as `set_diff`, but walks both ordered sets at once and records the changes in order.
+pub fn ordered_set_diff<L, R>(old: &L, new: &R) -> SetDiff<L::ElemType>
+where
+    L: AssociatedSetOrd,
+    R: AssociatedSetOrd<ElemType = L::ElemType>,
+    L::ElemType: Clone + Ord,
+{
+    let mut diff = SetDiff::default();
+    let mut old = old.iter().peekable();
+    let mut new = new.iter().peekable();
+    loop {
+        let ord = match (old.peek(), new.peek()) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        match ord {
+            Ordering::Less => diff.removed.extend(old.next().cloned()),
+            Ordering::Greater => diff.added.extend(new.next().cloned()),
+            Ordering::Equal => {
+                old.next();
+                new.next();
+            }
+        }
+    }
+    diff
+}
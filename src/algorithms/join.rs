@@ -0,0 +1,345 @@
+//! Relational joins between two key-value maps.
+//!
+//! The plain functions (`inner_join`, `left_join`, ...) work on any two maps by iterating one side and probing
+//! the other one with `AssociatedMap::get`. The `merge_*` functions are the fast path when both sides are
+//! `AssociatedMapOrd`: they walk both maps in key order at once, and yield results in key order.
+use crate::{
+    associated::{AssociatedMap, AssociatedMapOrd},
+    iter::AssociatedIterable,
+};
+use std::{cmp::Ordering, iter::Peekable};
+
+/// Value that is present on the left side, the right side, or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EitherOrBoth<A, B> {
+    /// Only the left value is present.
+    Left(A),
+    /// Only the right value is present.
+    Right(B),
+    /// Both values are present.
+    Both(A, B),
+}
+
+impl<A, B> EitherOrBoth<A, B> {
+    /// Returns the left value, if any.
+    pub fn left(self) -> Option<A> {
+        match self {
+            EitherOrBoth::Left(a) | EitherOrBoth::Both(a, _) => Some(a),
+            EitherOrBoth::Right(_) => None,
+        }
+    }
+
+    /// Returns the right value, if any.
+    pub fn right(self) -> Option<B> {
+        match self {
+            EitherOrBoth::Right(b) | EitherOrBoth::Both(_, b) => Some(b),
+            EitherOrBoth::Left(_) => None,
+        }
+    }
+
+    /// Returns both values as `Option`s.
+    pub fn left_and_right(self) -> (Option<A>, Option<B>) {
+        match self {
+            EitherOrBoth::Left(a) => (Some(a), None),
+            EitherOrBoth::Right(b) => (None, Some(b)),
+            EitherOrBoth::Both(a, b) => (Some(a), Some(b)),
+        }
+    }
+
+    /// Returns true if both values are present.
+    pub fn is_both(&self) -> bool {
+        matches!(self, EitherOrBoth::Both(_, _))
+    }
+
+    /// Swaps the left and right sides.
+    pub fn flip(self) -> EitherOrBoth<B, A> {
+        match self {
+            EitherOrBoth::Left(a) => EitherOrBoth::Right(a),
+            EitherOrBoth::Right(b) => EitherOrBoth::Left(b),
+            EitherOrBoth::Both(a, b) => EitherOrBoth::Both(b, a),
+        }
+    }
+}
+
+/// Full outer join iterator that probes `right` for every entry of `left`.
+///
+/// Entries only present in `right` are visited after all entries of `left`.
+pub struct ProbeJoin<'a, L, R>
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    left: &'a L,
+    right: &'a R,
+    left_iter: L::Iter<'a>,
+    right_iter: Option<R::Iter<'a>>,
+}
+
+impl<'a, L, R> ProbeJoin<'a, L, R>
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    fn new(left: &'a L, right: &'a R, right_tail: bool) -> Self {
+        ProbeJoin {
+            left,
+            right,
+            left_iter: left.iter(),
+            right_iter: if right_tail { Some(right.iter()) } else { None },
+        }
+    }
+}
+
+impl<'a, L, R> Iterator for ProbeJoin<'a, L, R>
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    type Item = (&'a L::KeyType, EitherOrBoth<&'a L::ValueType, &'a R::ValueType>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((k, v)) = self.left_iter.next() {
+            return Some(match self.right.get(k) {
+                Some(w) => (k, EitherOrBoth::Both(v, w)),
+                None => (k, EitherOrBoth::Left(v)),
+            });
+        }
+
+        let left = self.left;
+        self.right_iter
+            .as_mut()?
+            .find(|(k, _)| !left.contains_key(k))
+            .map(|(k, w)| (k, EitherOrBoth::Right(w)))
+    }
+}
+
+/// Full outer join iterator that walks two ordered maps in key order.
+pub struct MergeJoin<'a, L, R>
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    left: Peekable<L::Iter<'a>>,
+    right: Peekable<R::Iter<'a>>,
+    right_tail: bool,
+}
+
+impl<'a, L, R> MergeJoin<'a, L, R>
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    fn new(left: &'a L, right: &'a R, right_tail: bool) -> Self {
+        MergeJoin {
+            left: left.iter().peekable(),
+            right: right.iter().peekable(),
+            right_tail,
+        }
+    }
+}
+
+impl<'a, L, R> Iterator for MergeJoin<'a, L, R>
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: Ord + 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    type Item = (&'a L::KeyType, EitherOrBoth<&'a L::ValueType, &'a R::ValueType>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ord = match (self.left.peek(), self.right.peek()) {
+            (Some((lk, _)), Some((rk, _))) => lk.cmp(rk),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) if self.right_tail => Ordering::Greater,
+            _ => return None,
+        };
+        match ord {
+            Ordering::Less => self.left.next().map(|(k, v)| (k, EitherOrBoth::Left(v))),
+            Ordering::Greater => self.right.next().map(|(k, w)| (k, EitherOrBoth::Right(w))),
+            Ordering::Equal => {
+                let (k, v) = self.left.next()?;
+                let (_, w) = self.right.next()?;
+                Some((k, EitherOrBoth::Both(v, w)))
+            }
+        }
+    }
+}
+
+/// Visits the keys present in both maps, with the values from both sides.
+pub fn inner_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+) -> impl Iterator<Item = (&'a L::KeyType, &'a L::ValueType, &'a R::ValueType)> + 'a
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    ProbeJoin::new(left, right, false).filter_map(|(k, e)| match e {
+        EitherOrBoth::Both(v, w) => Some((k, v, w)),
+        _ => None,
+    })
+}
+
+/// Visits all keys of `left`, with the matching value of `right` if any.
+pub fn left_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+) -> impl Iterator<Item = (&'a L::KeyType, &'a L::ValueType, Option<&'a R::ValueType>)> + 'a
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    ProbeJoin::new(left, right, false).filter_map(|(k, e)| match e {
+        EitherOrBoth::Both(v, w) => Some((k, v, Some(w))),
+        EitherOrBoth::Left(v) => Some((k, v, None)),
+        EitherOrBoth::Right(_) => None,
+    })
+}
+
+/// Visits all keys of `right`, with the matching value of `left` if any.
+pub fn right_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+) -> impl Iterator<Item = (&'a L::KeyType, Option<&'a L::ValueType>, &'a R::ValueType)> + 'a
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    left_join(right, left).map(|(k, w, v)| (k, v, w))
+}
+
+/// Visits the keys present in either map.
+///
+/// Keys of `left` are visited first, then the keys only present in `right`.
+pub fn outer_join<'a, L, R>(left: &'a L, right: &'a R) -> ProbeJoin<'a, L, R>
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedIterable + AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    ProbeJoin::new(left, right, true)
+}
+
+/// Visits the entries of `left` whose keys are not present in `right`.
+pub fn anti_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+) -> impl Iterator<Item = (&'a L::KeyType, &'a L::ValueType)> + 'a
+where
+    L: AssociatedIterable + AssociatedMap + 'a,
+    R: AssociatedMap<KeyType = L::KeyType> + 'a,
+    L::KeyType: 'a,
+    L::ValueType: 'a,
+{
+    left.iter().filter(move |(k, _)| !right.contains_key(k))
+}
+
+/// Same as `inner_join`, but walks both ordered maps in key order.
+pub fn merge_inner_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+) -> impl Iterator<Item = (&'a L::KeyType, &'a L::ValueType, &'a R::ValueType)> + 'a
+where
+    L: AssociatedIterable + AssociatedMapOrd + 'a,
+    R: AssociatedIterable + AssociatedMapOrd<KeyType = L::KeyType> + 'a,
+    L::KeyType: Ord + 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    MergeJoin::new(left, right, false).filter_map(|(k, e)| match e {
+        EitherOrBoth::Both(v, w) => Some((k, v, w)),
+        _ => None,
+    })
+}
+
+/// Same as `left_join`, but walks both ordered maps in key order.
+pub fn merge_left_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+) -> impl Iterator<Item = (&'a L::KeyType, &'a L::ValueType, Option<&'a R::ValueType>)> + 'a
+where
+    L: AssociatedIterable + AssociatedMapOrd + 'a,
+    R: AssociatedIterable + AssociatedMapOrd<KeyType = L::KeyType> + 'a,
+    L::KeyType: Ord + 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    MergeJoin::new(left, right, false).filter_map(|(k, e)| match e {
+        EitherOrBoth::Both(v, w) => Some((k, v, Some(w))),
+        EitherOrBoth::Left(v) => Some((k, v, None)),
+        EitherOrBoth::Right(_) => None,
+    })
+}
+
+/// Same as `right_join`, but walks both ordered maps in key order.
+pub fn merge_right_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+) -> impl Iterator<Item = (&'a L::KeyType, Option<&'a L::ValueType>, &'a R::ValueType)> + 'a
+where
+    L: AssociatedIterable + AssociatedMapOrd + 'a,
+    R: AssociatedIterable + AssociatedMapOrd<KeyType = L::KeyType> + 'a,
+    L::KeyType: Ord + 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    merge_left_join(right, left).map(|(k, w, v)| (k, v, w))
+}
+
+/// Same as `outer_join`, but walks both ordered maps in key order.
+pub fn merge_outer_join<'a, L, R>(left: &'a L, right: &'a R) -> MergeJoin<'a, L, R>
+where
+    L: AssociatedIterable + AssociatedMapOrd + 'a,
+    R: AssociatedIterable + AssociatedMapOrd<KeyType = L::KeyType> + 'a,
+    L::KeyType: Ord + 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    MergeJoin::new(left, right, true)
+}
+
+/// Same as `anti_join`, but walks both ordered maps in key order.
+pub fn merge_anti_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+) -> impl Iterator<Item = (&'a L::KeyType, &'a L::ValueType)> + 'a
+where
+    L: AssociatedIterable + AssociatedMapOrd + 'a,
+    R: AssociatedIterable + AssociatedMapOrd<KeyType = L::KeyType> + 'a,
+    L::KeyType: Ord + 'a,
+    L::ValueType: 'a,
+    R::ValueType: 'a,
+{
+    MergeJoin::new(left, right, false).filter_map(|(k, e)| match e {
+        EitherOrBoth::Left(v) => Some((k, v)),
+        _ => None,
+    })
+}
@@ -1,6 +1,9 @@
 use crate::capacity::CapacityAware;
 
 /// Types that have allocator.
+///
+/// NOTE: `BTreeMap<K, V, A>`, `BTreeSet<T, A>` and `LinkedList<T, A>` don't expose their allocators,
+/// so only the other traits are implemented for them with any `A: Allocator`.
 pub trait AllocatorAware {
     /// Allocator type
     type Allocator: std::alloc::Allocator;
@@ -19,6 +22,12 @@ pub trait AllocatorCapacityConstructible: AllocatorAware + CapacityAware + Alloc
     fn with_capacity_in(capacity: Self::CapacityType, alloc: Self::Allocator) -> Self;
 }
 
+/// Types that can clone `Self` into a given allocator.
+pub trait AllocatorCloneable: AllocatorAware {
+    /// Clones `self`, allocating the clone in `alloc`.
+    fn clone_in(&self, alloc: Self::Allocator) -> Self;
+}
+
 mod impls {
     use super::*;
 
@@ -52,9 +61,53 @@ mod impls {
         };
     }
 
+    impl<T, A: std::alloc::Allocator> AllocatorAware for Box<[T], A> {
+        type Allocator = A;
+
+        fn allocator(&self) -> &Self::Allocator {
+            Box::allocator(self)
+        }
+    }
+
+    impl<T: Clone, A: std::alloc::Allocator> AllocatorCloneable for Box<[T], A> {
+        fn clone_in(&self, alloc: Self::Allocator) -> Self {
+            self.to_vec_in(alloc).into_boxed_slice()
+        }
+    }
+
+    impl<T: Clone, A: std::alloc::Allocator> AllocatorCloneable for Vec<T, A> {
+        fn clone_in(&self, alloc: Self::Allocator) -> Self {
+            self.as_slice().to_vec_in(alloc)
+        }
+    }
+
+    impl<T: Clone, A: std::alloc::Allocator> AllocatorCloneable for std::collections::VecDeque<T, A> {
+        fn clone_in(&self, alloc: Self::Allocator) -> Self {
+            let mut cloned = Self::with_capacity_in(self.len(), alloc);
+            cloned.extend(self.iter().cloned());
+            cloned
+        }
+    }
+
+    impl<T: Clone + Ord, A: std::alloc::Allocator> AllocatorCloneable for std::collections::BinaryHeap<T, A> {
+        fn clone_in(&self, alloc: Self::Allocator) -> Self {
+            let mut cloned = Vec::with_capacity_in(self.len(), alloc);
+            cloned.extend(self.iter().cloned());
+            cloned.into()
+        }
+    }
+
     allocator_impls!(
         [@Alloc T, A: std::alloc::Allocator => Vec<T, A>];
         [@AllocCtor T, A: std::alloc::Allocator => Vec<T, A>];
         [@AllocCapCtor T, A: std::alloc::Allocator => Vec<T, A>];
+
+        [@Alloc T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@AllocCtor T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@AllocCapCtor T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+
+        [@Alloc T, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
+        [@AllocCtor T: std::cmp::Ord, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
+        [@AllocCapCtor T: std::cmp::Ord, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
     );
 }
@@ -78,6 +78,150 @@ pub trait AssociatedSetOperation<S>: AssociatedSet {
     fn union<'a>(&'a self, other: &'a Self) -> Self::UnionIter<'a>;
 }
 
+/// Associated sets algebra producing owned sets or modifying `self` in place.
+///
+/// Owned operations keep the values and the hasher of `self`, as their `*_with` counterparts do.
+///
+/// NOTE: Unlike `AssociatedSetOperation<S>`, no borrowing iterator is involved, so no `S` is needed here.
+pub trait AssociatedSetAlgebra: AssociatedSet {
+    /// Consumes `self` and `other`, returns the union.
+    fn union_owned(self, other: Self) -> Self
+    where
+        Self: Owned + Sized;
+
+    /// Consumes `self` and `other`, returns the intersection.
+    fn intersection_owned(self, other: Self) -> Self
+    where
+        Self: Owned + Sized;
+
+    /// Consumes `self` and `other`, returns the values in `self` but not in `other`.
+    fn difference_owned(self, other: Self) -> Self
+    where
+        Self: Owned + Sized;
+
+    /// Consumes `self` and `other`, returns the values in `self` or in `other`, but not in both.
+    fn symmetric_difference_owned(self, other: Self) -> Self
+    where
+        Self: Owned + Sized;
+
+    /// Adds all values in `other` into `self`.
+    fn union_with(&mut self, other: &Self)
+    where
+        Self: Owned,
+        Self::ElemType: Clone;
+
+    /// Retains only the values in `self` that are also in `other`.
+    fn intersect_with(&mut self, other: &Self)
+    where
+        Self: Owned;
+
+    /// Removes all values in `other` from `self`.
+    fn subtract(&mut self, other: &Self)
+    where
+        Self: Owned;
+
+    /// Keeps the values in `self` or in `other`, but not in both.
+    fn symmetric_difference_with(&mut self, other: &Self)
+    where
+        Self: Owned,
+        Self::ElemType: Clone;
+}
+
+/// Wrapper of owned sets supporting `|`, `&`, `-` and `^` operators.
+///
+/// Binary operators consume both operands, while the `*Assign` operators take the right operand by reference.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetAlgebra<C>(pub C);
+
+impl<C> SetAlgebra<C> {
+    /// Unwraps the inner set.
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C> From<C> for SetAlgebra<C> {
+    fn from(set: C) -> Self {
+        SetAlgebra(set)
+    }
+}
+
+impl<C> std::ops::Deref for SetAlgebra<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.0
+    }
+}
+
+impl<C> std::ops::DerefMut for SetAlgebra<C> {
+    fn deref_mut(&mut self) -> &mut C {
+        &mut self.0
+    }
+}
+
+impl<C: AssociatedSetAlgebra + Owned> std::ops::BitOr for SetAlgebra<C> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        SetAlgebra(self.0.union_owned(rhs.0))
+    }
+}
+
+impl<C: AssociatedSetAlgebra + Owned> std::ops::BitAnd for SetAlgebra<C> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        SetAlgebra(self.0.intersection_owned(rhs.0))
+    }
+}
+
+impl<C: AssociatedSetAlgebra + Owned> std::ops::Sub for SetAlgebra<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        SetAlgebra(self.0.difference_owned(rhs.0))
+    }
+}
+
+impl<C: AssociatedSetAlgebra + Owned> std::ops::BitXor for SetAlgebra<C> {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        SetAlgebra(self.0.symmetric_difference_owned(rhs.0))
+    }
+}
+
+impl<C: AssociatedSetAlgebra + Owned> std::ops::BitOrAssign<&SetAlgebra<C>> for SetAlgebra<C>
+where
+    C::ElemType: Clone,
+{
+    fn bitor_assign(&mut self, rhs: &Self) {
+        self.0.union_with(&rhs.0)
+    }
+}
+
+impl<C: AssociatedSetAlgebra + Owned> std::ops::BitAndAssign<&SetAlgebra<C>> for SetAlgebra<C> {
+    fn bitand_assign(&mut self, rhs: &Self) {
+        self.0.intersect_with(&rhs.0)
+    }
+}
+
+impl<C: AssociatedSetAlgebra + Owned> std::ops::SubAssign<&SetAlgebra<C>> for SetAlgebra<C> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        self.0.subtract(&rhs.0)
+    }
+}
+
+impl<C: AssociatedSetAlgebra + Owned> std::ops::BitXorAssign<&SetAlgebra<C>> for SetAlgebra<C>
+where
+    C::ElemType: Clone,
+{
+    fn bitxor_assign(&mut self, rhs: &Self) {
+        self.0.symmetric_difference_with(&rhs.0)
+    }
+}
+
 /// Associated collections as ordered sets
 pub trait AssociatedSetOrd: AssociatedSet
 // where
@@ -295,6 +439,60 @@ mod impls {
             }
             assoc_impls!($($tail)*);
         };
+        ([@SetAlgebra $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> AssociatedSetAlgebra for $t {
+                fn union_owned(mut self, other: Self) -> Self {
+                    std::iter::Extend::extend(&mut self, other);
+                    self
+                }
+
+                fn intersection_owned(mut self, other: Self) -> Self {
+                    <$t>::retain(&mut self, |v| other.contains(v));
+                    self
+                }
+
+                fn difference_owned(mut self, other: Self) -> Self {
+                    <$t>::retain(&mut self, |v| !other.contains(v));
+                    self
+                }
+
+                fn symmetric_difference_owned(mut self, other: Self) -> Self {
+                    for v in other {
+                        if !<$t>::remove(&mut self, &v) {
+                            <$t>::insert(&mut self, v);
+                        }
+                    }
+                    self
+                }
+
+                fn union_with(&mut self, other: &Self)
+                where
+                    Self::ElemType: Clone,
+                {
+                    std::iter::Extend::extend(self, other.iter().cloned())
+                }
+
+                fn intersect_with(&mut self, other: &Self) {
+                    <$t>::retain(self, |v| other.contains(v))
+                }
+
+                fn subtract(&mut self, other: &Self) {
+                    <$t>::retain(self, |v| !other.contains(v))
+                }
+
+                fn symmetric_difference_with(&mut self, other: &Self)
+                where
+                    Self::ElemType: Clone,
+                {
+                    for v in other.iter() {
+                        if !<$t>::remove(self, v) {
+                            <$t>::insert(self, v.clone());
+                        }
+                    }
+                }
+            }
+            assoc_impls!($($tail)*);
+        };
         ([@OrdSet <$($targ: ty),*> => $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
             impl<$($args $(: $bound $(+ $others)*)?),*> AssociatedSetOrd for $t {
                 fn append(&mut self, other: &mut Self) {
@@ -412,7 +610,7 @@ mod impls {
     assoc_impls!(
         [@Set <K> => K: std::cmp::Eq | std::hash::Hash, S: std::hash::BuildHasher =>
             std::collections::HashSet<K, S>];
-        [@Set <K> => K: std::cmp::Ord => std::collections::BTreeSet<K>];
+        [@Set <K> => K: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeSet<K, A>];
         [@SetOp <S> => K: std::cmp::Eq | std::hash::Hash, S: std::hash::BuildHasher =>
             std::collections::HashSet<K, S>,
             std::collections::hash_set::Difference<'a, K, S>,
@@ -420,19 +618,21 @@ mod impls {
             std::collections::hash_set::SymmetricDifference<'a, K, S>,
             std::collections::hash_set::Union<'a, K, S>
         ];
-        [@SetOp <()> => K: std::cmp::Ord =>
-            std::collections::BTreeSet<K>,
-            std::collections::btree_set::Difference<'a, K>,
-            std::collections::btree_set::Intersection<'a, K>,
+        [@SetOp <()> => K: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone =>
+            std::collections::BTreeSet<K, A>,
+            std::collections::btree_set::Difference<'a, K, A>,
+            std::collections::btree_set::Intersection<'a, K, A>,
             std::collections::btree_set::SymmetricDifference<'a, K>,
             std::collections::btree_set::Union<'a, K>
         ];
-        [@OrdSet <K> => K: std::cmp::Ord => std::collections::BTreeSet<K>];
+        [@SetAlgebra K: std::cmp::Eq | std::hash::Hash, S: std::hash::BuildHasher => std::collections::HashSet<K, S>];
+        [@SetAlgebra K: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeSet<K, A>];
+        [@OrdSet <K> => K: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeSet<K, A>];
 
         [@Map <K, V> => K: std::cmp::Eq | std::hash::Hash, V, S: std::hash::BuildHasher =>
             std::collections::HashMap<K, V, S>
         ];
-        [@Map <K, V> => K: std::cmp::Ord, V => std::collections::BTreeMap<K, V>];
+        [@Map <K, V> => K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeMap<K, V, A>];
         [@MapIter <S> => K: std::cmp::Eq | std::hash::Hash, V, S: std::hash::BuildHasher =>
             std::collections::HashMap<K, V, S>,
             std::collections::hash_map::OccupiedError<'a, K, V>,
@@ -440,14 +640,14 @@ mod impls {
             std::collections::hash_map::Values<'a, K, V>,
             std::collections::hash_map::ValuesMut<'a, K, V>
         ];
-        [@MapIter <()> => K: std::cmp::Ord, V =>
-            std::collections::BTreeMap<K, V>,
-            std::collections::btree_map::OccupiedError<'a, K, V>,
+        [@MapIter <()> => K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone =>
+            std::collections::BTreeMap<K, V, A>,
+            std::collections::btree_map::OccupiedError<'a, K, V, A>,
             std::collections::btree_map::Keys<'a, K, V>,
             std::collections::btree_map::Values<'a, K, V>,
             std::collections::btree_map::ValuesMut<'a, K, V>
         ];
 
-        [@OrdMap <K, V> => K: std::cmp::Ord, V => std::collections::BTreeMap<K, V>];
+        [@OrdMap <K, V> => K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeMap<K, V, A>];
     );
 }
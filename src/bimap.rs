@@ -0,0 +1,407 @@
+//! Bidirectional maps, keeping a map and its inverse in sync.
+use crate::{
+    associated::AssociatedMap,
+    elem::{AssociatedCollectionTrait, CollectionTrait, Owned},
+    exact_size::ExactSized,
+    iter::AssociatedIterable,
+    misc::{AssociatedContains, EmptyConstructible, Erasable},
+};
+use std::{collections::HashMap, fmt, iter::FromIterator, marker::PhantomData};
+
+/// What to do when an inserted value is already paired with another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InsertPolicy {
+    /// Fails if either value is already paired.
+    Reject,
+    /// Removes the pairs holding either value.
+    Overwrite,
+    /// Fails if the left value is already paired, removes the pair holding the right value.
+    RejectLeft,
+    /// Fails if the right value is already paired, removes the pair holding the left value.
+    RejectRight,
+}
+
+/// Pairs removed by an insertion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Overwritten<L, R> {
+    /// No pair was removed.
+    Neither,
+    /// The pair holding the left value was removed.
+    Left(L, R),
+    /// The pair holding the right value was removed.
+    Right(L, R),
+    /// The same pair was already present.
+    Pair(L, R),
+    /// Both the pair holding the left value and the one holding the right value were removed.
+    Both((L, R), (L, R)),
+}
+
+/// One-to-one map, where each left value is paired with one right value and vice versa.
+///
+/// Left values are the keys of `ML`, and right values the keys of `MR`. The collection traits view it as a map from
+/// left to right values, while `inverse()` views it as a map from right to left values.
+///
+/// Only the read-side traits are implemented: values are keys of the inverse map, so they can't be modified in place
+/// through `AssociatedMap::get_mut`. Pairs are inserted, removed and cleared by the inherent methods.
+pub struct BiMap<L, R, ML = HashMap<L, R>, MR = HashMap<R, L>> {
+    left: ML,
+    right: MR,
+    _marker: PhantomData<fn() -> (L, R)>,
+}
+
+/// Inverse view of a `BiMap<L, R, ML, MR>`, as a map from right to left values.
+///
+/// Left and right are swapped, e.g. `InsertPolicy::RejectLeft` rejects right values of the `BiMap<L, R, ML, MR>`
+/// already paired.
+#[repr(transparent)]
+pub struct Inverse<L, R, ML = HashMap<L, R>, MR = HashMap<R, L>>(BiMap<L, R, ML, MR>);
+
+impl<L, R> BiMap<L, R> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::from_maps(HashMap::new(), HashMap::new())
+    }
+}
+
+impl<L, R, ML, MR> BiMap<L, R, ML, MR> {
+    /// Pairs two maps, which must be inverses of each other.
+    fn from_maps(left: ML, right: MR) -> Self {
+        BiMap {
+            left,
+            right,
+            _marker: PhantomData,
+        }
+    }
+
+    fn maps(&self) -> (&ML, &MR) {
+        (&self.left, &self.right)
+    }
+
+    fn maps_mut(&mut self) -> (&mut ML, &mut MR) {
+        (&mut self.left, &mut self.right)
+    }
+
+    /// Returns the map from left to right values.
+    pub fn left_map(&self) -> &ML {
+        &self.left
+    }
+
+    /// Returns the map from right to left values.
+    pub fn right_map(&self) -> &MR {
+        &self.right
+    }
+
+    /// Views `self` as a map from right to left values.
+    pub fn inverse(&self) -> &Inverse<L, R, ML, MR> {
+        // SAFETY: `Inverse<L, R, ML, MR>` is a transparent wrapper of `BiMap<L, R, ML, MR>`
+        unsafe { &*(self as *const Self as *const Inverse<L, R, ML, MR>) }
+    }
+
+    /// Views `self` as a mutable map from right to left values.
+    pub fn inverse_mut(&mut self) -> &mut Inverse<L, R, ML, MR> {
+        // SAFETY: `Inverse<L, R, ML, MR>` is a transparent wrapper of `BiMap<L, R, ML, MR>`
+        unsafe { &mut *(self as *mut Self as *mut Inverse<L, R, ML, MR>) }
+    }
+
+    /// Converts `self` into a map from right to left values, in O(1).
+    pub fn into_inverse(self) -> BiMap<R, L, MR, ML> {
+        BiMap::from_maps(self.right, self.left)
+    }
+}
+
+impl<L, R, ML, MR> Inverse<L, R, ML, MR> {
+    fn from_maps(right: MR, left: ML) -> Self {
+        Inverse(BiMap::from_maps(left, right))
+    }
+
+    fn maps(&self) -> (&MR, &ML) {
+        (&self.0.right, &self.0.left)
+    }
+
+    fn maps_mut(&mut self) -> (&mut MR, &mut ML) {
+        (&mut self.0.right, &mut self.0.left)
+    }
+
+    /// Views `self` as a map from left to right values again.
+    pub fn inverse(&self) -> &BiMap<L, R, ML, MR> {
+        &self.0
+    }
+
+    /// Views `self` as a mutable map from left to right values again.
+    pub fn inverse_mut(&mut self) -> &mut BiMap<L, R, ML, MR> {
+        &mut self.0
+    }
+}
+
+/// Inserts the pair `(key, value)` into `forward` and `(value, key)` into `backward`, applying `policy` on conflicts.
+fn insert_pair<K: Clone, V: Clone, MK, MV>(
+    forward: &mut MK,
+    backward: &mut MV,
+    key: K,
+    value: V,
+    policy: InsertPolicy,
+) -> Result<Overwritten<K, V>, (K, V)>
+where
+    MK: AssociatedMap<KeyType = K, ValueType = V> + Owned,
+    MV: AssociatedMap<KeyType = V, ValueType = K> + Owned,
+{
+    let (key_paired, value_paired) = (forward.contains_key(&key), backward.contains_key(&value));
+    let reject = match policy {
+        InsertPolicy::Reject => key_paired || value_paired,
+        InsertPolicy::Overwrite => false,
+        InsertPolicy::RejectLeft => key_paired,
+        InsertPolicy::RejectRight => value_paired,
+    };
+    if reject {
+        return Err((key, value));
+    }
+    let by_key = forward.remove_entry(&key);
+    if let Some((_, old_value)) = &by_key {
+        backward.remove(old_value);
+    }
+    let by_value = backward.remove_entry(&value);
+    if let Some((_, old_key)) = &by_value {
+        forward.remove(old_key);
+    }
+    forward.insert(key.clone(), value.clone());
+    backward.insert(value, key);
+    Ok(match (by_key, by_value) {
+        (None, None) => Overwritten::Neither,
+        // NOTE: removing the pair of `key` already removed `value` from `backward` if they were paired
+        (Some((k, v)), None) if value_paired => Overwritten::Pair(k, v),
+        (Some((k, v)), None) => Overwritten::Left(k, v),
+        (None, Some((v, k))) => Overwritten::Right(k, v),
+        (Some(by_key), Some((v, k))) => Overwritten::Both(by_key, (k, v)),
+    })
+}
+
+mod impls {
+    use super::*;
+
+    macro_rules! bimap_impls {
+        () => {};
+        ([$t: ty => $k: ident, $v: ident, $mk: ident, $mv: ident]; $($tail:tt)*) => {
+            impl<L, R, ML, MR> $t
+            where
+                $k: Clone,
+                $v: Clone,
+                $mk: AssociatedMap<KeyType = $k, ValueType = $v> + CollectionTrait<ElemType = ($k, $v)> + Owned,
+                $mv: AssociatedMap<KeyType = $v, ValueType = $k> + Owned,
+            {
+                /// Inserts a pair, applying `policy` if either value is already paired.
+                ///
+                /// Returns the removed pairs, or the given pair back if rejected.
+                pub fn insert_with(
+                    &mut self,
+                    key: $k,
+                    value: $v,
+                    policy: InsertPolicy,
+                ) -> Result<Overwritten<$k, $v>, ($k, $v)> {
+                    let (forward, backward) = self.maps_mut();
+                    insert_pair(forward, backward, key, value, policy)
+                }
+
+                /// Inserts a pair, removing the pairs holding either value.
+                pub fn insert_overwrite(&mut self, key: $k, value: $v) -> Overwritten<$k, $v> {
+                    match self.insert_with(key, value, InsertPolicy::Overwrite) {
+                        Ok(overwritten) => overwritten,
+                        Err(_) => unreachable!("overwriting never rejects"),
+                    }
+                }
+
+                /// Inserts a pair if neither value is already paired, otherwise returns it back.
+                pub fn insert_no_overwrite(&mut self, key: $k, value: $v) -> Result<(), ($k, $v)> {
+                    self.insert_with(key, value, InsertPolicy::Reject).map(|_| ())
+                }
+
+                /// Inserts a pair, removing the pairs holding either value, and returns the old value of `key`.
+                pub fn insert(&mut self, key: $k, value: $v) -> Option<$v> {
+                    match self.insert_overwrite(key, value) {
+                        Overwritten::Left(_, v) | Overwritten::Pair(_, v) | Overwritten::Both((_, v), _) => Some(v),
+                        Overwritten::Neither | Overwritten::Right(..) => None,
+                    }
+                }
+
+                /// Removes the pair holding `key`, returning its value.
+                pub fn remove(&mut self, key: &$k) -> Option<$v> {
+                    self.remove_entry(key).map(|(_, value)| value)
+                }
+
+                /// Removes the pair holding `key`.
+                pub fn remove_entry(&mut self, key: &$k) -> Option<($k, $v)> {
+                    let (forward, backward) = self.maps_mut();
+                    let (key, value) = forward.remove_entry(key)?;
+                    backward.remove(&value);
+                    Some((key, value))
+                }
+            }
+
+            impl<L, R, ML, MR> $t
+            where
+                $mk: Erasable + Owned,
+                $mv: Erasable + Owned,
+            {
+                /// Removes all pairs.
+                pub fn clear(&mut self) {
+                    let (forward, backward) = self.maps_mut();
+                    forward.clear();
+                    backward.clear();
+                }
+            }
+
+            impl<L, R, ML, MR> $t
+            where
+                $mk: AssociatedMap<KeyType = $k, ValueType = $v>,
+            {
+                /// Returns the value paired with `key`.
+                pub fn get(&self, key: &$k) -> Option<&$v> {
+                    self.maps().0.get(key)
+                }
+
+                /// Returns the pair holding `key`.
+                pub fn get_key_value(&self, key: &$k) -> Option<(&$k, &$v)> {
+                    self.maps().0.get_key_value(key)
+                }
+            }
+
+            impl<L, R, ML, MR> CollectionTrait for $t {
+                type ElemType = ($k, $v);
+            }
+
+            impl<L, R, ML, MR> AssociatedCollectionTrait for $t {
+                type KeyType = $k;
+                type ValueType = $v;
+            }
+
+            impl<L, R, ML, MR> IntoIterator for $t
+            where
+                $mk: IntoIterator<Item = ($k, $v)>,
+            {
+                type Item = ($k, $v);
+                type IntoIter = <$mk as IntoIterator>::IntoIter;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    let (forward, _) = self.into_maps();
+                    forward.into_iter()
+                }
+            }
+
+            impl<L, R, ML, MR> Extend<($k, $v)> for $t
+            where
+                $k: Clone,
+                $v: Clone,
+                $mk: AssociatedMap<KeyType = $k, ValueType = $v> + CollectionTrait<ElemType = ($k, $v)> + Owned,
+                $mv: AssociatedMap<KeyType = $v, ValueType = $k> + Owned,
+            {
+                fn extend<I: IntoIterator<Item = ($k, $v)>>(&mut self, iter: I) {
+                    iter.into_iter().for_each(|(key, value)| {
+                        self.insert_overwrite(key, value);
+                    })
+                }
+            }
+
+            impl<L, R, ML, MR> FromIterator<($k, $v)> for $t
+            where
+                $k: Clone,
+                $v: Clone,
+                $mk: AssociatedMap<KeyType = $k, ValueType = $v> + CollectionTrait<ElemType = ($k, $v)> + EmptyConstructible + Owned,
+                $mv: AssociatedMap<KeyType = $v, ValueType = $k> + EmptyConstructible + Owned,
+            {
+                fn from_iter<I: IntoIterator<Item = ($k, $v)>>(iter: I) -> Self {
+                    let mut map = Self::from_maps($mk::new(), $mv::new());
+                    map.extend(iter);
+                    map
+                }
+            }
+
+            impl<L, R, ML: Clone, MR: Clone> Clone for $t {
+                fn clone(&self) -> Self {
+                    let (forward, backward) = self.maps();
+                    Self::from_maps(forward.clone(), backward.clone())
+                }
+            }
+
+            impl<L, R, ML: Default, MR: Default> Default for $t {
+                fn default() -> Self {
+                    Self::from_maps(Default::default(), Default::default())
+                }
+            }
+
+            impl<L, R, ML, MR> fmt::Debug for $t
+            where
+                $mk: fmt::Debug,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.maps().0.fmt(f)
+                }
+            }
+
+            impl<L, R, ML, MR> PartialEq for $t
+            where
+                $mk: PartialEq,
+            {
+                fn eq(&self, other: &Self) -> bool {
+                    self.maps().0 == other.maps().0
+                }
+            }
+
+            impl<L, R, ML, MR> Eq for $t where $mk: Eq {}
+
+            impl<L, R, ML, MR> ExactSized for $t
+            where
+                $mk: ExactSized<SizeType = usize>,
+            {
+                fn is_empty(&self) -> bool {
+                    self.maps().0.is_empty()
+                }
+
+                fn len(&self) -> usize {
+                    self.maps().0.len()
+                }
+            }
+
+            impl<L, R, ML, MR> AssociatedIterable for $t
+            where
+                $mk: AssociatedIterable<KeyType = $k, ValueType = $v>,
+            {
+                type Iter<'a>
+                where
+                    $k: 'a,
+                    $v: 'a,
+                = <$mk as AssociatedIterable>::Iter<'a>;
+
+                fn iter(&self) -> Self::Iter<'_> {
+                    self.maps().0.iter()
+                }
+            }
+
+            impl<L, R, ML, MR> AssociatedContains for $t
+            where
+                $mk: AssociatedContains<KeyType = $k, ValueType = $v>,
+            {
+                fn contains_key(&self, value: &$k) -> bool {
+                    self.maps().0.contains_key(value)
+                }
+            }
+
+            bimap_impls!($($tail)*);
+        };
+    }
+
+    impl<L, R, ML, MR> BiMap<L, R, ML, MR> {
+        fn into_maps(self) -> (ML, MR) {
+            (self.left, self.right)
+        }
+    }
+
+    impl<L, R, ML, MR> Inverse<L, R, ML, MR> {
+        fn into_maps(self) -> (MR, ML) {
+            (self.0.right, self.0.left)
+        }
+    }
+
+    bimap_impls!(
+        [BiMap<L, R, ML, MR> => L, R, ML, MR];
+        [Inverse<L, R, ML, MR> => R, L, MR, ML];
+    );
+}
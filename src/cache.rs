@@ -0,0 +1,166 @@
+//! Maps of fixed capacity, evicting entries by recency or frequency of use.
+//!
+//! Both `LruCache<K, V, S>` and `LfuCache<K, V, S>` run every operation in O(1), over a `HashMap<K, usize, S>` of
+//! indices into linked lists of entries.
+use crate::{
+    associated::AssociatedMap,
+    capacity::CapacityAware,
+    elem::{AssociatedCollectionTrait, CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    iter::AssociatedIterable,
+    misc::{AssociatedContains, Erasable},
+};
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+pub mod lfu;
+mod list;
+pub mod lru;
+
+pub use self::{lfu::LfuCache, lru::LruCache};
+
+/// Callback called with the entries evicted to make room for new ones.
+pub type OnEvict<K, V> = Box<dyn FnMut(K, V) + Send>;
+
+/// Maps of fixed capacity, evicting an entry chosen by their policy when full.
+///
+/// `get` records an access for the eviction policy, while `peek` doesn't. As `AssociatedMap::get` only borrows
+/// `self`, it behaves as `peek` for caches, and `AssociatedMap::insert` as `put`.
+pub trait Cache: AssociatedCollectionTrait {
+    /// Returns the value of `key`, recording the access.
+    fn get(&mut self, key: &Self::KeyType) -> Option<&Self::ValueType>;
+
+    /// Returns the value of `key`, without recording the access.
+    fn peek(&self, key: &Self::KeyType) -> Option<&Self::ValueType>;
+
+    /// Inserts a key-value pair, recording the access, and returns the previous value of `key`.
+    ///
+    /// If `key` is absent and the cache is full, the next victim is evicted first.
+    fn put(&mut self, key: Self::KeyType, value: Self::ValueType) -> Option<Self::ValueType>;
+
+    /// Returns the entry to be evicted next.
+    fn peek_victim(&self) -> Option<(&Self::KeyType, &Self::ValueType)>;
+
+    /// Removes the entry to be evicted next, without calling the eviction callback.
+    fn pop_victim(&mut self) -> Option<(Self::KeyType, Self::ValueType)>;
+
+    /// Changes the capacity, evicting entries until they fit.
+    fn set_capacity(&mut self, capacity: usize);
+
+    /// Sets the callback called with evicted entries, replacing the previous one.
+    fn set_on_evict<F: FnMut(Self::KeyType, Self::ValueType) + Send + 'static>(&mut self, on_evict: F);
+}
+
+mod impls {
+    use super::*;
+
+    macro_rules! cache_impls {
+        () => {};
+        ([$t: ty, $iter: ty]; $($tail:tt)*) => {
+            impl<K, V, S> CollectionTrait for $t {
+                type ElemType = (K, V);
+            }
+
+            impl<K, V, S> AssociatedCollectionTrait for $t {
+                type KeyType = K;
+                type ValueType = V;
+            }
+
+            impl<K, V, S> Mutable for $t {}
+
+            impl<K, V, S> Owned for $t {}
+
+            impl<K, V, S> ExactSized for $t {
+                fn is_empty(&self) -> bool {
+                    <$t>::is_empty(self)
+                }
+
+                fn len(&self) -> usize {
+                    <$t>::len(self)
+                }
+            }
+
+            impl<K, V, S> CapacityAware for $t {
+                fn capacity(&self) -> usize {
+                    <$t>::capacity(self)
+                }
+            }
+
+            impl<K, V, S> AssociatedIterable for $t {
+                type Iter<'a>
+                where
+                    K: 'a,
+                    V: 'a,
+                = $iter;
+
+                fn iter(&self) -> Self::Iter<'_> {
+                    <$t>::iter(self)
+                }
+            }
+
+            impl<K: Eq + Hash, V, S: BuildHasher> AssociatedContains for $t {
+                fn contains_key(&self, key: &K) -> bool {
+                    <$t>::contains_key(self, key)
+                }
+            }
+
+            impl<K: Eq + Hash + Clone, V, S: BuildHasher> AssociatedMap for $t {
+                /// Same as `Cache::put`.
+                fn insert(&mut self, key: K, value: V) -> Option<V> {
+                    Cache::put(self, key, value)
+                }
+
+                fn remove(&mut self, key: &K) -> Option<V> {
+                    <$t>::remove_entry(self, key).map(|(_, value)| value)
+                }
+
+                fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+                    <$t>::remove_entry(self, key)
+                }
+
+                /// Same as `Cache::peek`.
+                fn get(&self, key: &K) -> Option<&V> {
+                    Cache::peek(self, key)
+                }
+
+                /// Doesn't record the access, as `Cache::peek`.
+                fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+                    <$t>::peek_mut(self, key)
+                }
+
+                fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+                    <$t>::peek_key_value(self, key)
+                }
+            }
+
+            impl<K, V, S> Erasable for $t {
+                /// Removes all entries, without calling the eviction callback.
+                fn clear(&mut self) {
+                    <$t>::clear(self)
+                }
+            }
+
+            impl<K: Eq + Hash + Clone, V, S: BuildHasher> Extend<(K, V)> for $t {
+                fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+                    iter.into_iter().for_each(|(key, value)| {
+                        Cache::put(self, key, value);
+                    })
+                }
+            }
+
+            impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for $t {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_map().entries(<$t>::iter(self)).finish()
+                }
+            }
+            cache_impls!($($tail)*);
+        };
+    }
+
+    cache_impls!(
+        [LruCache<K, V, S>, lru::Iter<'a, K, V>];
+        [LfuCache<K, V, S>, lfu::Iter<'a, K, V>];
+    );
+}
@@ -0,0 +1,322 @@
+//! Cache evicting the least frequently used entry, and the least recently used one among them.
+use super::{
+    list::{List, Slab, NIL},
+    Cache, OnEvict,
+};
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash},
+    mem,
+};
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    bucket: usize,
+}
+
+/// Entries used as many times, most recently used first.
+struct Bucket {
+    frequency: usize,
+    entries: List,
+}
+
+/// Cache of fixed capacity evicting the least frequently used entry, breaking ties by recency.
+///
+/// Iterates from the most frequently used entry, and from the most recently used one among entries used as many
+/// times, i.e. in the reverse order of eviction.
+///
+/// NOTE: keys are stored twice, in the index map and in the entries.
+pub struct LfuCache<K, V, S = RandomState> {
+    map: HashMap<K, usize, S>,
+    entries: Slab<Entry<K, V>>,
+    buckets: Slab<Bucket>,
+    /// Buckets in ascending order of frequency
+    frequencies: List,
+    capacity: usize,
+    on_evict: Option<OnEvict<K, V>>,
+}
+
+impl<K, V> LfuCache<K, V> {
+    /// Creates an empty cache holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S> LfuCache<K, V, S> {
+    /// Creates an empty cache holding at most `capacity` entries, hashing keys with `hash_builder`.
+    pub fn with_hasher(capacity: usize, hash_builder: S) -> Self {
+        LfuCache {
+            map: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+            entries: Slab::with_capacity(capacity),
+            buckets: Slab::with_capacity(0),
+            frequencies: List::EMPTY,
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if there is no entry.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Iterates over the entries in the reverse order of eviction.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: &self.entries,
+            buckets: &self.buckets,
+            bucket: self.frequencies.tail,
+            index: NIL,
+            len: self.len(),
+        }
+    }
+
+    /// Removes all entries, without calling the eviction callback.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.entries.clear();
+        self.buckets.clear();
+        self.frequencies = List::EMPTY;
+    }
+
+    /// Unlinks the entry at `index` from its bucket, removing the bucket if left empty.
+    fn unlink(&mut self, index: usize) {
+        let bucket = self.entries.get(index).bucket;
+        let list = &mut self.buckets.get_mut(bucket).entries;
+        self.entries.unlink(list, index);
+        if list.is_empty() {
+            self.buckets.unlink(&mut self.frequencies, bucket);
+            self.buckets.remove(bucket);
+        }
+    }
+
+    /// Links the entry at `index` at the front of `bucket`.
+    fn link(&mut self, index: usize, bucket: usize) {
+        self.entries
+            .link_after(&mut self.buckets.get_mut(bucket).entries, NIL, index);
+        self.entries.get_mut(index).bucket = bucket;
+    }
+
+    /// Returns the bucket of `frequency` right after `at`, or at the front if `at` is `NIL`, creating it if needed.
+    fn bucket_after(&mut self, at: usize, frequency: usize) -> usize {
+        let next = if at == NIL {
+            self.frequencies.head
+        } else {
+            self.buckets.next(at)
+        };
+        if next != NIL && self.buckets.get(next).frequency == frequency {
+            return next;
+        }
+        let bucket = self.buckets.insert(Bucket {
+            frequency,
+            entries: List::EMPTY,
+        });
+        self.buckets.link_after(&mut self.frequencies, at, bucket);
+        bucket
+    }
+
+    /// Records one more use of the entry at `index`.
+    fn touch(&mut self, index: usize) {
+        let bucket = self.entries.get(index).bucket;
+        let frequency = self.buckets.get(bucket).frequency;
+        let next = self.bucket_after(bucket, frequency + 1);
+        self.unlink(index);
+        self.link(index, next);
+    }
+
+    fn victim(&self) -> usize {
+        match self.frequencies.head {
+            NIL => NIL,
+            bucket => self.buckets.get(bucket).entries.tail,
+        }
+    }
+
+    fn evict(&mut self, key: K, value: V) {
+        if let Some(on_evict) = &mut self.on_evict {
+            on_evict(key, value);
+        }
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> LfuCache<K, V, S> {
+    /// Returns true if `key` has an entry, without recording the access.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the number of uses of `key`, counting its insertion.
+    pub fn frequency(&self, key: &K) -> Option<usize> {
+        let bucket = self.entries.get(*self.map.get(key)?).bucket;
+        Some(self.buckets.get(bucket).frequency)
+    }
+
+    /// Returns the entry of `key`, without recording the access.
+    pub fn peek_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        let entry = self.entries.get(*self.map.get(key)?);
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the value of `key`, without recording the access.
+    pub fn peek_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = *self.map.get(key)?;
+        Some(&mut self.entries.get_mut(index).value)
+    }
+
+    /// Removes the entry of `key`, without calling the eviction callback.
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let index = self.map.remove(key)?;
+        self.unlink(index);
+        let entry = self.entries.remove(index);
+        Some((entry.key, entry.value))
+    }
+}
+
+impl<K: Eq + Hash + Clone, V, S: BuildHasher> Cache for LfuCache<K, V, S> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let index = *self.map.get(key)?;
+        self.touch(index);
+        Some(&self.entries.get(index).value)
+    }
+
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key).map(|index| &self.entries.get(*index).value)
+    }
+
+    fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&index) = self.map.get(&key) {
+            self.touch(index);
+            return Some(mem::replace(&mut self.entries.get_mut(index).value, value));
+        }
+        if self.capacity == 0 {
+            self.evict(key, value);
+            return None;
+        }
+        if self.len() == self.capacity {
+            if let Some((key, value)) = self.pop_victim() {
+                self.evict(key, value);
+            }
+        }
+        let bucket = self.bucket_after(NIL, 1);
+        let index = self.entries.insert(Entry {
+            key: key.clone(),
+            value,
+            bucket,
+        });
+        self.link(index, bucket);
+        self.map.insert(key, index);
+        None
+    }
+
+    fn peek_victim(&self) -> Option<(&K, &V)> {
+        match self.victim() {
+            NIL => None,
+            index => {
+                let entry = self.entries.get(index);
+                Some((&entry.key, &entry.value))
+            }
+        }
+    }
+
+    fn pop_victim(&mut self) -> Option<(K, V)> {
+        let index = match self.victim() {
+            NIL => return None,
+            index => index,
+        };
+        self.unlink(index);
+        let entry = self.entries.remove(index);
+        self.map.remove(&entry.key);
+        Some((entry.key, entry.value))
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.len() > capacity {
+            if let Some((key, value)) = self.pop_victim() {
+                self.evict(key, value);
+            }
+        }
+    }
+
+    fn set_on_evict<F: FnMut(K, V) + Send + 'static>(&mut self, on_evict: F) {
+        self.on_evict = Some(Box::new(on_evict));
+    }
+}
+
+/// Iterator over the entries of a `LfuCache<K, V, S>`, in the reverse order of eviction.
+pub struct Iter<'a, K, V> {
+    entries: &'a Slab<Entry<K, V>>,
+    buckets: &'a Slab<Bucket>,
+    /// Next bucket to iterate, in descending order of frequency
+    bucket: usize,
+    index: usize,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index == NIL {
+            if self.bucket == NIL {
+                return None;
+            }
+            self.index = self.buckets.get(self.bucket).entries.head;
+            self.bucket = self.buckets.prev(self.bucket);
+        }
+        let entry = self.entries.get(self.index);
+        self.index = self.entries.next(self.index);
+        self.len -= 1;
+        Some((&entry.key, &entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+/// Owned iterator over the entries of a `LfuCache<K, V, S>`, in the reverse order of eviction.
+pub struct IntoIter<K, V>(std::vec::IntoIter<Entry<K, V>>);
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|entry| (entry.key, entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V, S> IntoIterator for LfuCache<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    /// Iterates in the reverse order of eviction.
+    fn into_iter(mut self) -> Self::IntoIter {
+        let mut entries = Vec::with_capacity(self.len());
+        let mut bucket = self.frequencies.tail;
+        while bucket != NIL {
+            self.entries.take_list(self.buckets.get(bucket).entries, &mut entries);
+            bucket = self.buckets.prev(bucket);
+        }
+        IntoIter(entries.into_iter())
+    }
+}
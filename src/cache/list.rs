@@ -0,0 +1,126 @@
+//! Doubly linked lists threaded through a slab of nodes, addressed by indices.
+
+/// Index of no node.
+pub(crate) const NIL: usize = usize::MAX;
+
+struct Node<T> {
+    value: Option<T>,
+    prev: usize,
+    next: usize,
+}
+
+/// Nodes of any number of lists, reusing the slots of removed ones.
+pub(crate) struct Slab<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+}
+
+/// Ends of a list of nodes in a `Slab<T>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct List {
+    pub(crate) head: usize,
+    pub(crate) tail: usize,
+}
+
+impl List {
+    pub(crate) const EMPTY: List = List { head: NIL, tail: NIL };
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.head == NIL
+    }
+}
+
+impl<T> Slab<T> {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Slab {
+            nodes: Vec::with_capacity(capacity),
+            free: Vec::new(),
+        }
+    }
+
+    /// Adds an unlinked node holding `value`, returns its index.
+    pub(crate) fn insert(&mut self, value: T) -> usize {
+        let node = Node {
+            value: Some(value),
+            prev: NIL,
+            next: NIL,
+        };
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Removes the node at `index`, which must have been unlinked.
+    pub(crate) fn remove(&mut self, index: usize) -> T {
+        self.free.push(index);
+        self.nodes[index].value.take().expect("vacant node")
+    }
+
+    pub(crate) fn get(&self, index: usize) -> &T {
+        self.nodes[index].value.as_ref().expect("vacant node")
+    }
+
+    pub(crate) fn get_mut(&mut self, index: usize) -> &mut T {
+        self.nodes[index].value.as_mut().expect("vacant node")
+    }
+
+    pub(crate) fn next(&self, index: usize) -> usize {
+        self.nodes[index].next
+    }
+
+    pub(crate) fn prev(&self, index: usize) -> usize {
+        self.nodes[index].prev
+    }
+
+    /// Links the node at `index` after the node `at` of `list`, or at its front if `at` is `NIL`.
+    pub(crate) fn link_after(&mut self, list: &mut List, at: usize, index: usize) {
+        let next = if at == NIL { list.head } else { self.nodes[at].next };
+        self.nodes[index].prev = at;
+        self.nodes[index].next = next;
+        match at {
+            NIL => list.head = index,
+            _ => self.nodes[at].next = index,
+        }
+        match next {
+            NIL => list.tail = index,
+            _ => self.nodes[next].prev = index,
+        }
+    }
+
+    /// Unlinks the node at `index` from `list`.
+    pub(crate) fn unlink(&mut self, list: &mut List, index: usize) {
+        let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+        match prev {
+            NIL => list.head = next,
+            _ => self.nodes[prev].next = next,
+        }
+        match next {
+            NIL => list.tail = prev,
+            _ => self.nodes[next].prev = prev,
+        }
+        self.nodes[index].prev = NIL;
+        self.nodes[index].next = NIL;
+    }
+
+    /// Removes all the nodes of `list` in order, appending their values to `values`.
+    pub(crate) fn take_list(&mut self, list: List, values: &mut Vec<T>) {
+        let mut index = list.head;
+        while index != NIL {
+            let next = self.nodes[index].next;
+            values.push(self.remove(index));
+            index = next;
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+    }
+}
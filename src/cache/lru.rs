@@ -0,0 +1,216 @@
+//! Cache evicting the least recently used entry.
+use super::{
+    list::{List, Slab, NIL},
+    Cache, OnEvict,
+};
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash},
+    mem,
+};
+
+/// Cache of fixed capacity evicting the least recently used entry, iterating from the most recently used one.
+///
+/// NOTE: keys are stored twice, in the index map and in the entries.
+pub struct LruCache<K, V, S = RandomState> {
+    map: HashMap<K, usize, S>,
+    entries: Slab<(K, V)>,
+    /// Most recently used first
+    order: List,
+    capacity: usize,
+    on_evict: Option<OnEvict<K, V>>,
+}
+
+impl<K, V> LruCache<K, V> {
+    /// Creates an empty cache holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S> LruCache<K, V, S> {
+    /// Creates an empty cache holding at most `capacity` entries, hashing keys with `hash_builder`.
+    pub fn with_hasher(capacity: usize, hash_builder: S) -> Self {
+        LruCache {
+            map: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+            entries: Slab::with_capacity(capacity),
+            order: List::EMPTY,
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if there is no entry.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Iterates over the entries from the most recently used one.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: &self.entries,
+            index: self.order.head,
+            len: self.len(),
+        }
+    }
+
+    /// Removes all entries, without calling the eviction callback.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.entries.clear();
+        self.order = List::EMPTY;
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> LruCache<K, V, S> {
+    /// Returns true if `key` has an entry, without recording the access.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the entry of `key`, without recording the access.
+    pub fn peek_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        let (key, value) = self.entries.get(*self.map.get(key)?);
+        Some((key, value))
+    }
+
+    /// Returns the value of `key`, without recording the access.
+    pub fn peek_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = *self.map.get(key)?;
+        Some(&mut self.entries.get_mut(index).1)
+    }
+
+    /// Removes the entry of `key`, without calling the eviction callback.
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let index = self.map.remove(key)?;
+        self.entries.unlink(&mut self.order, index);
+        Some(self.entries.remove(index))
+    }
+
+    /// Marks the entry at `index` as the most recently used.
+    fn touch(&mut self, index: usize) {
+        self.entries.unlink(&mut self.order, index);
+        self.entries.link_after(&mut self.order, NIL, index);
+    }
+
+    fn evict(&mut self, key: K, value: V) {
+        if let Some(on_evict) = &mut self.on_evict {
+            on_evict(key, value);
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V, S: BuildHasher> Cache for LruCache<K, V, S> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let index = *self.map.get(key)?;
+        self.touch(index);
+        Some(&self.entries.get(index).1)
+    }
+
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key).map(|index| &self.entries.get(*index).1)
+    }
+
+    fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&index) = self.map.get(&key) {
+            self.touch(index);
+            return Some(mem::replace(&mut self.entries.get_mut(index).1, value));
+        }
+        if self.capacity == 0 {
+            self.evict(key, value);
+            return None;
+        }
+        if self.len() == self.capacity {
+            if let Some((key, value)) = self.pop_victim() {
+                self.evict(key, value);
+            }
+        }
+        let index = self.entries.insert((key.clone(), value));
+        self.entries.link_after(&mut self.order, NIL, index);
+        self.map.insert(key, index);
+        None
+    }
+
+    fn peek_victim(&self) -> Option<(&K, &V)> {
+        match self.order.tail {
+            NIL => None,
+            index => {
+                let (key, value) = self.entries.get(index);
+                Some((key, value))
+            }
+        }
+    }
+
+    fn pop_victim(&mut self) -> Option<(K, V)> {
+        let index = match self.order.tail {
+            NIL => return None,
+            index => index,
+        };
+        self.entries.unlink(&mut self.order, index);
+        let (key, value) = self.entries.remove(index);
+        self.map.remove(&key);
+        Some((key, value))
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.len() > capacity {
+            if let Some((key, value)) = self.pop_victim() {
+                self.evict(key, value);
+            }
+        }
+    }
+
+    fn set_on_evict<F: FnMut(K, V) + Send + 'static>(&mut self, on_evict: F) {
+        self.on_evict = Some(Box::new(on_evict));
+    }
+}
+
+/// Iterator over the entries of a `LruCache<K, V, S>`, from the most recently used one.
+pub struct Iter<'a, K, V> {
+    entries: &'a Slab<(K, V)>,
+    index: usize,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == NIL {
+            return None;
+        }
+        let (key, value) = self.entries.get(self.index);
+        self.index = self.entries.next(self.index);
+        self.len -= 1;
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+impl<K, V, S> IntoIterator for LruCache<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    /// Iterates from the most recently used entry.
+    fn into_iter(mut self) -> Self::IntoIter {
+        let mut entries = Vec::with_capacity(self.len());
+        self.entries.take_list(self.order, &mut entries);
+        entries.into_iter()
+    }
+}
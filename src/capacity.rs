@@ -26,18 +26,21 @@ pub trait CapacityShrink: CapacityAware {
 
 /// Collections that can reserve capacity.
 pub trait CapacityReserve: CapacityAware {
-    /// Try reserve errors
-    type TryReserveError: std::error::Error;
-
     /// Reserves capacity for at least additional more elements to be inserted in the given `Self`.
     fn reserve(&mut self, additional: Self::CapacityType);
+}
+
+/// Collections that can reserve capacity without aborting on allocation failures.
+pub trait CapacityTryReserve: CapacityReserve {
+    /// Try reserve errors
+    type TryReserveError: std::error::Error;
 
     /// Tries to reserve capacity for at least additional more elements to be inserted in the given `Self`.
     fn try_reserve(&mut self, additional: Self::CapacityType) -> Result<(), Self::TryReserveError>;
 }
 
 /// Collections that can reserve exact capacity.
-pub trait CapacityReserveExact: CapacityReserve {
+pub trait CapacityReserveExact: CapacityTryReserve {
     /// Reserves the minimum capacity for exactly additional more elements to be inserted in the given `Self`.
     fn reserve_exact(&mut self, additional: Self::CapacityType);
 
@@ -45,6 +48,15 @@ pub trait CapacityReserveExact: CapacityReserve {
     fn try_reserve_exact(&mut self, additional: Self::CapacityType) -> Result<(), Self::TryReserveError>;
 }
 
+/// Collections that can take a hint about upcoming insertions.
+///
+/// Unlike `CapacityReserve`, every std collection implements it, so generic code can pre-size whatever supports
+/// it. The hint is ignored by collections without capacity.
+pub trait CapacityHint {
+    /// Hints that about `additional` more elements are going to be inserted.
+    fn reserve_hint(&mut self, _additional: usize) {}
+}
+
 mod impls {
     use super::*;
 
@@ -68,6 +80,22 @@ mod impls {
             }
             capacity_impls!($($tail)*);
         };
+        ([@AllocCapCtor $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityConstructible for $t {
+                fn with_capacity(capacity: Self::CapacityType) -> Self {
+                    <$t>::with_capacity_in(capacity, std::default::Default::default())
+                }
+            }
+            capacity_impls!($($tail)*);
+        };
+        ([@HashCapCtor $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityConstructible for $t {
+                fn with_capacity(capacity: Self::CapacityType) -> Self {
+                    <$t>::with_capacity_and_hasher(capacity, std::default::Default::default())
+                }
+            }
+            capacity_impls!($($tail)*);
+        };
         ([@CapShrink $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
             impl<$($args $(: $bound $(+ $others)*)?),*> CapacityShrink for $t {
                 fn shrink_to_fit(&mut self) {
@@ -82,11 +110,20 @@ mod impls {
         };
         ([@CapReserve $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
             impl<$($args $(: $bound $(+ $others)*)?),*> CapacityReserve for $t {
-                type TryReserveError = std::collections::TryReserveError;
-
                 fn reserve(&mut self, additional: Self::CapacityType) {
                     <$t>::reserve(self, additional)
                 }
+            }
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityHint for $t {
+                fn reserve_hint(&mut self, additional: usize) {
+                    <$t>::reserve(self, additional)
+                }
+            }
+            capacity_impls!($($tail)*);
+        };
+        ([@CapTryReserve $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityTryReserve for $t {
+                type TryReserveError = std::collections::TryReserveError;
 
                 fn try_reserve(&mut self, additional: Self::CapacityType) -> Result<(), Self::TryReserveError> {
                     <$t>::try_reserve(self, additional)
@@ -94,6 +131,40 @@ mod impls {
             }
             capacity_impls!($($tail)*);
         };
+        // Node-based collections allocate on every insertion, so they never run out of capacity.
+        ([@Unbounded $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityAware for $t {
+                type CapacityType = usize;
+
+                fn capacity(&self) -> Self::CapacityType {
+                    usize::MAX
+                }
+            }
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityConstructible for $t {
+                fn with_capacity(_capacity: Self::CapacityType) -> Self {
+                    <$t>::new_in(std::default::Default::default())
+                }
+            }
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityShrink for $t {
+                fn shrink_to(&mut self, _min_capacity: Self::CapacityType) {}
+            }
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityReserve for $t {
+                fn reserve(&mut self, _additional: Self::CapacityType) {}
+            }
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityTryReserve for $t {
+                type TryReserveError = std::convert::Infallible;
+
+                fn try_reserve(&mut self, _additional: Self::CapacityType) -> Result<(), Self::TryReserveError> {
+                    Ok(())
+                }
+            }
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityHint for $t {}
+            capacity_impls!($($tail)*);
+        };
+        ([@NoCapHint $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> CapacityHint for $t {}
+            capacity_impls!($($tail)*);
+        };
         ([@CapReserveExact $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
             impl<$($args $(: $bound $(+ $others)*)?),*> CapacityReserveExact for $t {
                 fn reserve_exact(&mut self, additional: Self::CapacityType) {
@@ -110,31 +181,45 @@ mod impls {
 
     capacity_impls!(
         [@Cap T, A: std::alloc::Allocator => Vec<T, A>];
-        [@CapCtor T => Vec<T>];
+        [@AllocCapCtor T, A: std::alloc::Allocator | std::default::Default => Vec<T, A>];
         [@CapShrink T, A: std::alloc::Allocator => Vec<T, A>];
         [@CapReserve T, A: std::alloc::Allocator => Vec<T, A>];
+        [@CapTryReserve T, A: std::alloc::Allocator => Vec<T, A>];
         [@CapReserveExact T, A: std::alloc::Allocator => Vec<T, A>];
 
-        [@Cap T => std::collections::VecDeque<T>];
-        [@CapCtor T => std::collections::VecDeque<T>];
-        [@CapShrink T => std::collections::VecDeque<T>];
-        [@CapReserve T => std::collections::VecDeque<T>];
-        [@CapReserveExact T => std::collections::VecDeque<T>];
+        [@Cap T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@AllocCapCtor T, A: std::alloc::Allocator | std::default::Default => std::collections::VecDeque<T, A>];
+        [@CapShrink T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@CapReserve T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@CapTryReserve T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@CapReserveExact T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
 
-        [@Cap T => std::collections::BinaryHeap<T>];
-        [@CapCtor T: std::cmp::Ord => std::collections::BinaryHeap<T>];
-        [@CapShrink T => std::collections::BinaryHeap<T>];
-        // BinaryHeap<T> doesn't have `try_reserve`
-        // [@CapReserve T => std::collections::BinaryHeap<T>];
+        [@Cap T, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
+        [@AllocCapCtor T: std::cmp::Ord, A: std::alloc::Allocator | std::default::Default => std::collections::BinaryHeap<T, A>];
+        [@CapShrink T, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
+        [@CapReserve T: std::cmp::Ord, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
+        [@CapTryReserve T: std::cmp::Ord, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
 
         [@Cap T, S: std::hash::BuildHasher => std::collections::HashSet<T, S>];
-        [@CapCtor T => std::collections::HashSet<T>];
+        [@HashCapCtor T, S: std::hash::BuildHasher | std::default::Default => std::collections::HashSet<T, S>];
         [@CapShrink T: std::cmp::Eq | std::hash::Hash, S: std::hash::BuildHasher => std::collections::HashSet<T, S>];
         [@CapReserve T: std::cmp::Eq | std::hash::Hash, S: std::hash::BuildHasher => std::collections::HashSet<T, S>];
+        [@CapTryReserve T: std::cmp::Eq | std::hash::Hash, S: std::hash::BuildHasher => std::collections::HashSet<T, S>];
 
         [@Cap K, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>];
-        [@CapCtor K, V => std::collections::HashMap<K, V>];
+        [@HashCapCtor K, V, S: std::hash::BuildHasher | std::default::Default => std::collections::HashMap<K, V, S>];
         [@CapShrink K: std::cmp::Eq | std::hash::Hash, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>];
         [@CapReserve K: std::cmp::Eq | std::hash::Hash, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>];
+        [@CapTryReserve K: std::cmp::Eq | std::hash::Hash, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>];
+
+        [@Unbounded T, A: std::alloc::Allocator | std::clone::Clone | std::default::Default => std::collections::LinkedList<T, A>];
+        [@Unbounded T: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone | std::default::Default => std::collections::BTreeSet<T, A>];
+        [@Unbounded K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone | std::default::Default => std::collections::BTreeMap<K, V, A>];
+
+        [@NoCapHint T => &[T]];
+        [@NoCapHint T => &mut [T]];
+        [@NoCapHint T, A: std::alloc::Allocator => Box<[T], A>];
     );
+
+    impl<T, const N: usize> CapacityHint for [T; N] {}
 }
@@ -0,0 +1,38 @@
+//! Collections storing their length and capacity as `u32`, for workloads holding many small collections.
+//!
+//! `Vec32<T>` takes 16 bytes instead of 24 for `Vec<T>` on 64-bit targets, and `Deque32<T>` takes 24 bytes
+//! instead of 32. Both use `u32` as their `SizeType` and `CapacityType`, and report capacities beyond
+//! `u32::MAX` as `CompactReserveError::CapacityOverflow`.
+use std::{alloc::Layout, fmt};
+
+mod raw;
+
+pub mod deque32;
+pub mod vec32;
+
+pub use self::{deque32::Deque32, vec32::Vec32};
+
+/// Errors of reserving capacity in compact collections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompactReserveError {
+    /// The capacity exceeds `u32::MAX` elements or the maximum size of an allocation.
+    CapacityOverflow,
+    /// The allocator failed to allocate `layout`.
+    AllocError {
+        /// Layout of the failed allocation
+        layout: Layout,
+    },
+}
+
+impl fmt::Display for CompactReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompactReserveError::CapacityOverflow => write!(f, "capacity overflow"),
+            CompactReserveError::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompactReserveError {}
@@ -0,0 +1,917 @@
+//! `VecDeque<T>` with `u32` length and capacity.
+use super::{
+    raw::{handle_reserve, RawBuf32},
+    vec32::{Drain, IntoIter},
+    CompactReserveError, Vec32,
+};
+use crate::{
+    capacity::{
+        CapacityAware, CapacityConstructible, CapacityHint, CapacityReserve, CapacityReserveExact, CapacityShrink,
+        CapacityTryReserve,
+    },
+    elem::{CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    heap_size::HeapSize,
+    index_vec::raw_range,
+    iter::{DrainRange, Iterable, IterableMut, Range, RangeMut},
+    misc::{Contains, EmptyConstructible, Erasable, Resizable, Retainable},
+    sequential::{DoubleEnded, RandomAccess, Sequential},
+};
+use std::{convert::TryFrom, fmt, iter::FromIterator, ops::RangeBounds, ptr};
+
+/// Double-ended queue implemented with a growable ring buffer, with `u32` length and capacity.
+pub struct Deque32<T> {
+    buf: RawBuf32<T>,
+    head: u32,
+}
+
+impl<T> Deque32<T> {
+    /// Creates an empty deque without allocating.
+    pub fn new() -> Self {
+        Deque32 {
+            buf: RawBuf32::new(),
+            head: 0,
+        }
+    }
+
+    /// Creates an empty deque with room for `capacity` elements.
+    pub fn with_capacity(capacity: u32) -> Self {
+        let mut d = Self::new();
+        d.reserve_exact(capacity);
+        d
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> u32 {
+        self.buf.len
+    }
+
+    /// Returns true if there is no element.
+    pub fn is_empty(&self) -> bool {
+        self.buf.len == 0
+    }
+
+    /// Returns the number of elements the deque can hold without reallocating.
+    pub fn capacity(&self) -> u32 {
+        self.buf.capacity()
+    }
+
+    /// Returns the physical position of the logical index `i`.
+    fn wrap(&self, i: u32) -> u32 {
+        let p = self.head as u64 + i as u64;
+        let cap = self.capacity() as u64;
+        (if p >= cap { p - cap } else { p }) as u32
+    }
+
+    fn slot(&self, i: u32) -> *mut T {
+        // SAFETY: `wrap` stays within the buffer.
+        unsafe { self.buf.ptr().add(self.wrap(i) as usize) }
+    }
+
+    /// Returns the elements as two slices, front first.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let head_len = self.buf.len.min(self.capacity() - self.head);
+        // SAFETY: `head..head + head_len` and `0..len - head_len` are initialized.
+        unsafe {
+            (
+                std::slice::from_raw_parts(self.buf.ptr().add(self.head as usize), head_len as usize),
+                std::slice::from_raw_parts(self.buf.ptr(), (self.buf.len - head_len) as usize),
+            )
+        }
+    }
+
+    /// Returns the elements as two mutable slices, front first.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let head_len = self.buf.len.min(self.capacity() - self.head);
+        // SAFETY: `head..head + head_len` and `0..len - head_len` are initialized and disjoint.
+        unsafe {
+            (
+                std::slice::from_raw_parts_mut(self.buf.ptr().add(self.head as usize), head_len as usize),
+                std::slice::from_raw_parts_mut(self.buf.ptr(), (self.buf.len - head_len) as usize),
+            )
+        }
+    }
+
+    /// Rearranges the elements so that they start at the beginning of the buffer, and returns them as a slice.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let cap = self.capacity();
+        let ptr = self.buf.ptr();
+        if self.head != 0 {
+            let head_len = self.buf.len.min(cap - self.head);
+            let tail_len = self.buf.len - head_len;
+            // SAFETY: the tail `0..tail_len` is moved next to the head `head..cap`, then the now contiguous
+            // elements are rotated into order and moved to the front of the buffer.
+            unsafe {
+                let start = self.head - tail_len;
+                ptr::copy(ptr, ptr.add(start as usize), tail_len as usize);
+                std::slice::from_raw_parts_mut(ptr.add(start as usize), self.buf.len as usize)
+                    .rotate_left(tail_len as usize);
+                ptr::copy(ptr.add(start as usize), ptr, self.buf.len as usize);
+            }
+            self.head = 0;
+        }
+        // SAFETY: the elements are at `0..len`.
+        unsafe { std::slice::from_raw_parts_mut(ptr, self.buf.len as usize) }
+    }
+
+    /// Fixes up the ring after growing the buffer from `old_cap`.
+    fn handle_capacity_increase(&mut self, old_cap: u32) {
+        let head_len = old_cap - self.head;
+        if self.buf.len > head_len {
+            // The elements wrapped around, move the head part to the end of the new buffer.
+            let new_head = self.capacity() - head_len;
+            // SAFETY: `new_head >= head`, and both ranges are within the buffer.
+            unsafe {
+                ptr::copy(
+                    self.buf.ptr().add(self.head as usize),
+                    self.buf.ptr().add(new_head as usize),
+                    head_len as usize,
+                )
+            };
+            self.head = new_head;
+        }
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements.
+    pub fn try_reserve(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+        let old_cap = self.capacity();
+        self.buf.try_reserve(additional)?;
+        self.handle_capacity_increase(old_cap);
+        Ok(())
+    }
+
+    /// Tries to reserve capacity for exactly `additional` more elements.
+    pub fn try_reserve_exact(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+        let old_cap = self.capacity();
+        self.buf.try_reserve_exact(additional)?;
+        self.handle_capacity_increase(old_cap);
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// Panics if the capacity exceeds `u32::MAX`.
+    pub fn reserve(&mut self, additional: u32) {
+        handle_reserve(self.try_reserve(additional))
+    }
+
+    /// Reserves capacity for exactly `additional` more elements.
+    pub fn reserve_exact(&mut self, additional: u32) {
+        handle_reserve(self.try_reserve_exact(additional))
+    }
+
+    /// Shrinks the capacity as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0)
+    }
+
+    /// Shrinks the capacity with a lower bound.
+    pub fn shrink_to(&mut self, min_capacity: u32) {
+        if self.capacity() > min_capacity.max(self.buf.len) {
+            self.make_contiguous();
+            handle_reserve(self.buf.try_resize(self.buf.len.max(min_capacity)))
+        }
+    }
+
+    /// Returns the element at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: u32) -> Option<&T> {
+        // SAFETY: logical indices below `len` are initialized.
+        (index < self.buf.len).then(|| unsafe { &*self.slot(index) })
+    }
+
+    /// Returns the element at `index` mutably, or `None` if out of bounds.
+    pub fn get_mut(&mut self, index: u32) -> Option<&mut T> {
+        // SAFETY: logical indices below `len` are initialized.
+        (index < self.buf.len).then(|| unsafe { &mut *self.slot(index) })
+    }
+
+    /// Returns the front element, or `None` if empty.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the front element mutably, or `None` if empty.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns the back element, or `None` if empty.
+    pub fn back(&self) -> Option<&T> {
+        self.buf.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Returns the back element mutably, or `None` if empty.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.buf.len.checked_sub(1).and_then(move |i| self.get_mut(i))
+    }
+
+    /// Appends an element to the back.
+    pub fn push_back(&mut self, value: T) {
+        if self.buf.len == self.capacity() {
+            self.reserve(1);
+        }
+        // SAFETY: `len < capacity`, so the slot is free.
+        unsafe { ptr::write(self.slot(self.buf.len), value) };
+        self.buf.len += 1;
+    }
+
+    /// Prepends an element to the front.
+    pub fn push_front(&mut self, value: T) {
+        if self.buf.len == self.capacity() {
+            self.reserve(1);
+        }
+        self.head = if self.head == 0 {
+            self.capacity() - 1
+        } else {
+            self.head - 1
+        };
+        self.buf.len += 1;
+        // SAFETY: `len <= capacity`, so the slot before the old head is free.
+        unsafe { ptr::write(self.slot(0), value) };
+    }
+
+    /// Removes the back element and returns it, or `None` if empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.buf.len == 0 {
+            return None;
+        }
+        self.buf.len -= 1;
+        // SAFETY: the slot was initialized and is now outside of the deque.
+        Some(unsafe { ptr::read(self.slot(self.buf.len)) })
+    }
+
+    /// Removes the front element and returns it, or `None` if empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.buf.len == 0 {
+            return None;
+        }
+        let slot = self.slot(0);
+        self.head = self.wrap(1);
+        self.buf.len -= 1;
+        // SAFETY: the slot was initialized and is now outside of the deque.
+        Some(unsafe { ptr::read(slot) })
+    }
+
+    /// Swaps the elements at `i` and `j`.
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap(&mut self, i: u32, j: u32) {
+        assert!(
+            i < self.buf.len && j < self.buf.len,
+            "swap indices {}, {} out of bounds {}",
+            i,
+            j,
+            self.buf.len
+        );
+        // SAFETY: both slots are initialized.
+        unsafe { ptr::swap(self.slot(i), self.slot(j)) }
+    }
+
+    /// Inserts an element at `index`, shifting all elements after it to the back.
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: u32, value: T) {
+        assert!(
+            index <= self.buf.len,
+            "insertion index {} out of bounds {}",
+            index,
+            self.buf.len
+        );
+        self.push_back(value);
+        self.make_contiguous()[index as usize..].rotate_right(1);
+    }
+
+    /// Removes and returns the element at `index`, or `None` if out of bounds.
+    pub fn remove(&mut self, index: u32) -> Option<T> {
+        if index >= self.buf.len {
+            return None;
+        }
+        self.make_contiguous()[index as usize..].rotate_left(1);
+        self.pop_back()
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the back element.
+    pub fn swap_remove_back(&mut self, index: u32) -> Option<T> {
+        if index >= self.buf.len {
+            return None;
+        }
+        self.swap(index, self.buf.len - 1);
+        self.pop_back()
+    }
+
+    /// Shortens the deque to `len` elements, dropping the rest.
+    pub fn truncate(&mut self, len: u32) {
+        while self.buf.len > len {
+            drop(self.pop_back());
+        }
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+        self.head = 0;
+    }
+
+    /// Resizes the deque to `new_len`, appending elements created by `f` if it grows.
+    pub fn resize_with(&mut self, new_len: u32, mut f: impl FnMut() -> T) {
+        if new_len <= self.buf.len {
+            self.truncate(new_len);
+        } else {
+            self.reserve(new_len - self.buf.len);
+            while self.buf.len < new_len {
+                self.push_back(f());
+            }
+        }
+    }
+
+    /// Resizes the deque to `new_len`, appending clones of `value` if it grows.
+    pub fn resize(&mut self, new_len: u32, value: T)
+    where
+        T: Clone,
+    {
+        self.resize_with(new_len, || value.clone())
+    }
+
+    /// Retains only the elements specified by the predicate, preserving their order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut pred: F) {
+        let mut kept = 0;
+        for i in 0..self.buf.len {
+            if pred(self.get(i).unwrap()) {
+                self.swap(kept, i);
+                kept += 1;
+            }
+        }
+        self.truncate(kept);
+    }
+
+    /// Moves all elements of `other` to the back of `self`.
+    pub fn append(&mut self, other: &mut Self) {
+        self.reserve(other.buf.len);
+        while let Some(value) = other.pop_front() {
+            self.push_back(value);
+        }
+    }
+
+    /// Splits the deque into two at `at`, returning the elements from `at` on.
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: u32) -> Self {
+        self.drain(at..).collect()
+    }
+
+    /// Rotates the deque `mid` places to the left.
+    pub fn rotate_left(&mut self, mid: u32) {
+        self.make_contiguous().rotate_left(mid as usize)
+    }
+
+    /// Rotates the deque `mid` places to the right.
+    pub fn rotate_right(&mut self, mid: u32) {
+        self.make_contiguous().rotate_right(mid as usize)
+    }
+
+    /// Returns the index of the first element for which `pred` is false, assuming the deque is partitioned.
+    pub fn partition_point<P: FnMut(&T) -> bool>(&self, mut pred: P) -> u32 {
+        let (front, back) = self.as_slices();
+        match back.first() {
+            Some(v) if pred(v) => (front.len() + back.partition_point(pred)) as u32,
+            _ => front.partition_point(pred) as u32,
+        }
+    }
+
+    /// Iterates over the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    /// Iterates mutably over the elements.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+
+    /// Iterates over the elements in `range`.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn range<R: RangeBounds<u32>>(&self, range: R) -> Iter<'_, T> {
+        let range = std::slice::range(raw_range(range), ..self.buf.len as usize);
+        let (front, back) = self.as_slices();
+        let (front, back) = split_range(front, back, range);
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    /// Iterates mutably over the elements in `range`.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn range_mut<R: RangeBounds<u32>>(&mut self, range: R) -> IterMut<'_, T> {
+        let range = std::slice::range(raw_range(range), ..self.buf.len as usize);
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = split_range_mut(front, back, range);
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+
+    /// Removes the elements in `range` and yields them.
+    ///
+    /// NOTE: the elements are made contiguous first, which moves them if the ring buffer wraps around.
+    pub fn drain<R: RangeBounds<u32>>(&mut self, range: R) -> Drain<'_, T> {
+        self.make_contiguous();
+        let ptr = self.buf.ptr();
+        Drain::new(ptr, &mut self.buf.len, range)
+    }
+}
+
+fn split_range<'a, T>(front: &'a [T], back: &'a [T], range: std::ops::Range<usize>) -> (&'a [T], &'a [T]) {
+    let mid = front.len();
+    if range.end <= mid {
+        (&front[range], &[])
+    } else if range.start >= mid {
+        (&back[range.start - mid..range.end - mid], &[])
+    } else {
+        (&front[range.start..], &back[..range.end - mid])
+    }
+}
+
+fn split_range_mut<'a, T>(
+    front: &'a mut [T],
+    back: &'a mut [T],
+    range: std::ops::Range<usize>,
+) -> (&'a mut [T], &'a mut [T]) {
+    let mid = front.len();
+    if range.end <= mid {
+        (&mut front[range], &mut [])
+    } else if range.start >= mid {
+        (&mut back[range.start - mid..range.end - mid], &mut [])
+    } else {
+        (&mut front[range.start..], &mut back[..range.end - mid])
+    }
+}
+
+impl<T> Drop for Deque32<T> {
+    fn drop(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        // SAFETY: both slices are initialized, `buf` frees the memory afterwards.
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+impl<T> From<Deque32<T>> for Vec32<T> {
+    fn from(mut d: Deque32<T>) -> Self {
+        d.make_contiguous();
+        Vec32::from_raw_buf(std::mem::replace(&mut d.buf, RawBuf32::new()))
+    }
+}
+
+impl<T> From<Vec32<T>> for Deque32<T> {
+    fn from(v: Vec32<T>) -> Self {
+        Deque32 {
+            buf: v.into_raw_buf(),
+            head: 0,
+        }
+    }
+}
+
+impl<T> Default for Deque32<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for Deque32<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Deque32<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Deque32<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.len == other.buf.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Deque32<T> {}
+
+impl<T: std::hash::Hash> std::hash::Hash for Deque32<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u32(self.buf.len);
+        self.iter().for_each(|v| v.hash(state));
+    }
+}
+
+impl<T> std::ops::Index<u32> for Deque32<T> {
+    type Output = T;
+
+    fn index(&self, index: u32) -> &T {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> std::ops::IndexMut<u32> for Deque32<T> {
+    fn index_mut(&mut self, index: u32) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T> Extend<T> for Deque32<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(u32::try_from(iter.size_hint().0).unwrap_or(u32::MAX));
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+
+    fn extend_one(&mut self, item: T) {
+        self.push_back(item)
+    }
+
+    fn extend_reserve(&mut self, additional: usize) {
+        self.reserve(u32::try_from(additional).unwrap_or(u32::MAX))
+    }
+}
+
+impl<T> FromIterator<T> for Deque32<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut d = Self::new();
+        d.extend(iter);
+        d
+    }
+}
+
+impl<T> IntoIterator for Deque32<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        Vec32::from(self).into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque32<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Iterator over `&T` of `Deque32<T>`.
+pub struct Iter<'a, T> {
+    front: std::slice::Iter<'a, T>,
+    back: std::slice::Iter<'a, T>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match self.front.next() {
+            Some(v) => Some(v),
+            None => self.back.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        match self.back.next_back() {
+            Some(v) => Some(v),
+            None => self.front.next_back(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> std::iter::FusedIterator for Iter<'_, T> {}
+
+/// Iterator over `&mut T` of `Deque32<T>`.
+pub struct IterMut<'a, T> {
+    front: std::slice::IterMut<'a, T>,
+    back: std::slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.front.next() {
+            Some(v) => Some(v),
+            None => self.back.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        match self.back.next_back() {
+            Some(v) => Some(v),
+            None => self.front.next_back(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> std::iter::FusedIterator for IterMut<'_, T> {}
+
+mod impls {
+    use super::*;
+
+    impl<T> CollectionTrait for Deque32<T> {
+        type ElemType = T;
+    }
+
+    impl<T> Mutable for Deque32<T> {}
+    impl<T> Owned for Deque32<T> {}
+
+    impl<T> ExactSized for Deque32<T> {
+        type SizeType = u32;
+
+        fn is_empty(&self) -> bool {
+            self.is_empty()
+        }
+
+        fn len(&self) -> u32 {
+            self.len()
+        }
+    }
+
+    impl<T> Iterable for Deque32<T> {
+        type Iter<'a>
+        where
+            T: 'a,
+        = Iter<'a, T>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.iter()
+        }
+    }
+
+    impl<T> IterableMut for Deque32<T> {
+        type IterMut<'a>
+        where
+            T: 'a,
+        = IterMut<'a, T>;
+
+        fn iter_mut(&mut self) -> Self::IterMut<'_> {
+            self.iter_mut()
+        }
+    }
+
+    impl<T> Range for Deque32<T> {
+        type RangeIter<'a>
+        where
+            T: 'a,
+        = Iter<'a, T>;
+
+        fn range<R: RangeBounds<Self::SizeType>>(&self, range: R) -> Self::RangeIter<'_> {
+            self.range(range)
+        }
+    }
+
+    impl<T> RangeMut for Deque32<T> {
+        type RangeIterMut<'a>
+        where
+            T: 'a,
+        = IterMut<'a, T>;
+
+        fn range_mut<R: RangeBounds<Self::SizeType>>(&mut self, range: R) -> Self::RangeIterMut<'_> {
+            self.range_mut(range)
+        }
+    }
+
+    impl<T> DrainRange for Deque32<T> {
+        type DrainRangeIter<'a>
+        where
+            T: 'a,
+        = Drain<'a, T>;
+
+        fn drain_range<R: RangeBounds<Self::SizeType>>(&mut self, range: R) -> Self::DrainRangeIter<'_> {
+            self.drain(range)
+        }
+    }
+
+    impl<T> EmptyConstructible for Deque32<T> {
+        fn new() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T> Erasable for Deque32<T> {
+        fn clear(&mut self) {
+            self.clear()
+        }
+    }
+
+    impl<T: PartialEq> Contains for Deque32<T> {
+        fn contains(&self, value: &T) -> bool {
+            self.iter().any(|v| v == value)
+        }
+    }
+
+    impl<T> Retainable for Deque32<T> {
+        fn retain<F: FnMut(&T) -> bool>(&mut self, pred: F) {
+            self.retain(pred)
+        }
+    }
+
+    impl<T: Clone> Resizable for Deque32<T> {
+        fn resize(&mut self, new_len: Self::SizeType, value: T) {
+            self.resize(new_len, value)
+        }
+
+        fn resize_with(&mut self, new_len: Self::SizeType, generator: impl FnMut() -> T) {
+            self.resize_with(new_len, generator)
+        }
+
+        fn truncate(&mut self, len: Self::SizeType) {
+            self.truncate(len)
+        }
+    }
+
+    impl<T> Sequential for Deque32<T> {
+        fn back(&self) -> Option<&Self::ElemType> {
+            self.back()
+        }
+
+        fn back_mut(&mut self) -> Option<&mut Self::ElemType> {
+            self.back_mut()
+        }
+
+        fn append(&mut self, other: &mut Self) {
+            self.append(other)
+        }
+
+        fn push_back(&mut self, elt: Self::ElemType) {
+            self.push_back(elt)
+        }
+
+        fn pop_back(&mut self) -> Option<Self::ElemType> {
+            self.pop_back()
+        }
+
+        fn remove(&mut self, at: Self::SizeType) -> Option<Self::ElemType> {
+            self.remove(at)
+        }
+
+        fn split_off(&mut self, at: Self::SizeType) -> Self {
+            self.split_off(at)
+        }
+    }
+
+    impl<T> DoubleEnded for Deque32<T> {
+        fn front(&self) -> Option<&Self::ElemType> {
+            self.front()
+        }
+
+        fn front_mut(&mut self) -> Option<&mut Self::ElemType> {
+            self.front_mut()
+        }
+
+        fn push_front(&mut self, value: Self::ElemType) {
+            self.push_front(value)
+        }
+
+        fn pop_front(&mut self) -> Option<Self::ElemType> {
+            self.pop_front()
+        }
+    }
+
+    impl<T> RandomAccess for Deque32<T> {
+        fn rotate_left(&mut self, mid: Self::SizeType) {
+            self.rotate_left(mid)
+        }
+
+        fn rotate_right(&mut self, mid: Self::SizeType) {
+            self.rotate_right(mid)
+        }
+
+        fn partition_point<P: FnMut(&Self::ElemType) -> bool>(&self, pred: P) -> Self::SizeType {
+            self.partition_point(pred)
+        }
+
+        fn swap(&mut self, i: Self::SizeType, j: Self::SizeType) {
+            self.swap(i, j)
+        }
+
+        fn get(&self, index: Self::SizeType) -> Option<&Self::ElemType> {
+            self.get(index)
+        }
+
+        fn get_mut(&mut self, index: Self::SizeType) -> Option<&mut Self::ElemType> {
+            self.get_mut(index)
+        }
+
+        fn insert(&mut self, index: Self::SizeType, value: Self::ElemType) {
+            self.insert(index, value)
+        }
+
+        fn swap_remove_back(&mut self, index: Self::SizeType) -> Option<Self::ElemType> {
+            self.swap_remove_back(index)
+        }
+    }
+
+    impl<T> CapacityAware for Deque32<T> {
+        type CapacityType = u32;
+
+        fn capacity(&self) -> u32 {
+            self.capacity()
+        }
+    }
+
+    impl<T> CapacityConstructible for Deque32<T> {
+        fn with_capacity(capacity: u32) -> Self {
+            Self::with_capacity(capacity)
+        }
+    }
+
+    impl<T> CapacityShrink for Deque32<T> {
+        fn shrink_to_fit(&mut self) {
+            self.shrink_to_fit()
+        }
+
+        fn shrink_to(&mut self, min_capacity: u32) {
+            self.shrink_to(min_capacity)
+        }
+    }
+
+    impl<T> CapacityReserve for Deque32<T> {
+        fn reserve(&mut self, additional: u32) {
+            self.reserve(additional)
+        }
+    }
+
+    impl<T> CapacityTryReserve for Deque32<T> {
+        type TryReserveError = CompactReserveError;
+
+        fn try_reserve(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+            self.try_reserve(additional)
+        }
+    }
+
+    impl<T> CapacityReserveExact for Deque32<T> {
+        fn reserve_exact(&mut self, additional: u32) {
+            self.reserve_exact(additional)
+        }
+
+        fn try_reserve_exact(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+            self.try_reserve_exact(additional)
+        }
+    }
+
+    impl<T> CapacityHint for Deque32<T> {
+        fn reserve_hint(&mut self, additional: usize) {
+            if let Ok(additional) = u32::try_from(additional) {
+                let _ = self.try_reserve(additional);
+            }
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for Deque32<T> {
+        fn heap_size_of_children(&self) -> usize {
+            let (front, back) = self.as_slices();
+            self.capacity() as usize * std::mem::size_of::<T>()
+                + front.heap_size_of_children()
+                + back.heap_size_of_children()
+        }
+    }
+}
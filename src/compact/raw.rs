@@ -0,0 +1,119 @@
+use super::CompactReserveError;
+use std::{
+    alloc::{Allocator, Global, Layout},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+/// Buffer of `cap` possibly uninitialized `T`s, freed on drop without dropping the elements.
+///
+/// NOTE: the length of the owning collection is stored here to fill the padding after `cap`.
+pub(super) struct RawBuf32<T> {
+    ptr: NonNull<T>,
+    cap: u32,
+    /// Number of initialized elements, maintained by the owner
+    pub(super) len: u32,
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: `RawBuf32<T>` owns its `T`s like `Vec<T>`.
+unsafe impl<T: Send> Send for RawBuf32<T> {}
+unsafe impl<T: Sync> Sync for RawBuf32<T> {}
+
+impl<T> RawBuf32<T> {
+    const IS_ZST: bool = std::mem::size_of::<T>() == 0;
+
+    pub(super) fn new() -> Self {
+        RawBuf32 {
+            ptr: NonNull::dangling(),
+            cap: if Self::IS_ZST { u32::MAX } else { 0 },
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(super) fn capacity(&self) -> u32 {
+        self.cap
+    }
+
+    pub(super) fn ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    fn layout(cap: u32) -> Result<Layout, CompactReserveError> {
+        Layout::array::<T>(cap as usize).map_err(|_| CompactReserveError::CapacityOverflow)
+    }
+
+    /// Reallocates the buffer to hold exactly `new_cap` elements.
+    ///
+    /// The caller must ensure no initialized element lives at or beyond `new_cap`.
+    pub(super) fn try_resize(&mut self, new_cap: u32) -> Result<(), CompactReserveError> {
+        if Self::IS_ZST || new_cap == self.cap {
+            return Ok(());
+        }
+        let new_layout = Self::layout(new_cap)?;
+        // SAFETY: `self.ptr` was allocated by `Global` with the layout of `self.cap` elements if `self.cap > 0`.
+        unsafe {
+            if new_cap == 0 {
+                Global.deallocate(self.ptr.cast(), Self::layout(self.cap)?);
+                self.ptr = NonNull::dangling();
+                self.cap = 0;
+                return Ok(());
+            }
+            let ptr = if self.cap == 0 {
+                Global.allocate(new_layout)
+            } else if new_cap > self.cap {
+                Global.grow(self.ptr.cast(), Self::layout(self.cap)?, new_layout)
+            } else {
+                Global.shrink(self.ptr.cast(), Self::layout(self.cap)?, new_layout)
+            };
+            self.ptr = ptr
+                .map_err(|_| CompactReserveError::AllocError { layout: new_layout })?
+                .cast();
+        }
+        self.cap = new_cap;
+        Ok(())
+    }
+
+    /// Makes room for `additional` more elements after `len`, growing the buffer at least twice.
+    pub(super) fn try_reserve(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(CompactReserveError::CapacityOverflow)?;
+        if required <= self.cap {
+            return Ok(());
+        }
+        self.try_resize(required.max(self.cap.saturating_mul(2)).max(4))
+    }
+
+    /// Makes room for exactly `additional` more elements after `len`.
+    pub(super) fn try_reserve_exact(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(CompactReserveError::CapacityOverflow)?;
+        if required <= self.cap {
+            return Ok(());
+        }
+        self.try_resize(required)
+    }
+}
+
+impl<T> Drop for RawBuf32<T> {
+    fn drop(&mut self) {
+        if !Self::IS_ZST && self.cap > 0 {
+            // SAFETY: allocated by `Global` with the layout of `self.cap` elements.
+            unsafe { Global.deallocate(self.ptr.cast(), Self::layout(self.cap).unwrap()) }
+        }
+    }
+}
+
+/// Aborts on reservation failures like `Vec::reserve`.
+pub(super) fn handle_reserve(result: Result<(), CompactReserveError>) {
+    match result {
+        Ok(()) => {}
+        Err(CompactReserveError::CapacityOverflow) => panic!("capacity overflow"),
+        Err(CompactReserveError::AllocError { layout }) => std::alloc::handle_alloc_error(layout),
+    }
+}
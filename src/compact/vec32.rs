@@ -0,0 +1,765 @@
+//! `Vec<T>` with `u32` length and capacity.
+use super::{
+    raw::{handle_reserve, RawBuf32},
+    CompactReserveError,
+};
+use crate::{
+    capacity::{
+        CapacityAware, CapacityConstructible, CapacityHint, CapacityReserve, CapacityReserveExact, CapacityShrink,
+        CapacityTryReserve,
+    },
+    elem::{CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    heap_size::HeapSize,
+    index_vec::raw_range,
+    iter::{DrainRange, Iterable, IterableMut, Range, RangeMut},
+    misc::{Contains, EmptyConstructible, Erasable, Resizable, Retainable},
+    sequential::{Contiguous, RandomAccess, Sequential},
+};
+use std::{convert::TryFrom, fmt, iter::FromIterator, marker::PhantomData, ops::RangeBounds, ptr};
+
+/// Contiguous growable array with `u32` length and capacity.
+pub struct Vec32<T> {
+    buf: RawBuf32<T>,
+}
+
+impl<T> Vec32<T> {
+    /// Creates an empty vector without allocating.
+    pub fn new() -> Self {
+        Vec32 { buf: RawBuf32::new() }
+    }
+
+    /// Creates an empty vector with room for `capacity` elements.
+    pub fn with_capacity(capacity: u32) -> Self {
+        let mut v = Self::new();
+        v.reserve_exact(capacity);
+        v
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> u32 {
+        self.buf.len
+    }
+
+    /// Returns true if there is no element.
+    pub fn is_empty(&self) -> bool {
+        self.buf.len == 0
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> u32 {
+        self.buf.capacity()
+    }
+
+    /// Extracts a slice of the entire vector.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { std::slice::from_raw_parts(self.buf.ptr(), self.buf.len as usize) }
+    }
+
+    /// Extracts a mutable slice of the entire vector.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { std::slice::from_raw_parts_mut(self.buf.ptr(), self.buf.len as usize) }
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// Panics if the capacity exceeds `u32::MAX`.
+    pub fn reserve(&mut self, additional: u32) {
+        handle_reserve(self.buf.try_reserve(additional))
+    }
+
+    /// Reserves capacity for exactly `additional` more elements.
+    pub fn reserve_exact(&mut self, additional: u32) {
+        handle_reserve(self.buf.try_reserve_exact(additional))
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements.
+    pub fn try_reserve(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+        self.buf.try_reserve(additional)
+    }
+
+    /// Tries to reserve capacity for exactly `additional` more elements.
+    pub fn try_reserve_exact(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+        self.buf.try_reserve_exact(additional)
+    }
+
+    /// Shrinks the capacity as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0)
+    }
+
+    /// Shrinks the capacity with a lower bound.
+    pub fn shrink_to(&mut self, min_capacity: u32) {
+        if self.capacity() > min_capacity {
+            handle_reserve(self.buf.try_resize(self.buf.len.max(min_capacity)))
+        }
+    }
+
+    /// Appends an element to the back.
+    pub fn push(&mut self, value: T) {
+        if self.buf.len == self.capacity() {
+            self.reserve(1);
+        }
+        // SAFETY: `len < capacity`
+        unsafe { ptr::write(self.buf.ptr().add(self.buf.len as usize), value) };
+        self.buf.len += 1;
+    }
+
+    /// Removes the last element and returns it, or `None` if empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.buf.len == 0 {
+            return None;
+        }
+        self.buf.len -= 1;
+        // SAFETY: the element at `len` was initialized and is now outside of the vector.
+        Some(unsafe { ptr::read(self.buf.ptr().add(self.buf.len as usize)) })
+    }
+
+    /// Inserts an element at `index`, shifting all elements after it to the right.
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: u32, value: T) {
+        assert!(
+            index <= self.buf.len,
+            "insertion index {} out of bounds {}",
+            index,
+            self.buf.len
+        );
+        if self.buf.len == self.capacity() {
+            self.reserve(1);
+        }
+        // SAFETY: `index <= len < capacity`
+        unsafe {
+            let p = self.buf.ptr().add(index as usize);
+            ptr::copy(p, p.add(1), (self.buf.len - index) as usize);
+            ptr::write(p, value);
+        }
+        self.buf.len += 1;
+    }
+
+    /// Removes and returns the element at `index`, shifting all elements after it to the left.
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: u32) -> T {
+        assert!(
+            index < self.buf.len,
+            "removal index {} out of bounds {}",
+            index,
+            self.buf.len
+        );
+        self.buf.len -= 1;
+        // SAFETY: `index < len`
+        unsafe {
+            let p = self.buf.ptr().add(index as usize);
+            let value = ptr::read(p);
+            ptr::copy(p.add(1), p, (self.buf.len - index) as usize);
+            value
+        }
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the last element.
+    ///
+    /// Panics if `index >= len`.
+    pub fn swap_remove(&mut self, index: u32) -> T {
+        assert!(
+            index < self.buf.len,
+            "removal index {} out of bounds {}",
+            index,
+            self.buf.len
+        );
+        let last = self.buf.len - 1;
+        self.as_mut_slice().swap(index as usize, last as usize);
+        self.pop().unwrap()
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    pub fn truncate(&mut self, len: u32) {
+        if len >= self.buf.len {
+            return;
+        }
+        let tail = ptr::slice_from_raw_parts_mut(
+            // SAFETY: `len < self.buf.len`
+            unsafe { self.buf.ptr().add(len as usize) },
+            (self.buf.len - len) as usize,
+        );
+        self.buf.len = len;
+        // SAFETY: the tail was initialized and is now outside of the vector.
+        unsafe { ptr::drop_in_place(tail) };
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Resizes the vector to `new_len`, appending elements created by `f` if it grows.
+    pub fn resize_with(&mut self, new_len: u32, mut f: impl FnMut() -> T) {
+        if new_len <= self.buf.len {
+            self.truncate(new_len);
+        } else {
+            self.reserve(new_len - self.buf.len);
+            while self.buf.len < new_len {
+                self.push(f());
+            }
+        }
+    }
+
+    /// Resizes the vector to `new_len`, appending clones of `value` if it grows.
+    pub fn resize(&mut self, new_len: u32, value: T)
+    where
+        T: Clone,
+    {
+        self.resize_with(new_len, || value.clone())
+    }
+
+    /// Retains only the elements specified by the predicate, preserving their order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut pred: F) {
+        let mut kept = 0;
+        for i in 0..self.buf.len as usize {
+            if pred(&self.as_slice()[i]) {
+                self.as_mut_slice().swap(kept, i);
+                kept += 1;
+            }
+        }
+        self.truncate(kept as u32);
+    }
+
+    /// Moves all elements of `other` to the back of `self`.
+    pub fn append(&mut self, other: &mut Self) {
+        self.reserve(other.buf.len);
+        // SAFETY: there is room for `other.buf.len` elements, which are moved out of `other`.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                other.buf.ptr(),
+                self.buf.ptr().add(self.buf.len as usize),
+                other.buf.len as usize,
+            );
+        }
+        self.buf.len += other.buf.len;
+        other.buf.len = 0;
+    }
+
+    /// Splits the vector into two at `at`, returning the elements from `at` on.
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: u32) -> Self {
+        assert!(at <= self.buf.len, "split index {} out of bounds {}", at, self.buf.len);
+        let mut other = Self::with_capacity(self.buf.len - at);
+        // SAFETY: the elements from `at` are moved into `other`, which has room for them.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.buf.ptr().add(at as usize),
+                other.buf.ptr(),
+                (self.buf.len - at) as usize,
+            );
+        }
+        other.buf.len = self.buf.len - at;
+        self.buf.len = at;
+        other
+    }
+
+    /// Removes the elements in `range` and yields them.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn drain<R: RangeBounds<u32>>(&mut self, range: R) -> Drain<'_, T> {
+        let ptr = self.buf.ptr();
+        Drain::new(ptr, &mut self.buf.len, range)
+    }
+
+    pub(super) fn into_raw_buf(self) -> RawBuf32<T> {
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again.
+        unsafe { ptr::read(&this.buf) }
+    }
+
+    pub(super) fn from_raw_buf(buf: RawBuf32<T>) -> Self {
+        Vec32 { buf }
+    }
+}
+
+impl<T> Drop for Vec32<T> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` elements are initialized, `buf` frees the memory afterwards.
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl<T> Default for Vec32<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for Vec32<T> {
+    fn clone(&self) -> Self {
+        self.as_slice().iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Vec32<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Vec32<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq> Eq for Vec32<T> {}
+
+impl<T: std::hash::Hash> std::hash::Hash for Vec32<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T> std::ops::Deref for Vec32<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> std::ops::DerefMut for Vec32<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> AsRef<[T]> for Vec32<T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> AsMut<[T]> for Vec32<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> Extend<T> for Vec32<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(u32::try_from(iter.size_hint().0).unwrap_or(u32::MAX));
+        for value in iter {
+            self.push(value);
+        }
+    }
+
+    fn extend_one(&mut self, item: T) {
+        self.push(item)
+    }
+
+    fn extend_reserve(&mut self, additional: usize) {
+        self.reserve(u32::try_from(additional).unwrap_or(u32::MAX))
+    }
+}
+
+impl<T> FromIterator<T> for Vec32<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut v = Self::new();
+        v.extend(iter);
+        v
+    }
+}
+
+impl<T> IntoIterator for Vec32<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        let buf = self.into_raw_buf();
+        let end = buf.len;
+        IntoIter { buf, start: 0, end }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Vec32<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+/// Owning iterator of `Vec32<T>`.
+pub struct IntoIter<T> {
+    buf: RawBuf32<T>,
+    start: u32,
+    end: u32,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        // SAFETY: elements in `start..end` are initialized and not yielded yet.
+        Some(unsafe { ptr::read(self.buf.ptr().add(self.start as usize - 1)) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: elements in `start..end` are initialized and not yielded yet.
+        Some(unsafe { ptr::read(self.buf.ptr().add(self.end as usize)) })
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> std::iter::FusedIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        // SAFETY: elements in `start..end` are initialized and not yielded yet.
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.buf.ptr().add(self.start as usize),
+                (self.end - self.start) as usize,
+            ))
+        }
+    }
+}
+
+/// Draining iterator of `Vec32<T>` and `Deque32<T>`.
+///
+/// The elements after the drained range are moved back when dropped.
+pub struct Drain<'a, T> {
+    ptr: *mut T,
+    len: &'a mut u32,
+    iter: std::ops::Range<u32>,
+    tail_start: u32,
+    tail_len: u32,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Drain<'a, T> {
+    /// Drains `range` out of the `*len` contiguous elements at `ptr`.
+    pub(super) fn new<R: RangeBounds<u32>>(ptr: *mut T, len: &'a mut u32, range: R) -> Self {
+        let range = std::slice::range(raw_range(range), ..*len as usize);
+        let (start, end) = (range.start as u32, range.end as u32);
+        let tail_len = *len - end;
+        *len = start;
+        Drain {
+            ptr,
+            len,
+            iter: start..end,
+            tail_start: end,
+            tail_len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        // SAFETY: elements in `iter` are initialized and not yielded yet.
+        self.iter.next().map(|i| unsafe { ptr::read(self.ptr.add(i as usize)) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        // SAFETY: elements in `iter` are initialized and not yielded yet.
+        self.iter
+            .next_back()
+            .map(|i| unsafe { ptr::read(self.ptr.add(i as usize)) })
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+impl<T> std::iter::FusedIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.by_ref().for_each(drop);
+        // SAFETY: the tail is initialized, and moved right after the kept elements.
+        unsafe {
+            ptr::copy(
+                self.ptr.add(self.tail_start as usize),
+                self.ptr.add(*self.len as usize),
+                self.tail_len as usize,
+            );
+        }
+        *self.len += self.tail_len;
+    }
+}
+
+mod impls {
+    use super::*;
+
+    impl<T> CollectionTrait for Vec32<T> {
+        type ElemType = T;
+    }
+
+    impl<T> Mutable for Vec32<T> {}
+    impl<T> Owned for Vec32<T> {}
+
+    impl<T> ExactSized for Vec32<T> {
+        type SizeType = u32;
+
+        fn is_empty(&self) -> bool {
+            self.is_empty()
+        }
+
+        fn len(&self) -> u32 {
+            self.len()
+        }
+    }
+
+    impl<T> Iterable for Vec32<T> {
+        type Iter<'a>
+        where
+            T: 'a,
+        = std::slice::Iter<'a, T>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.as_slice().iter()
+        }
+    }
+
+    impl<T> IterableMut for Vec32<T> {
+        type IterMut<'a>
+        where
+            T: 'a,
+        = std::slice::IterMut<'a, T>;
+
+        fn iter_mut(&mut self) -> Self::IterMut<'_> {
+            self.as_mut_slice().iter_mut()
+        }
+    }
+
+    impl<T> Range for Vec32<T> {
+        type RangeIter<'a>
+        where
+            T: 'a,
+        = std::slice::Iter<'a, T>;
+
+        fn range<R: RangeBounds<Self::SizeType>>(&self, range: R) -> Self::RangeIter<'_> {
+            self.as_slice()[std::slice::range(raw_range(range), ..self.buf.len as usize)].iter()
+        }
+    }
+
+    impl<T> RangeMut for Vec32<T> {
+        type RangeIterMut<'a>
+        where
+            T: 'a,
+        = std::slice::IterMut<'a, T>;
+
+        fn range_mut<R: RangeBounds<Self::SizeType>>(&mut self, range: R) -> Self::RangeIterMut<'_> {
+            let len = self.buf.len as usize;
+            self.as_mut_slice()[std::slice::range(raw_range(range), ..len)].iter_mut()
+        }
+    }
+
+    impl<T> DrainRange for Vec32<T> {
+        type DrainRangeIter<'a>
+        where
+            T: 'a,
+        = Drain<'a, T>;
+
+        fn drain_range<R: RangeBounds<Self::SizeType>>(&mut self, range: R) -> Self::DrainRangeIter<'_> {
+            self.drain(range)
+        }
+    }
+
+    impl<T> EmptyConstructible for Vec32<T> {
+        fn new() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T> Erasable for Vec32<T> {
+        fn clear(&mut self) {
+            self.clear()
+        }
+    }
+
+    impl<T: PartialEq> Contains for Vec32<T> {
+        fn contains(&self, value: &T) -> bool {
+            self.as_slice().contains(value)
+        }
+    }
+
+    impl<T> Retainable for Vec32<T> {
+        fn retain<F: FnMut(&T) -> bool>(&mut self, pred: F) {
+            self.retain(pred)
+        }
+    }
+
+    impl<T: Clone> Resizable for Vec32<T> {
+        fn resize(&mut self, new_len: Self::SizeType, value: T) {
+            self.resize(new_len, value)
+        }
+
+        fn resize_with(&mut self, new_len: Self::SizeType, generator: impl FnMut() -> T) {
+            self.resize_with(new_len, generator)
+        }
+
+        fn truncate(&mut self, len: Self::SizeType) {
+            self.truncate(len)
+        }
+    }
+
+    impl<T> Contiguous for Vec32<T> {
+        fn as_slice(&self) -> &[Self::ElemType] {
+            self.as_slice()
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [Self::ElemType] {
+            self.as_mut_slice()
+        }
+    }
+
+    impl<T> Sequential for Vec32<T> {
+        fn back(&self) -> Option<&Self::ElemType> {
+            self.last()
+        }
+
+        fn back_mut(&mut self) -> Option<&mut Self::ElemType> {
+            self.last_mut()
+        }
+
+        fn append(&mut self, other: &mut Self) {
+            self.append(other)
+        }
+
+        fn push_back(&mut self, elt: Self::ElemType) {
+            self.push(elt)
+        }
+
+        fn pop_back(&mut self) -> Option<Self::ElemType> {
+            self.pop()
+        }
+
+        fn remove(&mut self, at: Self::SizeType) -> Option<Self::ElemType> {
+            Some(self.remove(at))
+        }
+
+        fn split_off(&mut self, at: Self::SizeType) -> Self {
+            self.split_off(at)
+        }
+    }
+
+    impl<T> RandomAccess for Vec32<T> {
+        fn rotate_left(&mut self, mid: Self::SizeType) {
+            self.as_mut_slice().rotate_left(mid as usize)
+        }
+
+        fn rotate_right(&mut self, mid: Self::SizeType) {
+            self.as_mut_slice().rotate_right(mid as usize)
+        }
+
+        fn partition_point<P: FnMut(&Self::ElemType) -> bool>(&self, pred: P) -> Self::SizeType {
+            self.as_slice().partition_point(pred) as u32
+        }
+
+        fn swap(&mut self, i: Self::SizeType, j: Self::SizeType) {
+            self.as_mut_slice().swap(i as usize, j as usize)
+        }
+
+        fn get(&self, index: Self::SizeType) -> Option<&Self::ElemType> {
+            self.as_slice().get(index as usize)
+        }
+
+        fn get_mut(&mut self, index: Self::SizeType) -> Option<&mut Self::ElemType> {
+            self.as_mut_slice().get_mut(index as usize)
+        }
+
+        fn insert(&mut self, index: Self::SizeType, value: Self::ElemType) {
+            self.insert(index, value)
+        }
+
+        fn swap_remove_back(&mut self, index: Self::SizeType) -> Option<Self::ElemType> {
+            Some(self.swap_remove(index))
+        }
+    }
+
+    impl<T> CapacityAware for Vec32<T> {
+        type CapacityType = u32;
+
+        fn capacity(&self) -> u32 {
+            self.capacity()
+        }
+    }
+
+    impl<T> CapacityConstructible for Vec32<T> {
+        fn with_capacity(capacity: u32) -> Self {
+            Self::with_capacity(capacity)
+        }
+    }
+
+    impl<T> CapacityShrink for Vec32<T> {
+        fn shrink_to_fit(&mut self) {
+            self.shrink_to_fit()
+        }
+
+        fn shrink_to(&mut self, min_capacity: u32) {
+            self.shrink_to(min_capacity)
+        }
+    }
+
+    impl<T> CapacityReserve for Vec32<T> {
+        fn reserve(&mut self, additional: u32) {
+            self.reserve(additional)
+        }
+    }
+
+    impl<T> CapacityTryReserve for Vec32<T> {
+        type TryReserveError = CompactReserveError;
+
+        fn try_reserve(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+            self.try_reserve(additional)
+        }
+    }
+
+    impl<T> CapacityReserveExact for Vec32<T> {
+        fn reserve_exact(&mut self, additional: u32) {
+            self.reserve_exact(additional)
+        }
+
+        fn try_reserve_exact(&mut self, additional: u32) -> Result<(), CompactReserveError> {
+            self.try_reserve_exact(additional)
+        }
+    }
+
+    impl<T> CapacityHint for Vec32<T> {
+        fn reserve_hint(&mut self, additional: usize) {
+            if let Ok(additional) = u32::try_from(additional) {
+                let _ = self.try_reserve(additional);
+            }
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for Vec32<T> {
+        fn heap_size_of_children(&self) -> usize {
+            self.capacity() as usize * std::mem::size_of::<T>() + self.as_slice().heap_size_of_children()
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Equality, ordering and hashing across different collection types.
+//!
+//! Sequences are compared element-wise in iteration order, while sets and maps are compared regardless of the
+//! iteration order, so that `Vec<T>` can be compared with `VecDeque<T>` and `HashSet<T>` with `BTreeSet<T>`.
+use crate::{
+    associated::{AssociatedMap, AssociatedSet},
+    exact_size::ExactSized,
+    iter::{AssociatedIterable, Iterable},
+};
+use std::{
+    cmp::Ordering,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+/// Sequences that can be compared element-wise with other sequences.
+///
+/// NOTE: Sets and maps are sequences as well, use `SetEq` and `MapEq` to ignore their iteration order.
+pub trait CollectionEq<Rhs: ?Sized = Self>: Iterable {
+    /// Returns true if `self` and `other` yield equal elements in the same order.
+    fn collection_eq(&self, other: &Rhs) -> bool;
+
+    /// Returns true if `self` and `other` differ in any element.
+    fn collection_ne(&self, other: &Rhs) -> bool {
+        !self.collection_eq(other)
+    }
+}
+
+impl<L: Iterable, R: Iterable> CollectionEq<R> for L
+where
+    L::ElemType: PartialEq<R::ElemType>,
+{
+    fn collection_eq(&self, other: &R) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+/// Sequences that can be compared lexicographically with other sequences.
+pub trait SequenceOrd<Rhs: ?Sized = Self>: Iterable {
+    /// Compares `self` and `other` lexicographically.
+    fn sequence_partial_cmp(&self, other: &Rhs) -> Option<Ordering>;
+
+    /// Compares `self` and `other` lexicographically.
+    fn sequence_cmp(&self, other: &Rhs) -> Ordering
+    where
+        Rhs: Iterable<ElemType = Self::ElemType>,
+        Self::ElemType: Ord;
+}
+
+impl<L: Iterable, R: Iterable> SequenceOrd<R> for L
+where
+    L::ElemType: PartialOrd<R::ElemType>,
+{
+    fn sequence_partial_cmp(&self, other: &R) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+
+    fn sequence_cmp(&self, other: &R) -> Ordering
+    where
+        R: Iterable<ElemType = Self::ElemType>,
+        Self::ElemType: Ord,
+    {
+        self.iter().cmp(other.iter())
+    }
+}
+
+/// Sets that can be compared with other sets regardless of the iteration order.
+pub trait SetEq<Rhs: ?Sized = Self>: AssociatedSet + ExactSized {
+    /// Returns true if `self` and `other` contain the same values.
+    fn set_eq(&self, other: &Rhs) -> bool;
+
+    /// Returns true if all values of `self` are contained in `other`.
+    fn set_is_subset(&self, other: &Rhs) -> bool;
+}
+
+impl<L, R> SetEq<R> for L
+where
+    L: AssociatedSet + ExactSized,
+    R: AssociatedSet<ElemType = L::ElemType> + ExactSized<SizeType = L::SizeType>,
+{
+    fn set_eq(&self, other: &R) -> bool {
+        self.len() == other.len() && self.set_is_subset(other)
+    }
+
+    fn set_is_subset(&self, other: &R) -> bool {
+        self.iter().all(|v| other.contains(v))
+    }
+}
+
+/// Maps that can be compared with other maps regardless of the iteration order.
+pub trait MapEq<Rhs: ?Sized = Self>: AssociatedIterable + ExactSized {
+    /// Returns true if `self` and `other` contain the same keys with equal values.
+    fn map_eq(&self, other: &Rhs) -> bool;
+}
+
+impl<L, R> MapEq<R> for L
+where
+    L: AssociatedIterable + ExactSized,
+    R: AssociatedMap<KeyType = L::KeyType> + ExactSized<SizeType = L::SizeType>,
+    L::ValueType: PartialEq<R::ValueType>,
+{
+    fn map_eq(&self, other: &R) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|(k, v)| match other.get(k) {
+                Some(w) => *v == *w,
+                None => false,
+            })
+    }
+}
+
+/// Feeds the elements and the length of `c` into `state`, consistent with `CollectionEq`.
+pub fn sequence_hash<C: Iterable, H: Hasher>(c: &C, state: &mut H)
+where
+    C::ElemType: Hash,
+{
+    let mut len = 0usize;
+    for v in c.iter() {
+        v.hash(state);
+        len += 1;
+    }
+    state.write_usize(len);
+}
+
+/// Feeds `c` into `state` regardless of the iteration order, consistent with `SetEq`.
+///
+/// NOTE: Elements are hashed with a fixed-key `DefaultHasher`, not with `H`; only the combined result reaches `state`.
+pub fn set_hash<C: AssociatedSet, H: Hasher>(c: &C, state: &mut H)
+where
+    C::ElemType: Hash,
+{
+    unordered_hash(c.iter(), state)
+}
+
+/// Feeds `c` into `state` regardless of the iteration order, consistent with `MapEq`.
+///
+/// NOTE: Elements are hashed with a fixed-key `DefaultHasher`, not with `H`; only the combined result reaches `state`.
+pub fn map_hash<C: AssociatedIterable, H: Hasher>(c: &C, state: &mut H)
+where
+    C::KeyType: Hash,
+    C::ValueType: Hash,
+{
+    unordered_hash(c.iter(), state)
+}
+
+/// Combines the hashes of all items with a commutative operation, so that the iteration order is irrelevant.
+/// Each item is hashed by a fresh `DefaultHasher::new()`, so the per-item hashes are independent of `state`'s keys.
+fn unordered_hash<T: Hash, H: Hasher>(iter: impl Iterator<Item = T>, state: &mut H) {
+    let (len, sum) = iter.fold((0usize, 0u64), |(len, sum), v| {
+        let mut hasher = DefaultHasher::new();
+        v.hash(&mut hasher);
+        (len + 1, sum.wrapping_add(hasher.finish()))
+    });
+    state.write_usize(len);
+    state.write_u64(sum);
+}
+
+/// Wrapper comparing and hashing sequences element-wise, e.g. `BySequence(vec) == BySequence(vec_deque)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BySequence<C>(pub C);
+
+impl<L: Iterable, R: Iterable> PartialEq<BySequence<R>> for BySequence<L>
+where
+    L::ElemType: PartialEq<R::ElemType>,
+{
+    fn eq(&self, other: &BySequence<R>) -> bool {
+        self.0.collection_eq(&other.0)
+    }
+}
+
+impl<C: Iterable> Eq for BySequence<C> where C::ElemType: Eq {}
+
+impl<L: Iterable, R: Iterable> PartialOrd<BySequence<R>> for BySequence<L>
+where
+    L::ElemType: PartialOrd<R::ElemType>,
+{
+    fn partial_cmp(&self, other: &BySequence<R>) -> Option<Ordering> {
+        self.0.sequence_partial_cmp(&other.0)
+    }
+}
+
+impl<C: Iterable> Ord for BySequence<C>
+where
+    C::ElemType: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.sequence_cmp(&other.0)
+    }
+}
+
+impl<C: Iterable> Hash for BySequence<C>
+where
+    C::ElemType: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        sequence_hash(&self.0, state)
+    }
+}
+
+/// Wrapper comparing and hashing sets regardless of the iteration order, e.g. `BySet(hash_set) == BySet(btree_set)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BySet<C>(pub C);
+
+impl<L, R> PartialEq<BySet<R>> for BySet<L>
+where
+    L: AssociatedSet + ExactSized,
+    R: AssociatedSet<ElemType = L::ElemType> + ExactSized<SizeType = L::SizeType>,
+{
+    fn eq(&self, other: &BySet<R>) -> bool {
+        self.0.set_eq(&other.0)
+    }
+}
+
+impl<C: AssociatedSet + ExactSized> Eq for BySet<C> where C::ElemType: Eq {}
+
+impl<C: AssociatedSet + ExactSized> Hash for BySet<C>
+where
+    C::ElemType: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        set_hash(&self.0, state)
+    }
+}
+
+/// Wrapper comparing and hashing maps regardless of the iteration order, e.g. `ByMap(hash_map) == ByMap(btree_map)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByMap<C>(pub C);
+
+impl<L, R> PartialEq<ByMap<R>> for ByMap<L>
+where
+    L: AssociatedIterable + ExactSized,
+    R: AssociatedMap<KeyType = L::KeyType> + ExactSized<SizeType = L::SizeType>,
+    L::ValueType: PartialEq<R::ValueType>,
+{
+    fn eq(&self, other: &ByMap<R>) -> bool {
+        self.0.map_eq(&other.0)
+    }
+}
+
+impl<C: AssociatedIterable + AssociatedMap + ExactSized> Eq for ByMap<C>
+where
+    C::KeyType: Eq,
+    C::ValueType: Eq,
+{
+}
+
+impl<C: AssociatedIterable + AssociatedMap + ExactSized> Hash for ByMap<C>
+where
+    C::KeyType: Hash,
+    C::ValueType: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        map_hash(&self.0, state)
+    }
+}
@@ -0,0 +1,347 @@
+//! Counts of hashable or ordered keys, similar to Python's `collections.Counter`.
+use crate::{
+    associated::AssociatedMap,
+    elem::{AssociatedCollectionTrait, CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    iter::{AssociatedIterable, Iterable},
+    misc::{AssociatedContains, AssociatedRetainable, EmptyConstructible, Erasable},
+};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap},
+    fmt,
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::{Add, Sub},
+};
+
+/// Map from keys to signed counts, over any map `M` from `K` to `i64`.
+///
+/// Counts may be zero or negative after `subtract`, while `+` and `-` only keep positive counts.
+pub struct Counter<K, M = HashMap<K, i64>> {
+    map: M,
+    _marker: PhantomData<fn() -> K>,
+}
+
+impl<K, M> Counter<K, M> {
+    /// Wraps a map of counts.
+    pub fn from_map(map: M) -> Self {
+        Counter {
+            map,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying map of counts.
+    pub fn as_map(&self) -> &M {
+        &self.map
+    }
+
+    /// Unwraps the underlying map of counts.
+    pub fn into_map(self) -> M {
+        self.map
+    }
+}
+
+impl<K, M> Counter<K, M>
+where
+    M: AssociatedMap<KeyType = K, ValueType = i64> + AssociatedIterable + Owned,
+{
+    /// Counts the elements of any iterable collection.
+    pub fn from_iterable<C: Iterable<ElemType = K>>(c: &C) -> Self
+    where
+        K: Clone,
+        M: EmptyConstructible,
+    {
+        c.iter().cloned().collect()
+    }
+
+    /// Returns the count of `key`, zero if absent.
+    pub fn count(&self, key: &K) -> i64 {
+        self.map.get(key).copied().unwrap_or(0)
+    }
+
+    /// Adds `n` to the count of `key`, returns the new count.
+    pub fn add_count(&mut self, key: K, n: i64) -> i64 {
+        match self.map.get_mut(&key) {
+            Some(count) => {
+                *count += n;
+                *count
+            }
+            None => {
+                self.map.insert(key, n);
+                n
+            }
+        }
+    }
+
+    /// Counts one more occurrence of each key.
+    pub fn update<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|key| {
+            self.add_count(key, 1);
+        })
+    }
+
+    /// Counts one less occurrence of each key, keeping zero and negative counts.
+    pub fn subtract<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|key| {
+            self.add_count(key, -1);
+        })
+    }
+
+    /// Returns the sum of all counts.
+    pub fn total(&self) -> i64 {
+        self.map.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Returns the `n` keys with the highest counts, in descending order of counts.
+    ///
+    /// Ties keep the iteration order. Uses a heap of at most `n + 1` entries.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, i64)> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(n + 1);
+        for (rank, (key, count)) in self.map.iter().enumerate() {
+            heap.push(Reverse(Ranked {
+                count: *count,
+                rank,
+                key,
+            }));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ranked)| (ranked.key, ranked.count))
+            .collect()
+    }
+}
+
+/// Counted key ordered by count, then by reverse iteration rank.
+struct Ranked<'a, K> {
+    count: i64,
+    rank: usize,
+    key: &'a K,
+}
+
+impl<'a, K> PartialEq for Ranked<'a, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a, K> Eq for Ranked<'a, K> {}
+
+impl<'a, K> PartialOrd for Ranked<'a, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, K> Ord for Ranked<'a, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.count.cmp(&other.count).then(other.rank.cmp(&self.rank))
+    }
+}
+
+impl<K, M: EmptyConstructible + Owned> Counter<K, M> {
+    /// Creates an empty counter.
+    pub fn new() -> Self {
+        Self::from_map(M::new())
+    }
+}
+
+impl<K, M: Default> Default for Counter<K, M> {
+    fn default() -> Self {
+        Self::from_map(M::default())
+    }
+}
+
+impl<K, M: Clone> Clone for Counter<K, M> {
+    fn clone(&self) -> Self {
+        Self::from_map(self.map.clone())
+    }
+}
+
+impl<K, M: fmt::Debug> fmt::Debug for Counter<K, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Counter").field(&self.map).finish()
+    }
+}
+
+impl<K, M: PartialEq> PartialEq for Counter<K, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K, M: Eq> Eq for Counter<K, M> {}
+
+impl<K, M> From<M> for Counter<K, M> {
+    fn from(map: M) -> Self {
+        Self::from_map(map)
+    }
+}
+
+impl<K, M> FromIterator<K> for Counter<K, M>
+where
+    M: AssociatedMap<KeyType = K, ValueType = i64> + AssociatedIterable + EmptyConstructible + Owned,
+{
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.update(iter);
+        counter
+    }
+}
+
+/// Adds the given counts.
+impl<K, M> Extend<(K, i64)> for Counter<K, M>
+where
+    M: AssociatedMap<KeyType = K, ValueType = i64> + AssociatedIterable + Owned,
+{
+    fn extend<I: IntoIterator<Item = (K, i64)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(key, n)| {
+            self.add_count(key, n);
+        })
+    }
+}
+
+impl<K, M: IntoIterator<Item = (K, i64)>> IntoIterator for Counter<K, M> {
+    type Item = (K, i64);
+    type IntoIter = M::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+/// Adds counts, keeping only positive ones.
+impl<K, M> Add for Counter<K, M>
+where
+    M: AssociatedMap<KeyType = K, ValueType = i64>
+        + CollectionTrait<ElemType = (K, i64)>
+        + AssociatedIterable
+        + AssociatedRetainable
+        + Owned,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self.extend(rhs.map);
+        self.map.retain(|_, count| *count > 0);
+        self
+    }
+}
+
+/// Subtracts counts, keeping only positive ones.
+impl<K, M> Sub for Counter<K, M>
+where
+    M: AssociatedMap<KeyType = K, ValueType = i64>
+        + CollectionTrait<ElemType = (K, i64)>
+        + AssociatedIterable
+        + AssociatedRetainable
+        + Owned,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self.extend(rhs.map.into_iter().map(|(key, count)| (key, -count)));
+        self.map.retain(|_, count| *count > 0);
+        self
+    }
+}
+
+mod impls {
+    use super::*;
+
+    impl<K, M> CollectionTrait for Counter<K, M> {
+        type ElemType = (K, i64);
+    }
+
+    impl<K, M> AssociatedCollectionTrait for Counter<K, M> {
+        type KeyType = K;
+        type ValueType = i64;
+    }
+
+    impl<K, M: Mutable> Mutable for Counter<K, M> {}
+
+    impl<K, M: CollectionTrait<ElemType = (K, i64)> + Owned> Owned for Counter<K, M> {}
+
+    impl<K, M: ExactSized> ExactSized for Counter<K, M> {
+        type SizeType = M::SizeType;
+
+        fn is_empty(&self) -> bool {
+            self.map.is_empty()
+        }
+
+        fn len(&self) -> M::SizeType {
+            self.map.len()
+        }
+    }
+
+    impl<K, M: AssociatedIterable<KeyType = K, ValueType = i64>> AssociatedIterable for Counter<K, M> {
+        type Iter<'a>
+        where
+            K: 'a,
+        = M::Iter<'a>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.map.iter()
+        }
+    }
+
+    impl<K, M: AssociatedContains<KeyType = K, ValueType = i64>> AssociatedContains for Counter<K, M> {
+        fn contains_key(&self, key: &K) -> bool {
+            self.map.contains_key(key)
+        }
+    }
+
+    impl<K, M: AssociatedRetainable<KeyType = K, ValueType = i64>> AssociatedRetainable for Counter<K, M> {
+        fn retain<F: FnMut(&K, &mut i64) -> bool>(&mut self, pred: F) {
+            self.map.retain(pred)
+        }
+    }
+
+    impl<K, M> AssociatedMap for Counter<K, M>
+    where
+        M: AssociatedMap<KeyType = K, ValueType = i64> + CollectionTrait<ElemType = (K, i64)> + Owned,
+    {
+        fn insert(&mut self, key: K, value: i64) -> Option<i64> {
+            self.map.insert(key, value)
+        }
+
+        fn remove(&mut self, key: &K) -> Option<i64> {
+            self.map.remove(key)
+        }
+
+        fn remove_entry(&mut self, key: &K) -> Option<(K, i64)> {
+            self.map.remove_entry(key)
+        }
+
+        fn get(&self, key: &K) -> Option<&i64> {
+            self.map.get(key)
+        }
+
+        fn get_mut(&mut self, key: &K) -> Option<&mut i64> {
+            self.map.get_mut(key)
+        }
+
+        fn get_key_value(&self, key: &K) -> Option<(&K, &i64)> {
+            self.map.get_key_value(key)
+        }
+    }
+
+    impl<K, M: EmptyConstructible + Owned> EmptyConstructible for Counter<K, M> {
+        fn new() -> Self {
+            Counter::from_map(M::new())
+        }
+    }
+
+    impl<K, M: Erasable + Owned> Erasable for Counter<K, M> {
+        fn clear(&mut self) {
+            self.map.clear()
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Maps creating missing values on access, similar to Python's `collections.defaultdict`.
+use crate::{
+    associated::{AssociatedMap, AssociatedMapIter},
+    elem::{AssociatedCollectionTrait, CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    iter::{AssociatedIterable, AssociatedIterableMut},
+    misc::{AssociatedContains, AssociatedRetainable, Erasable},
+};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+/// Map `M` whose missing values are created by `factory` from their key when accessed mutably.
+///
+/// `S` is the `AssociatedMapIter<S>` parameter of `M`, inferred on construction.
+pub struct DefaultMap<M, F, S> {
+    map: M,
+    factory: F,
+    _marker: PhantomData<fn() -> S>,
+}
+
+impl<M, F, S> DefaultMap<M, F, S> {
+    /// Wraps `map`, creating missing values with `factory`.
+    pub fn new(map: M, factory: F) -> Self
+    where
+        M: AssociatedMapIter<S> + Owned,
+        F: Fn(&M::KeyType) -> M::ValueType,
+    {
+        DefaultMap {
+            map,
+            factory,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying map.
+    pub fn as_map(&self) -> &M {
+        &self.map
+    }
+
+    /// Unwraps the underlying map.
+    pub fn into_map(self) -> M {
+        self.map
+    }
+
+    /// Returns the value factory.
+    pub fn factory(&self) -> &F {
+        &self.factory
+    }
+}
+
+impl<K, V: Default, M, S> DefaultMap<M, fn(&K) -> V, S>
+where
+    M: AssociatedMapIter<S, KeyType = K, ValueType = V> + Owned,
+{
+    /// Wraps `map`, creating missing values with `V::default()`.
+    pub fn with_default(map: M) -> Self {
+        Self::new(map, |_| V::default())
+    }
+}
+
+impl<K, V, M, F, S> DefaultMap<M, F, S>
+where
+    M: AssociatedMapIter<S, KeyType = K, ValueType = V> + Owned,
+    F: Fn(&K) -> V,
+{
+    /// Returns the value of `key`, inserting the one created by the factory if absent.
+    ///
+    /// FIXME: bounded by the lifetime requirements of `AssociatedMapIter::TryInsertError`
+    pub fn get_or_default_mut<'a>(&'a mut self, key: K) -> &'a mut V
+    where
+        K: 'a,
+        V: 'a,
+        S: 'a,
+    {
+        if self.map.contains_key(&key) {
+            return self.map.get_mut(&key).unwrap();
+        }
+        let value = (self.factory)(&key);
+        match self.map.try_insert(key, value) {
+            Ok(value) => value,
+            Err(_) => unreachable!("key is absent"),
+        }
+    }
+}
+
+/// Panics if `key` is absent, as values can't be created through a shared reference.
+impl<K, V, M, F, S> Index<&K> for DefaultMap<M, F, S>
+where
+    M: AssociatedMap<KeyType = K, ValueType = V>,
+{
+    type Output = V;
+
+    fn index(&self, key: &K) -> &V {
+        self.map.get(key).expect("key not found")
+    }
+}
+
+/// Inserts the value created by the factory if `key` is absent, same as `get_or_default_mut(key.clone())`.
+///
+/// NOTE: bounded on projections rather than `K`/`V` parameters, so that their lifetime follows from `M`'s.
+impl<M, F, S> IndexMut<&M::KeyType> for DefaultMap<M, F, S>
+where
+    M: AssociatedMapIter<S> + Owned,
+    M::KeyType: Clone,
+    F: Fn(&M::KeyType) -> M::ValueType,
+{
+    fn index_mut(&mut self, key: &M::KeyType) -> &mut M::ValueType {
+        self.get_or_default_mut(key.clone())
+    }
+}
+
+impl<M: Clone, F: Clone, S> Clone for DefaultMap<M, F, S> {
+    fn clone(&self) -> Self {
+        DefaultMap {
+            map: self.map.clone(),
+            factory: self.factory.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M: fmt::Debug, F, S> fmt::Debug for DefaultMap<M, F, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DefaultMap").field(&self.map).finish()
+    }
+}
+
+impl<M: Extend<T>, F, S, T> Extend<T> for DefaultMap<M, F, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.map.extend(iter)
+    }
+}
+
+impl<M: IntoIterator, F, S> IntoIterator for DefaultMap<M, F, S> {
+    type Item = M::Item;
+    type IntoIter = M::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+mod impls {
+    use super::*;
+
+    impl<M: CollectionTrait, F, S> CollectionTrait for DefaultMap<M, F, S> {
+        type ElemType = M::ElemType;
+    }
+
+    impl<M: AssociatedCollectionTrait, F, S> AssociatedCollectionTrait for DefaultMap<M, F, S> {
+        type KeyType = M::KeyType;
+        type ValueType = M::ValueType;
+    }
+
+    impl<M: Mutable, F, S> Mutable for DefaultMap<M, F, S> {}
+
+    impl<M: Owned, F, S> Owned for DefaultMap<M, F, S> {}
+
+    impl<M: ExactSized, F, S> ExactSized for DefaultMap<M, F, S> {
+        type SizeType = M::SizeType;
+
+        fn is_empty(&self) -> bool {
+            self.map.is_empty()
+        }
+
+        fn len(&self) -> M::SizeType {
+            self.map.len()
+        }
+    }
+
+    impl<M: AssociatedIterable, F, S> AssociatedIterable for DefaultMap<M, F, S> {
+        type Iter<'a>
+        where
+            M::KeyType: 'a,
+            M::ValueType: 'a,
+        = M::Iter<'a>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.map.iter()
+        }
+    }
+
+    impl<M: AssociatedIterableMut, F, S> AssociatedIterableMut for DefaultMap<M, F, S> {
+        type IterMut<'a>
+        where
+            M::KeyType: 'a,
+            M::ValueType: 'a,
+        = M::IterMut<'a>;
+
+        fn iter_mut(&mut self) -> Self::IterMut<'_> {
+            self.map.iter_mut()
+        }
+    }
+
+    impl<M: AssociatedContains, F, S> AssociatedContains for DefaultMap<M, F, S> {
+        fn contains_key(&self, key: &M::KeyType) -> bool {
+            self.map.contains_key(key)
+        }
+    }
+
+    impl<M: AssociatedRetainable, F, S> AssociatedRetainable for DefaultMap<M, F, S> {
+        fn retain<P: FnMut(&M::KeyType, &mut M::ValueType) -> bool>(&mut self, pred: P) {
+            self.map.retain(pred)
+        }
+    }
+
+    impl<M: AssociatedMap + Owned, F, S> AssociatedMap for DefaultMap<M, F, S> {
+        fn insert(&mut self, key: M::KeyType, value: M::ValueType) -> Option<M::ValueType> {
+            self.map.insert(key, value)
+        }
+
+        fn remove(&mut self, key: &M::KeyType) -> Option<M::ValueType> {
+            self.map.remove(key)
+        }
+
+        fn remove_entry(&mut self, key: &M::KeyType) -> Option<(M::KeyType, M::ValueType)> {
+            self.map.remove_entry(key)
+        }
+
+        /// Returns `None` if `key` is absent, see `get_or_default_mut` to create its value.
+        fn get(&self, key: &M::KeyType) -> Option<&M::ValueType> {
+            self.map.get(key)
+        }
+
+        fn get_mut(&mut self, key: &M::KeyType) -> Option<&mut M::ValueType> {
+            self.map.get_mut(key)
+        }
+
+        fn get_key_value(&self, key: &M::KeyType) -> Option<(&M::KeyType, &M::ValueType)> {
+            self.map.get_key_value(key)
+        }
+    }
+
+    impl<M: Erasable + Owned, F, S> Erasable for DefaultMap<M, F, S> {
+        fn clear(&mut self) {
+            self.map.clear()
+        }
+    }
+}
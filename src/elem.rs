@@ -108,23 +108,24 @@ mod impls {
 
     impl<T> Mutable for &mut [T] {}
     impl<T, const N: usize> Mutable for [T; N] {}
+    impl<T, A: std::alloc::Allocator> Mutable for Box<[T], A> {}
     impl Mutable for String {}
     impl<T, A: std::alloc::Allocator> Mutable for Vec<T, A> {}
-    impl<T> Mutable for std::collections::VecDeque<T> {}
-    impl<T> Mutable for std::collections::LinkedList<T> {}
-    impl<T> Mutable for std::collections::BinaryHeap<T> {}
-    impl<T> Mutable for std::collections::BTreeSet<T> {}
+    impl<T, A: std::alloc::Allocator> Mutable for std::collections::VecDeque<T, A> {}
+    impl<T, A: std::alloc::Allocator> Mutable for std::collections::LinkedList<T, A> {}
+    impl<T, A: std::alloc::Allocator> Mutable for std::collections::BinaryHeap<T, A> {}
+    impl<T, A: std::alloc::Allocator + Clone> Mutable for std::collections::BTreeSet<T, A> {}
     impl<T, S> Mutable for std::collections::HashSet<T, S> {}
-    impl<K, V> Mutable for std::collections::BTreeMap<K, V> {}
+    impl<K, V, A: std::alloc::Allocator + Clone> Mutable for std::collections::BTreeMap<K, V, A> {}
     impl<K, V, S> Mutable for std::collections::HashMap<K, V, S> {}
 
     impl<T, A: std::alloc::Allocator> Owned for Vec<T, A> {}
-    impl<T> Owned for std::collections::VecDeque<T> {}
-    impl<T> Owned for std::collections::LinkedList<T> {}
-    impl<T> Owned for std::collections::BinaryHeap<T> {}
-    impl<T> Owned for std::collections::BTreeSet<T> {}
+    impl<T, A: std::alloc::Allocator> Owned for std::collections::VecDeque<T, A> {}
+    impl<T, A: std::alloc::Allocator> Owned for std::collections::LinkedList<T, A> {}
+    impl<T, A: std::alloc::Allocator> Owned for std::collections::BinaryHeap<T, A> {}
+    impl<T, A: std::alloc::Allocator + Clone> Owned for std::collections::BTreeSet<T, A> {}
     impl<T, S> Owned for std::collections::HashSet<T, S> {}
-    impl<K, V> Owned for std::collections::BTreeMap<K, V> {}
+    impl<K, V, A: std::alloc::Allocator + Clone> Owned for std::collections::BTreeMap<K, V, A> {}
     impl<K, V, S> Owned for std::collections::HashMap<K, V, S> {}
 
     impl<T, const N: usize> CollectionTrait for [T; N] {
@@ -138,11 +139,18 @@ mod impls {
         type ElemType = T;
     }
 
-    impl<T> CollectionTrait for std::collections::VecDeque<T> {
+    impl<T, A: std::alloc::Allocator> CollectionTrait for Box<[T], A> {
         type ElemType = T;
     }
 
-    impl<T> CollectionTrait for std::collections::LinkedList<T> {
+    // NOTE: `Box` is `#[fundamental]`, so `ExtendOwned`/`IntoIteratorOwned` for `Box<[T], A>` overlaps the blanket
+    // implementations above, and `Box<[T], A>` cannot be a `Collection`.
+
+    impl<T, A: std::alloc::Allocator> CollectionTrait for std::collections::VecDeque<T, A> {
+        type ElemType = T;
+    }
+
+    impl<T, A: std::alloc::Allocator> CollectionTrait for std::collections::LinkedList<T, A> {
         type ElemType = T;
     }
 
@@ -150,11 +158,11 @@ mod impls {
         type ElemType = T;
     }
 
-    impl<T> CollectionTrait for std::collections::BTreeSet<T> {
+    impl<T, A: std::alloc::Allocator + Clone> CollectionTrait for std::collections::BTreeSet<T, A> {
         type ElemType = T;
     }
 
-    impl<T> CollectionTrait for std::collections::BinaryHeap<T> {
+    impl<T, A: std::alloc::Allocator> CollectionTrait for std::collections::BinaryHeap<T, A> {
         type ElemType = T;
     }
 
@@ -162,7 +170,7 @@ mod impls {
         type ElemType = (K, V);
     }
 
-    impl<K, V> CollectionTrait for std::collections::BTreeMap<K, V> {
+    impl<K, V, A: std::alloc::Allocator + Clone> CollectionTrait for std::collections::BTreeMap<K, V, A> {
         type ElemType = (K, V);
     }
 
@@ -171,7 +179,7 @@ mod impls {
         type ValueType = ();
     }
 
-    impl<K> AssociatedCollectionTrait for std::collections::BTreeSet<K> {
+    impl<K, A: std::alloc::Allocator + Clone> AssociatedCollectionTrait for std::collections::BTreeSet<K, A> {
         type KeyType = K;
         type ValueType = ();
     }
@@ -181,7 +189,7 @@ mod impls {
         type ValueType = V;
     }
 
-    impl<K, V> AssociatedCollectionTrait for std::collections::BTreeMap<K, V> {
+    impl<K, V, A: std::alloc::Allocator + Clone> AssociatedCollectionTrait for std::collections::BTreeMap<K, V, A> {
         type KeyType = K;
         type ValueType = V;
     }
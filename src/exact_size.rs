@@ -51,6 +51,16 @@ mod impls {
         }
     }
 
+    impl<T, A: std::alloc::Allocator> ExactSized for Box<[T], A> {
+        fn is_empty(&self) -> bool {
+            <[T]>::is_empty(self)
+        }
+
+        fn len(&self) -> usize {
+            <[T]>::len(self)
+        }
+    }
+
     impl<T, const N: usize> ExactSized for [T; N] {
         fn is_empty(&self) -> bool {
             N == 0
@@ -63,12 +73,12 @@ mod impls {
 
     exact_sized_impls!(
         [T, A: std::alloc::Allocator => Vec<T, A>];
-        [T => std::collections::VecDeque<T>];
-        [T => std::collections::LinkedList<T>];
-        [T => std::collections::BTreeSet<T>];
+        [T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [T, A: std::alloc::Allocator => std::collections::LinkedList<T, A>];
+        [T, A: std::alloc::Allocator | Clone => std::collections::BTreeSet<T, A>];
         [T, S: std::hash::BuildHasher => std::collections::HashSet<T, S>];
-        [T => std::collections::BinaryHeap<T>];
-        [K, V => std::collections::BTreeMap<K, V>];
+        [T, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
+        [K, V, A: std::alloc::Allocator | Clone => std::collections::BTreeMap<K, V, A>];
         [K, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>];
     );
 }
@@ -0,0 +1,197 @@
+//! Fallible insertions, reporting allocation failures instead of aborting.
+//!
+//! All traits are implemented for capacity-aware collections by reserving room for one more element before
+//! inserting it. Bounded collections report being full through their `TryReserveError`.
+use crate::{
+    associated::{AssociatedMap, AssociatedSet},
+    capacity::CapacityTryReserve,
+    elem::{AssociatedCollectionTrait, ExtendOwned, Owned},
+    sequential::{DoubleEnded, Sequential},
+};
+use std::{fmt, iter};
+
+/// Error of fallible insertions, giving back what was not inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryInsertError<T, E> {
+    /// Element(s) not inserted
+    pub elem: T,
+    /// Reason of the failure
+    pub error: E,
+}
+
+impl<T, E> TryInsertError<T, E> {
+    /// Returns the element(s) not inserted.
+    pub fn into_elem(self) -> T {
+        self.elem
+    }
+}
+
+impl<T, E: fmt::Display> fmt::Display for TryInsertError<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to insert: {}", self.error)
+    }
+}
+
+impl<T: fmt::Debug, E: std::error::Error + 'static> std::error::Error for TryInsertError<T, E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Result of fallible insertions returning `R`, giving back `T` on failure.
+pub type TryInsertResult<R, T, E> = Result<R, TryInsertError<T, E>>;
+
+/// Sequences that can push elements without aborting on allocation failures.
+pub trait TryPush: Sequential + CapacityTryReserve {
+    /// Appends an element to the back, or gives it back if there is no room.
+    fn try_push_back(&mut self, elt: Self::ElemType) -> TryInsertResult<(), Self::ElemType, Self::TryReserveError>
+    where
+        Self: Owned;
+
+    /// Prepends an element to the front, or gives it back if there is no room.
+    fn try_push_front(&mut self, elt: Self::ElemType) -> TryInsertResult<(), Self::ElemType, Self::TryReserveError>
+    where
+        Self: DoubleEnded + Owned;
+}
+
+impl<C: Sequential + CapacityTryReserve> TryPush for C
+where
+    C::CapacityType: From<u8>,
+{
+    fn try_push_back(&mut self, elt: Self::ElemType) -> TryInsertResult<(), Self::ElemType, Self::TryReserveError>
+    where
+        Self: Owned,
+    {
+        match self.try_reserve(1.into()) {
+            Ok(()) => {
+                self.push_back(elt);
+                Ok(())
+            }
+            Err(error) => Err(TryInsertError { elem: elt, error }),
+        }
+    }
+
+    fn try_push_front(&mut self, elt: Self::ElemType) -> TryInsertResult<(), Self::ElemType, Self::TryReserveError>
+    where
+        Self: DoubleEnded + Owned,
+    {
+        match self.try_reserve(1.into()) {
+            Ok(()) => {
+                self.push_front(elt);
+                Ok(())
+            }
+            Err(error) => Err(TryInsertError { elem: elt, error }),
+        }
+    }
+}
+
+/// Sets that can insert values without aborting on allocation failures.
+///
+/// NOTE: `try_insert` is taken by `AssociatedMapIter` for occupied entries.
+pub trait TryInsert: AssociatedSet + CapacityTryReserve {
+    /// Adds a value to the set, returning whether it was newly inserted, or gives it back if there is no room.
+    fn try_insert_value(
+        &mut self,
+        value: Self::ElemType,
+    ) -> TryInsertResult<bool, Self::ElemType, Self::TryReserveError>
+    where
+        Self: Owned;
+}
+
+impl<C: AssociatedSet + CapacityTryReserve> TryInsert for C
+where
+    C::CapacityType: From<u8>,
+{
+    fn try_insert_value(
+        &mut self,
+        value: Self::ElemType,
+    ) -> TryInsertResult<bool, Self::ElemType, Self::TryReserveError>
+    where
+        Self: Owned,
+    {
+        match self.try_reserve(1.into()) {
+            Ok(()) => Ok(self.insert(value)),
+            Err(error) => Err(TryInsertError { elem: value, error }),
+        }
+    }
+}
+
+/// Key-value pair of a map.
+pub type KeyValue<M> = (<M as AssociatedCollectionTrait>::KeyType, <M as AssociatedCollectionTrait>::ValueType);
+
+/// Maps that can insert key-value pairs without aborting on allocation failures.
+pub trait AssociatedTryInsert: AssociatedMap + CapacityTryReserve {
+    /// Inserts a key-value pair, returning the old value, or gives the pair back if there is no room.
+    ///
+    /// NOTE: room is reserved even if `key` is present, so a full map may fail to replace values.
+    fn try_insert_pair(
+        &mut self,
+        key: Self::KeyType,
+        value: Self::ValueType,
+    ) -> TryInsertResult<Option<Self::ValueType>, KeyValue<Self>, Self::TryReserveError>
+    where
+        Self: Owned;
+}
+
+impl<C: AssociatedMap + CapacityTryReserve> AssociatedTryInsert for C
+where
+    C::CapacityType: From<u8>,
+{
+    fn try_insert_pair(
+        &mut self,
+        key: Self::KeyType,
+        value: Self::ValueType,
+    ) -> TryInsertResult<Option<Self::ValueType>, KeyValue<Self>, Self::TryReserveError>
+    where
+        Self: Owned,
+    {
+        match self.try_reserve(1.into()) {
+            Ok(()) => Ok(self.insert(key, value)),
+            Err(error) => Err(TryInsertError {
+                elem: (key, value),
+                error,
+            }),
+        }
+    }
+}
+
+/// Elements not inserted by `TryExtend::try_extend`, starting with the one that failed.
+pub type Remaining<T, I> = iter::Chain<iter::Once<T>, I>;
+
+/// Collections that can be extended without aborting on allocation failures.
+pub trait TryExtend: ExtendOwned + CapacityTryReserve {
+    /// Extends `self` until an element can't be inserted, giving back that element and the rest of `iter`.
+    fn try_extend<I>(
+        &mut self,
+        iter: I,
+    ) -> TryInsertResult<(), Remaining<Self::ElemType, I::IntoIter>, Self::TryReserveError>
+    where
+        I: IntoIterator<Item = Self::ElemType>,
+        Self: Owned;
+}
+
+impl<C: ExtendOwned + CapacityTryReserve> TryExtend for C
+where
+    C::CapacityType: From<u8>,
+{
+    fn try_extend<I>(
+        &mut self,
+        iter: I,
+    ) -> TryInsertResult<(), Remaining<Self::ElemType, I::IntoIter>, Self::TryReserveError>
+    where
+        I: IntoIterator<Item = Self::ElemType>,
+        Self: Owned,
+    {
+        let mut iter = iter.into_iter();
+        while let Some(elem) = iter.next() {
+            if let Err(error) = self.try_reserve(1.into()) {
+                return Err(TryInsertError {
+                    elem: iter::once(elem).chain(iter),
+                    error,
+                });
+            }
+            self.extend_one(elem);
+        }
+        Ok(())
+    }
+}
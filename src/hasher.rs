@@ -0,0 +1,49 @@
+/// Types that have hasher builders. (`HashMap<K, V, S>`, `HashSet<T, S>`)
+pub trait HasherAware {
+    /// Hasher builder type
+    type Hasher: std::hash::BuildHasher;
+
+    /// Returns the hasher builder associated with `Self`
+    fn hasher(&self) -> &Self::Hasher;
+
+    /// Construct `Self` without data, given a hasher builder.
+    fn with_hasher(hasher: Self::Hasher) -> Self
+    where
+        Self: Sized;
+
+    /// Construct `Self` without data, given capacity and a hasher builder.
+    fn with_capacity_and_hasher(capacity: usize, hasher: Self::Hasher) -> Self
+    where
+        Self: Sized;
+}
+
+mod impls {
+    use super::*;
+
+    macro_rules! hasher_impls {
+        () => {};
+        ([@Hasher $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> HasherAware for $t {
+                type Hasher = S;
+
+                fn hasher(&self) -> &Self::Hasher {
+                    <$t>::hasher(self)
+                }
+
+                fn with_hasher(hasher: Self::Hasher) -> Self {
+                    <$t>::with_hasher(hasher)
+                }
+
+                fn with_capacity_and_hasher(capacity: usize, hasher: Self::Hasher) -> Self {
+                    <$t>::with_capacity_and_hasher(capacity, hasher)
+                }
+            }
+            hasher_impls!($($tail)*);
+        };
+    }
+
+    hasher_impls!(
+        [@Hasher T, S: std::hash::BuildHasher => std::collections::HashSet<T, S>];
+        [@Hasher K, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>];
+    );
+}
@@ -0,0 +1,185 @@
+//! Deep accounting of the heap memory owned by collections and their elements.
+//!
+//! The sizes of hash tables and B-trees are estimates based on the current `std` layouts.
+
+/// Types that can report the heap memory they own.
+pub trait HeapSize {
+    /// Returns the number of heap bytes owned by `self`, excluding `self` itself.
+    fn heap_size_of_children(&self) -> usize;
+
+    /// Returns the size of `self` plus the heap bytes it owns.
+    fn total_size(&self) -> usize {
+        std::mem::size_of_val(self) + self.heap_size_of_children()
+    }
+}
+
+/// Returns the heap bytes owned by all `elems`.
+fn children_of<'a, T: HeapSize + 'a>(elems: impl Iterator<Item = &'a T>) -> usize {
+    elems.map(HeapSize::heap_size_of_children).sum()
+}
+
+/// Estimates the bytes of a hash table holding `capacity` elements of `T`.
+///
+/// NOTE: `std` hash tables store one control byte per bucket plus a trailing group of 16 control bytes,
+/// and keep at most 7/8 of the buckets full.
+fn hash_table_size<T>(capacity: usize) -> usize {
+    const GROUP_WIDTH: usize = 16;
+    let buckets = match capacity {
+        0 => return 0,
+        c if c < 8 => c + 1,
+        c => c / 7 * 8,
+    };
+    buckets * std::mem::size_of::<T>() + buckets + GROUP_WIDTH
+}
+
+/// Estimates the bytes of a B-tree holding `len` key-value pairs.
+///
+/// NOTE: `std` B-tree nodes hold up to 11 pairs, assume they are about two thirds full.
+/// Internal nodes additionally hold 12 edges.
+fn btree_size<K, V>(len: usize) -> usize {
+    const CAPACITY: usize = 11;
+    const FILL: usize = CAPACITY * 2 / 3;
+    // parent pointer, parent index, length, keys and values
+    let leaf = std::mem::size_of::<(usize, u16, u16, [K; CAPACITY], [V; CAPACITY])>();
+    let internal = leaf + (CAPACITY + 1) * std::mem::size_of::<usize>();
+    let mut size = 0;
+    let mut nodes = if len == 0 { 0 } else { (len - 1) / FILL + 1 };
+    let mut node = leaf;
+    while nodes > 0 {
+        size += nodes * node;
+        nodes = if nodes == 1 { 0 } else { (nodes + FILL) / (FILL + 1) };
+        node = internal;
+    }
+    size
+}
+
+mod impls {
+    use super::*;
+
+    macro_rules! heap_size_impls {
+        () => {};
+        ([@Zero $($t: ty),*]; $($tail:tt)*) => {
+            $(
+                impl HeapSize for $t {
+                    fn heap_size_of_children(&self) -> usize {
+                        0
+                    }
+                }
+            )*
+            heap_size_impls!($($tail)*);
+        };
+        ([@Tuple $($name: ident),*]; $($tail:tt)*) => {
+            impl<$($name: HeapSize),*> HeapSize for ($($name,)*) {
+                #[allow(non_snake_case)]
+                fn heap_size_of_children(&self) -> usize {
+                    let ($($name,)*) = self;
+                    0 $(+ $name.heap_size_of_children())*
+                }
+            }
+            heap_size_impls!($($tail)*);
+        };
+        ([@Contiguous $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> HeapSize for $t {
+                fn heap_size_of_children(&self) -> usize {
+                    self.capacity() * std::mem::size_of::<T>() + children_of(self.iter())
+                }
+            }
+            heap_size_impls!($($tail)*);
+        };
+        ([@HashSet $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> HeapSize for $t {
+                fn heap_size_of_children(&self) -> usize {
+                    hash_table_size::<K>(self.capacity()) + children_of(self.iter())
+                }
+            }
+            heap_size_impls!($($tail)*);
+        };
+        ([@HashMap $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> HeapSize for $t {
+                fn heap_size_of_children(&self) -> usize {
+                    hash_table_size::<(K, V)>(self.capacity()) + children_of(self.keys()) + children_of(self.values())
+                }
+            }
+            heap_size_impls!($($tail)*);
+        };
+    }
+
+    heap_size_impls!(
+        [@Zero (), bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, str];
+        [@Tuple A];
+        [@Tuple A, B];
+        [@Tuple A, B, C];
+        [@Tuple A, B, C, D];
+        [@Tuple A, B, C, D, E];
+        [@Tuple A, B, C, D, E, F];
+        [@Tuple A, B, C, D, E, F, G];
+        [@Tuple A, B, C, D, E, F, G, H];
+        [@Contiguous T: HeapSize, A: std::alloc::Allocator => Vec<T, A>];
+        [@Contiguous T: HeapSize, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@Contiguous T: HeapSize, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
+        [@HashSet K: HeapSize, S => std::collections::HashSet<K, S>];
+        [@HashMap K: HeapSize, V: HeapSize, S => std::collections::HashMap<K, V, S>];
+    );
+
+    // Borrowed data isn't owned.
+    impl<T: ?Sized> HeapSize for &T {
+        fn heap_size_of_children(&self) -> usize {
+            0
+        }
+    }
+
+    impl<T: ?Sized> HeapSize for &mut T {
+        fn heap_size_of_children(&self) -> usize {
+            0
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for [T] {
+        fn heap_size_of_children(&self) -> usize {
+            children_of(self.iter())
+        }
+    }
+
+    impl<T: HeapSize, const N: usize> HeapSize for [T; N] {
+        fn heap_size_of_children(&self) -> usize {
+            children_of(self.iter())
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for Option<T> {
+        fn heap_size_of_children(&self) -> usize {
+            self.as_ref().map_or(0, HeapSize::heap_size_of_children)
+        }
+    }
+
+    impl HeapSize for String {
+        fn heap_size_of_children(&self) -> usize {
+            self.capacity()
+        }
+    }
+
+    impl<T: HeapSize + ?Sized, A: std::alloc::Allocator> HeapSize for Box<T, A> {
+        fn heap_size_of_children(&self) -> usize {
+            (**self).total_size()
+        }
+    }
+
+    impl<T: HeapSize, A: std::alloc::Allocator> HeapSize for std::collections::LinkedList<T, A> {
+        // Each node holds the element and two links.
+        fn heap_size_of_children(&self) -> usize {
+            self.len() * std::mem::size_of::<(T, usize, usize)>() + children_of(self.iter())
+        }
+    }
+
+    impl<K: HeapSize, A: std::alloc::Allocator + Clone> HeapSize for std::collections::BTreeSet<K, A> {
+        fn heap_size_of_children(&self) -> usize {
+            btree_size::<K, ()>(self.len()) + children_of(self.iter())
+        }
+    }
+
+    impl<K: HeapSize, V: HeapSize, A: std::alloc::Allocator + Clone> HeapSize for std::collections::BTreeMap<K, V, A> {
+        fn heap_size_of_children(&self) -> usize {
+            btree_size::<K, V>(self.len()) + children_of(self.keys()) + children_of(self.values())
+        }
+    }
+}
@@ -0,0 +1,466 @@
+//! Vectors indexed by a dedicated index type instead of `usize`, like `IndexVec` in rustc.
+//!
+//! `TypedIndexVec<I, T>` uses `I` as its `SizeType`, so `Sequential`, `RandomAccess`, `Range` and `DrainRange`
+//! only accept `I` as positions, and a table can't be indexed with the ID type of another table.
+use crate::{
+    capacity::{CapacityAware, CapacityConstructible, CapacityReserve, CapacityShrink, CapacityTryReserve},
+    elem::{CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    heap_size::HeapSize,
+    iter::{DrainRange, Iterable, IterableMut, Range, RangeMut},
+    misc::{Contains, EmptyConstructible, Erasable, Resizable, Retainable},
+    sequential::{Contiguous, RandomAccess, Sequential},
+};
+use std::{
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+};
+
+/// Index types convertible from and to `usize`.
+pub trait Idx: Copy + Ord + std::hash::Hash + std::fmt::Debug + 'static {
+    /// Converts a `usize` position into `Self`.
+    ///
+    /// Panics if `idx` doesn't fit into `Self`.
+    fn new(idx: usize) -> Self;
+
+    /// Converts `self` into a `usize` position.
+    fn index(self) -> usize;
+}
+
+impl Idx for usize {
+    fn new(idx: usize) -> Self {
+        idx
+    }
+
+    fn index(self) -> usize {
+        self
+    }
+}
+
+impl Idx for u32 {
+    fn new(idx: usize) -> Self {
+        assert!(idx <= u32::MAX as usize, "index {} overflows u32", idx);
+        idx as u32
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Idx for u16 {
+    fn new(idx: usize) -> Self {
+        assert!(idx <= u16::MAX as usize, "index {} overflows u16", idx);
+        idx as u16
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Defines a `u32` newtype implementing `Idx`, e.g. `define_index!(pub struct EntityId;)`.
+#[macro_export]
+macro_rules! define_index {
+    ($(#[$attr: meta])* $vis: vis struct $name: ident;) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        $vis struct $name(u32);
+
+        impl $crate::index_vec::Idx for $name {
+            fn new(idx: usize) -> Self {
+                $name(<u32 as $crate::index_vec::Idx>::new(idx))
+            }
+
+            fn index(self) -> usize {
+                self.0 as usize
+            }
+        }
+    };
+}
+
+/// `Vec<T>` only indexable by `I`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TypedIndexVec<I: Idx, T> {
+    raw: Vec<T>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T> TypedIndexVec<I, T> {
+    /// Creates an empty vector.
+    pub fn new() -> Self {
+        Self::from_raw(Vec::new())
+    }
+
+    /// Creates an empty vector with room for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_raw(Vec::with_capacity(capacity))
+    }
+
+    /// Wraps a `Vec<T>`, whose positions become indices of type `I`.
+    pub fn from_raw(raw: Vec<T>) -> Self {
+        TypedIndexVec {
+            raw,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Unwraps the underlying `Vec<T>`.
+    pub fn into_raw(self) -> Vec<T> {
+        self.raw
+    }
+
+    /// Returns the underlying `Vec<T>`.
+    pub fn raw(&self) -> &Vec<T> {
+        &self.raw
+    }
+
+    /// Returns the index the next pushed element will get.
+    pub fn next_index(&self) -> I {
+        I::new(self.raw.len())
+    }
+
+    /// Appends `value` and returns its index.
+    pub fn push(&mut self, value: T) -> I {
+        let idx = self.next_index();
+        self.raw.push(value);
+        idx
+    }
+
+    /// Returns the element at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: I) -> Option<&T> {
+        self.raw.get(index.index())
+    }
+
+    /// Returns the element at `index` mutably, or `None` if out of bounds.
+    pub fn get_mut(&mut self, index: I) -> Option<&mut T> {
+        self.raw.get_mut(index.index())
+    }
+
+    /// Iterates over all indices.
+    pub fn indices(&self) -> impl DoubleEndedIterator<Item = I> + ExactSizeIterator {
+        (0..self.raw.len()).map(I::new)
+    }
+
+    /// Iterates over the elements along with their indices.
+    pub fn iter_enumerated(&self) -> impl DoubleEndedIterator<Item = (I, &T)> + ExactSizeIterator {
+        self.raw.iter().enumerate().map(|(i, v)| (I::new(i), v))
+    }
+
+    /// Iterates mutably over the elements along with their indices.
+    pub fn iter_enumerated_mut(&mut self) -> impl DoubleEndedIterator<Item = (I, &mut T)> + ExactSizeIterator {
+        self.raw.iter_mut().enumerate().map(|(i, v)| (I::new(i), v))
+    }
+}
+
+impl<I: Idx, T> Default for TypedIndexVec<I, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T> std::ops::Index<I> for TypedIndexVec<I, T> {
+    type Output = T;
+
+    fn index(&self, index: I) -> &T {
+        &self.raw[index.index()]
+    }
+}
+
+impl<I: Idx, T> std::ops::IndexMut<I> for TypedIndexVec<I, T> {
+    fn index_mut(&mut self, index: I) -> &mut T {
+        &mut self.raw[index.index()]
+    }
+}
+
+impl<I: Idx, T> AsRef<[T]> for TypedIndexVec<I, T> {
+    fn as_ref(&self) -> &[T] {
+        &self.raw
+    }
+}
+
+impl<I: Idx, T> FromIterator<T> for TypedIndexVec<I, T> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        Self::from_raw(Vec::from_iter(iter))
+    }
+}
+
+impl<I: Idx, T> Extend<T> for TypedIndexVec<I, T> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        self.raw.extend(iter)
+    }
+
+    fn extend_one(&mut self, item: T) {
+        self.raw.push(item)
+    }
+
+    fn extend_reserve(&mut self, additional: usize) {
+        self.raw.reserve(additional)
+    }
+}
+
+impl<I: Idx, T> IntoIterator for TypedIndexVec<I, T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.into_iter()
+    }
+}
+
+impl<'a, I: Idx, T> IntoIterator for &'a TypedIndexVec<I, T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter()
+    }
+}
+
+/// Converts bounds on `I` into bounds on `usize`.
+pub(crate) fn raw_range<I: Idx, R: RangeBounds<I>>(range: R) -> (Bound<usize>, Bound<usize>) {
+    let convert = |bound: Bound<&I>| match bound {
+        Bound::Included(i) => Bound::Included(i.index()),
+        Bound::Excluded(i) => Bound::Excluded(i.index()),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+    (convert(range.start_bound()), convert(range.end_bound()))
+}
+
+mod impls {
+    use super::*;
+
+    impl<I: Idx, T> CollectionTrait for TypedIndexVec<I, T> {
+        type ElemType = T;
+    }
+
+    impl<I: Idx, T> Mutable for TypedIndexVec<I, T> {}
+    impl<I: Idx, T> Owned for TypedIndexVec<I, T> {}
+
+    impl<I: Idx, T> ExactSized for TypedIndexVec<I, T> {
+        type SizeType = I;
+
+        fn is_empty(&self) -> bool {
+            self.raw.is_empty()
+        }
+
+        fn len(&self) -> I {
+            I::new(self.raw.len())
+        }
+    }
+
+    impl<I: Idx, T> Iterable for TypedIndexVec<I, T> {
+        type Iter<'a>
+        where
+            T: 'a,
+        = std::slice::Iter<'a, T>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.raw.iter()
+        }
+    }
+
+    impl<I: Idx, T> IterableMut for TypedIndexVec<I, T> {
+        type IterMut<'a>
+        where
+            T: 'a,
+        = std::slice::IterMut<'a, T>;
+
+        fn iter_mut(&mut self) -> Self::IterMut<'_> {
+            self.raw.iter_mut()
+        }
+    }
+
+    impl<I: Idx, T> Range for TypedIndexVec<I, T> {
+        type RangeIter<'a>
+        where
+            T: 'a,
+        = std::slice::Iter<'a, T>;
+
+        fn range<R: RangeBounds<I>>(&self, range: R) -> Self::RangeIter<'_> {
+            self.raw[std::slice::range(raw_range(range), ..self.raw.len())].iter()
+        }
+    }
+
+    impl<I: Idx, T> RangeMut for TypedIndexVec<I, T> {
+        type RangeIterMut<'a>
+        where
+            T: 'a,
+        = std::slice::IterMut<'a, T>;
+
+        fn range_mut<R: RangeBounds<I>>(&mut self, range: R) -> Self::RangeIterMut<'_> {
+            let len = self.raw.len();
+            self.raw[std::slice::range(raw_range(range), ..len)].iter_mut()
+        }
+    }
+
+    impl<I: Idx, T> DrainRange for TypedIndexVec<I, T> {
+        type DrainRangeIter<'a>
+        where
+            I: 'a,
+            T: 'a,
+        = std::vec::Drain<'a, T>;
+
+        fn drain_range<R: RangeBounds<I>>(&mut self, range: R) -> Self::DrainRangeIter<'_> {
+            self.raw.drain(raw_range(range))
+        }
+    }
+
+    impl<I: Idx, T> EmptyConstructible for TypedIndexVec<I, T> {
+        fn new() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<I: Idx, T> Erasable for TypedIndexVec<I, T> {
+        fn clear(&mut self) {
+            self.raw.clear()
+        }
+    }
+
+    impl<I: Idx, T: PartialEq> Contains for TypedIndexVec<I, T> {
+        fn contains(&self, value: &T) -> bool {
+            self.raw.contains(value)
+        }
+    }
+
+    impl<I: Idx, T> Retainable for TypedIndexVec<I, T> {
+        fn retain<F: FnMut(&T) -> bool>(&mut self, pred: F) {
+            self.raw.retain(pred)
+        }
+    }
+
+    impl<I: Idx, T: Clone> Resizable for TypedIndexVec<I, T> {
+        fn resize(&mut self, new_len: I, value: T) {
+            self.raw.resize(new_len.index(), value)
+        }
+
+        fn resize_with(&mut self, new_len: I, generator: impl FnMut() -> T) {
+            self.raw.resize_with(new_len.index(), generator)
+        }
+
+        fn truncate(&mut self, len: I) {
+            self.raw.truncate(len.index())
+        }
+    }
+
+    impl<I: Idx, T> Contiguous for TypedIndexVec<I, T> {
+        fn as_slice(&self) -> &[Self::ElemType] {
+            &self.raw
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [Self::ElemType] {
+            &mut self.raw
+        }
+    }
+
+    impl<I: Idx, T> Sequential for TypedIndexVec<I, T> {
+        fn back(&self) -> Option<&Self::ElemType> {
+            self.raw.last()
+        }
+
+        fn back_mut(&mut self) -> Option<&mut Self::ElemType> {
+            self.raw.last_mut()
+        }
+
+        fn append(&mut self, other: &mut Self) {
+            self.raw.append(&mut other.raw)
+        }
+
+        fn push_back(&mut self, elt: Self::ElemType) {
+            self.raw.push(elt)
+        }
+
+        fn pop_back(&mut self) -> Option<Self::ElemType> {
+            self.raw.pop()
+        }
+
+        fn remove(&mut self, at: Self::SizeType) -> Option<Self::ElemType> {
+            Some(self.raw.remove(at.index()))
+        }
+
+        fn split_off(&mut self, at: Self::SizeType) -> Self {
+            Self::from_raw(self.raw.split_off(at.index()))
+        }
+    }
+
+    impl<I: Idx, T> RandomAccess for TypedIndexVec<I, T> {
+        fn rotate_left(&mut self, mid: Self::SizeType) {
+            self.raw.rotate_left(mid.index())
+        }
+
+        fn rotate_right(&mut self, mid: Self::SizeType) {
+            self.raw.rotate_right(mid.index())
+        }
+
+        fn partition_point<P: FnMut(&Self::ElemType) -> bool>(&self, pred: P) -> Self::SizeType {
+            I::new(self.raw.partition_point(pred))
+        }
+
+        fn swap(&mut self, i: Self::SizeType, j: Self::SizeType) {
+            self.raw.swap(i.index(), j.index())
+        }
+
+        fn get(&self, index: Self::SizeType) -> Option<&Self::ElemType> {
+            self.raw.get(index.index())
+        }
+
+        fn get_mut(&mut self, index: Self::SizeType) -> Option<&mut Self::ElemType> {
+            self.raw.get_mut(index.index())
+        }
+
+        fn insert(&mut self, index: Self::SizeType, value: Self::ElemType) {
+            self.raw.insert(index.index(), value)
+        }
+
+        fn swap_remove_back(&mut self, index: Self::SizeType) -> Option<Self::ElemType> {
+            Some(self.raw.swap_remove(index.index()))
+        }
+    }
+
+    // Capacities count elements, not positions.
+    impl<I: Idx, T> CapacityAware for TypedIndexVec<I, T> {
+        type CapacityType = usize;
+
+        fn capacity(&self) -> usize {
+            self.raw.capacity()
+        }
+    }
+
+    impl<I: Idx, T> CapacityConstructible for TypedIndexVec<I, T> {
+        fn with_capacity(capacity: usize) -> Self {
+            Self::with_capacity(capacity)
+        }
+    }
+
+    impl<I: Idx, T> CapacityShrink for TypedIndexVec<I, T> {
+        fn shrink_to_fit(&mut self) {
+            self.raw.shrink_to_fit()
+        }
+
+        fn shrink_to(&mut self, min_capacity: usize) {
+            self.raw.shrink_to(min_capacity)
+        }
+    }
+
+    impl<I: Idx, T> CapacityReserve for TypedIndexVec<I, T> {
+        fn reserve(&mut self, additional: usize) {
+            self.raw.reserve(additional)
+        }
+    }
+
+    impl<I: Idx, T> CapacityTryReserve for TypedIndexVec<I, T> {
+        type TryReserveError = std::collections::TryReserveError;
+
+        fn try_reserve(&mut self, additional: usize) -> Result<(), Self::TryReserveError> {
+            self.raw.try_reserve(additional)
+        }
+    }
+
+    impl<I: Idx, T: HeapSize> HeapSize for TypedIndexVec<I, T> {
+        fn heap_size_of_children(&self) -> usize {
+            self.raw.heap_size_of_children()
+        }
+    }
+}
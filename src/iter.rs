@@ -236,7 +236,29 @@ mod impls {
         }
     }
 
-    impl<K> AssociatedIterable for std::collections::HashSet<K> {
+    impl<T, A: std::alloc::Allocator> Iterable for Box<[T], A> {
+        type Iter<'a>
+        where
+            T: 'a,
+        = std::slice::Iter<'a, T>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            <[T]>::iter(self)
+        }
+    }
+
+    impl<T, A: std::alloc::Allocator> IterableMut for Box<[T], A> {
+        type IterMut<'a>
+        where
+            T: 'a,
+        = std::slice::IterMut<'a, T>;
+
+        fn iter_mut(&mut self) -> Self::IterMut<'_> {
+            <[T]>::iter_mut(self)
+        }
+    }
+
+    impl<K, S: std::hash::BuildHasher> AssociatedIterable for std::collections::HashSet<K, S> {
         type Iter<'a>
         where
             K: 'a,
@@ -247,7 +269,7 @@ mod impls {
         }
     }
 
-    impl<K> AssociatedIterable for std::collections::BTreeSet<K> {
+    impl<K, A: std::alloc::Allocator + Clone> AssociatedIterable for std::collections::BTreeSet<K, A> {
         type Iter<'a>
         where
             K: 'a,
@@ -315,6 +337,29 @@ mod impls {
         }
     }
 
+    impl<T, A: std::alloc::Allocator> Range for Box<[T], A> {
+        type RangeIter<'a>
+        where
+            T: 'a,
+        = std::slice::Iter<'a, T>;
+
+        fn range<R: RangeBounds<Self::SizeType>>(&self, range: R) -> Self::RangeIter<'_> {
+            self[std::slice::range(range, ..self.len())].iter()
+        }
+    }
+
+    impl<T, A: std::alloc::Allocator> RangeMut for Box<[T], A> {
+        type RangeIterMut<'a>
+        where
+            T: 'a,
+        = std::slice::IterMut<'a, T>;
+
+        fn range_mut<R: RangeBounds<Self::SizeType>>(&mut self, range: R) -> Self::RangeIterMut<'_> {
+            let len = self.len();
+            self[std::slice::range(range, ..len)].iter_mut()
+        }
+    }
+
     impl<T, A: std::alloc::Allocator> Range for Vec<T, A> {
         type RangeIter<'a>
         where
@@ -420,8 +465,8 @@ mod impls {
             }
             iter_impls!($($tail)*);
         };
-        ([@Range $t: ty, $iter: ty, $iter_mut: ty]; $($tail:tt)*) => {
-            impl<T> Range for $t {
+        ([@Range $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty, $iter: ty, $iter_mut: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> Range for $t {
                 type RangeIter<'a> where T: 'a = $iter;
 
                 fn range<R: std::ops::RangeBounds<Self::SizeType>>(&self, range: R) -> Self::RangeIter<'_> {
@@ -429,7 +474,7 @@ mod impls {
                 }
             }
 
-            impl<T> RangeMut for $t {
+            impl<$($args $(: $bound $(+ $others)*)?),*> RangeMut for $t {
                 type RangeIterMut<'a> where T: 'a = $iter_mut;
 
                 fn range_mut<R: std::ops::RangeBounds<Self::SizeType>>(&mut self, range: R) -> Self::RangeIterMut<'_> {
@@ -468,7 +513,7 @@ mod impls {
         };
         ([@DrainRange $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty, $iter: ty]; $($tail:tt)*) => {
             impl<$($args $(: $bound $(+ $others)*)?),*> DrainRange for $t {
-                type DrainRangeIter<'a> where T: 'a = $iter;
+                type DrainRangeIter<'a> where $($args: 'a),* = $iter;
 
                 fn drain_range<R: RangeBounds<Self::SizeType>>(&mut self, range: R) -> Self::DrainRangeIter<'_> {
                     <$t>::drain(self, range)
@@ -479,7 +524,7 @@ mod impls {
         };
         ([@DrainFilter $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty, $iter: ty]; $($tail:tt)*) => {
             impl<$($args $(: $bound $(+ $others)*)?),*> DrainFilter for $t {
-                type DrainFilterIter<'a, F> where T: 'a, F: FnMut(&mut T) -> bool + 'a = $iter;
+                type DrainFilterIter<'a, F> where $($args: 'a,)* F: FnMut(&mut T) -> bool + 'a = $iter;
 
                 fn drain_filter<'a, F: FnMut(&mut T) -> bool + 'a>(&'a mut self, filter: F) -> Self::DrainFilterIter<'a, F> {
                     <$t>::drain_filter(self, filter)
@@ -526,30 +571,29 @@ mod impls {
     iter_impls!(
         [@Slice Vec<T, A>];
 
-        [@Delegate T => std::collections::VecDeque<T>, std::collections::vec_deque::Iter<'a, T>, std::collections::vec_deque::IterMut<'a, T>];
-        [@Delegate T => std::collections::LinkedList<T>, std::collections::linked_list::Iter<'a, T>, std::collections::linked_list::IterMut<'a, T>];
-        [@Delegate T => std::collections::BTreeSet<T>, std::collections::btree_set::Iter<'a, T>];
+        [@Delegate T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>, std::collections::vec_deque::Iter<'a, T>, std::collections::vec_deque::IterMut<'a, T>];
+        [@Delegate T, A: std::alloc::Allocator => std::collections::LinkedList<T, A>, std::collections::linked_list::Iter<'a, T>, std::collections::linked_list::IterMut<'a, T>];
+        [@Delegate T, A: std::alloc::Allocator | Clone => std::collections::BTreeSet<T, A>, std::collections::btree_set::Iter<'a, T>];
         [@Delegate T, S => std::collections::HashSet<T, S>, std::collections::hash_set::Iter<'a, T>];
-        [@Delegate T => std::collections::BinaryHeap<T>, std::collections::binary_heap::Iter<'a, T>];
-        [@DelegateMap K, V => std::collections::BTreeMap<K, V>, std::collections::btree_map::Iter<'a, K, V>, std::collections::btree_map::IterMut<'a, K, V>];
+        [@Delegate T, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>, std::collections::binary_heap::Iter<'a, T>];
+        [@DelegateMap K, V, A: std::alloc::Allocator | Clone => std::collections::BTreeMap<K, V, A>, std::collections::btree_map::Iter<'a, K, V>, std::collections::btree_map::IterMut<'a, K, V>];
         [@DelegateMap K, V, S => std::collections::HashMap<K, V, S>, std::collections::hash_map::Iter<'a, K, V>, std::collections::hash_map::IterMut<'a, K, V>];
 
-        [@Range std::collections::VecDeque<T>, std::collections::vec_deque::Iter<'a, T>, std::collections::vec_deque::IterMut<'a, T>];
-        [@AssocRange K: std::cmp::Ord, V => std::collections::BTreeMap<K, V>, std::collections::btree_map::Range<'a, K, V>, std::collections::btree_map::RangeMut<'a, K, V>];
+        [@Range T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>, std::collections::vec_deque::Iter<'a, T>, std::collections::vec_deque::IterMut<'a, T>];
+        [@AssocRange K: std::cmp::Ord, V, A: std::alloc::Allocator | Clone => std::collections::BTreeMap<K, V, A>, std::collections::btree_map::Range<'a, K, V>, std::collections::btree_map::RangeMut<'a, K, V>];
 
-        [@DrainFull T => T: std::cmp::Ord => std::collections::BinaryHeap<T>, std::collections::binary_heap::Drain<'a, T>];
-        [@DrainFull T => T: std::cmp::Ord => std::collections::HashSet<T>, std::collections::hash_set::Drain<'a, T>];
+        [@DrainFull T => T: std::cmp::Ord, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>, std::collections::binary_heap::Drain<'a, T, A>];
+        [@DrainFull T => T, S: std::hash::BuildHasher => std::collections::HashSet<T, S>, std::collections::hash_set::Drain<'a, T>];
 
-        // FIXME: omit allocator here => need to rewrite associated types
-        [@DrainRange T => Vec<T>, std::vec::Drain<'a, T>];
-        [@DrainRange T => std::collections::VecDeque<T>, std::collections::vec_deque::Drain<'a, T>];
+        [@DrainRange T, A: std::alloc::Allocator => Vec<T, A>, std::vec::Drain<'a, T, A>];
+        [@DrainRange T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>, std::collections::vec_deque::Drain<'a, T, A>];
 
-        [@DrainFilter T => Vec<T>, std::vec::DrainFilter<'a, T, F>];
-        [@DrainFilter T => std::collections::LinkedList<T>, std::collections::linked_list::DrainFilter<'a, T, F>];
-        [@DrainFilterSet T: std::cmp::Ord => std::collections::BTreeSet<T>, std::collections::btree_set::DrainFilter<'a, T, F>];
+        [@DrainFilter T, A: std::alloc::Allocator => Vec<T, A>, std::vec::DrainFilter<'a, T, F, A>];
+        [@DrainFilter T, A: std::alloc::Allocator | std::clone::Clone => std::collections::LinkedList<T, A>, std::collections::linked_list::DrainFilter<'a, T, F, A>];
+        [@DrainFilterSet T: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeSet<T, A>, std::collections::btree_set::DrainFilter<'a, T, F, A>];
         [@DrainFilterSet T, S: std::hash::BuildHasher => std::collections::HashSet<T, S>, std::collections::hash_set::DrainFilter<'a, T, F>];
 
         [@AssocDrainFilter K, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>, std::collections::hash_map::DrainFilter<'a, K, V, F>];
-        [@AssocDrainFilter K: std::cmp::Ord, V => std::collections::BTreeMap<K, V>, std::collections::btree_map::DrainFilter<'a, K, V, F>];
+        [@AssocDrainFilter K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeMap<K, V, A>, std::collections::btree_map::DrainFilter<'a, K, V, F, A>];
     );
 }
@@ -8,23 +8,47 @@
 #![feature(type_alias_impl_trait)]
 #![feature(slice_range)]
 #![feature(btree_drain_filter)]
+#![feature(btreemap_alloc)]
 #![feature(drain_filter)]
 #![feature(hash_drain_filter)]
 #![feature(linked_list_remove)]
 #![feature(map_try_insert)]
 #![feature(map_first_last)]
 #![feature(extend_one)]
+#![feature(scoped_threads)]
+#![feature(build_hasher_simple_hash_one)]
 
 use crate::elem::{AssociatedCollectionTrait, CollectionTrait, ExtendOwned, IntoIteratorOwned};
 
+pub mod algorithms;
 pub mod allocator;
 pub mod associated;
+pub mod bimap;
+pub mod cache;
 pub mod capacity;
+pub mod compact;
+pub mod compare;
+pub mod counter;
+pub mod default_map;
 pub mod elem;
 pub mod exact_size;
+pub mod fallible;
+pub mod hasher;
+pub mod heap_size;
+pub mod index_vec;
 pub mod iter;
+pub mod macros;
 pub mod misc;
+pub mod multimap;
+pub mod multiset;
+#[cfg(feature = "rayon")]
+pub mod par;
+pub mod persistent;
 pub mod sequential;
+pub mod shared;
+pub mod split;
+pub mod sync;
+pub mod testing;
 
 /// Std collections (e.g, `[T; N]`)
 ///
@@ -138,7 +162,7 @@ impl<S, C: AssociatedCollection + associated::AssociatedMap + associated::Associ
 pub trait OrderedMap<S = ()>: Map<S> + associated::AssociatedMapOrd {}
 impl<S, C: Map<S> + associated::AssociatedMapOrd> OrderedMap<S> for C {}
 
-/// Std collections that are aware of allocators. (`Vec<T>`)
+/// Std collections that are aware of allocators. (`Vec<T, A>`, `VecDeque<T, A>`, `BinaryHeap<T, A>`)
 pub trait AllocatorAwareCollection: Collection + allocator::AllocatorAware + allocator::AllocatorConstructible {}
 impl<C: Collection + allocator::AllocatorAware + allocator::AllocatorConstructible> AllocatorAwareCollection for C {}
 
@@ -178,3 +202,27 @@ impl<
 > AssociatedCapacityAwareCollection for C
 {
 }
+
+/// Std collections that can iterate in parallel (e.g., `Vec<T>`, `HashSet<T>`)
+#[cfg(feature = "rayon")]
+pub trait ParCollection: Collection + par::ParIterable {}
+#[cfg(feature = "rayon")]
+impl<C: Collection + par::ParIterable> ParCollection for C {}
+
+/// Std sequential collections that can iterate mutably in parallel (e.g., `Vec<T>`, `LinkedList<T>`)
+#[cfg(feature = "rayon")]
+pub trait ParSequentialCollection: SequentialCollection + ParCollection + par::ParIterableMut {}
+#[cfg(feature = "rayon")]
+impl<C: SequentialCollection + ParCollection + par::ParIterableMut> ParSequentialCollection for C {}
+
+/// Std associated collections that can iterate in parallel (e.g., `HashMap<K, V>`)
+#[cfg(feature = "rayon")]
+pub trait ParAssociatedCollection:
+    AssociatedCollection + par::AssociatedParIterable + par::AssociatedParIterableMut
+{
+}
+#[cfg(feature = "rayon")]
+impl<C: AssociatedCollection + par::AssociatedParIterable + par::AssociatedParIterableMut> ParAssociatedCollection
+    for C
+{
+}
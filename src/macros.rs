@@ -0,0 +1,37 @@
+//! Collection literals generic over the target collection type.
+use crate::{
+    elem::{ExtendOwned, Owned},
+    misc::EmptyConstructible,
+};
+
+/// Creates any owned collection from a list of elements, e.g. `let v: VecDeque<_> = collection![1, 2, 3];`.
+///
+/// The target is pre-sized through `ExtendOwned::extend_reserve` when the collection supports it.
+#[macro_export]
+macro_rules! collection {
+    ($($elem: expr),* $(,)?) => {
+        $crate::macros::from_elems([$($elem),*])
+    };
+}
+
+/// Creates any owned map from a list of key-value pairs, e.g. `let m: BTreeMap<_, _> = map!{1 => "a", 2 => "b"};`.
+///
+/// The target is pre-sized through `ExtendOwned::extend_reserve` when the collection supports it.
+#[macro_export]
+macro_rules! map {
+    ($($key: expr => $value: expr),* $(,)?) => {
+        $crate::macros::from_elems([$(($key, $value)),*])
+    };
+}
+
+/// Constructs `C` from an array of elements. Used by `collection!` and `map!`.
+#[doc(hidden)]
+pub fn from_elems<C, const N: usize>(elems: [C::ElemType; N]) -> C
+where
+    C: EmptyConstructible + ExtendOwned + Owned,
+{
+    let mut c = C::new();
+    c.extend_reserve(N);
+    c.extend(elems);
+    c
+}
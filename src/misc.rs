@@ -81,6 +81,22 @@ mod impls {
             }
             misc_impls!($($tail)*);
         };
+        ([@AllocEmptyCtor $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> EmptyConstructible for $t {
+                fn new() -> Self {
+                    <$t>::new_in(std::default::Default::default())
+                }
+            }
+            misc_impls!($($tail)*);
+        };
+        ([@HashEmptyCtor $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> EmptyConstructible for $t {
+                fn new() -> Self {
+                    <$t>::with_hasher(std::default::Default::default())
+                }
+            }
+            misc_impls!($($tail)*);
+        };
         ([@Erase $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
             impl<$($args $(: $bound $(+ $others)*)?),*> Erasable for $t {
                 fn clear(&mut self) {
@@ -166,7 +182,13 @@ mod impls {
         }
     }
 
-    impl<T: PartialEq<T>> Contains for Vec<T> {
+    impl<T: PartialEq<T>, A: std::alloc::Allocator> Contains for Box<[T], A> {
+        fn contains(&self, value: &T) -> bool {
+            <[T]>::contains(self, value)
+        }
+    }
+
+    impl<T: PartialEq<T>, A: std::alloc::Allocator> Contains for Vec<T, A> {
         fn contains(&self, value: &T) -> bool {
             self.as_slice().contains(value)
         }
@@ -176,46 +198,48 @@ mod impls {
     impl<T> Erasable for &[T] {}
     impl<T> Erasable for &mut [T] {}
     impl<T, const N: usize> Erasable for [T; N] {}
+    impl<T, A: std::alloc::Allocator> Erasable for Box<[T], A> {}
 
     impl EmptyConstructible for &str {}
     impl<T> EmptyConstructible for &[T] {}
     impl<T> EmptyConstructible for &mut [T] {}
     impl<T, const N: usize> EmptyConstructible for [T; N] {}
+    impl<T, A: std::alloc::Allocator> EmptyConstructible for Box<[T], A> {}
 
     misc_impls!(
-        [@EmptyCtor T => Vec<T>];
-        [@EmptyCtor T => std::collections::VecDeque<T>];
-        [@EmptyCtor T: std::cmp::Ord => std::collections::BinaryHeap<T>];
-        [@EmptyCtor T => std::collections::HashSet<T>];
-        [@EmptyCtor K, V => std::collections::HashMap<K, V>];
-        [@EmptyCtor T => std::collections::LinkedList<T>];
-        [@EmptyCtor T: std::cmp::Ord => std::collections::BTreeSet<T>];
-        [@EmptyCtor K: std::cmp::Ord, V => std::collections::BTreeMap<K, V>];
+        [@AllocEmptyCtor T, A: std::alloc::Allocator | std::default::Default => Vec<T, A>];
+        [@AllocEmptyCtor T, A: std::alloc::Allocator | std::default::Default => std::collections::VecDeque<T, A>];
+        [@AllocEmptyCtor T: std::cmp::Ord, A: std::alloc::Allocator | std::default::Default => std::collections::BinaryHeap<T, A>];
+        [@HashEmptyCtor T, S: std::hash::BuildHasher | std::default::Default => std::collections::HashSet<T, S>];
+        [@HashEmptyCtor K, V, S: std::hash::BuildHasher | std::default::Default => std::collections::HashMap<K, V, S>];
+        [@AllocEmptyCtor T, A: std::alloc::Allocator | std::default::Default => std::collections::LinkedList<T, A>];
+        [@AllocEmptyCtor T: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone | std::default::Default => std::collections::BTreeSet<T, A>];
+        [@AllocEmptyCtor K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone | std::default::Default => std::collections::BTreeMap<K, V, A>];
 
         [@Erase T, A: std::alloc::Allocator => Vec<T, A>];
-        [@Erase T => std::collections::VecDeque<T>];
-        [@Erase T: std::cmp::Ord => std::collections::BinaryHeap<T>];
+        [@Erase T, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@Erase T: std::cmp::Ord, A: std::alloc::Allocator => std::collections::BinaryHeap<T, A>];
         [@Erase T, S => std::collections::HashSet<T, S>];
         [@Erase K, V, S => std::collections::HashMap<K, V, S>];
-        [@Erase T => std::collections::LinkedList<T>];
-        [@Erase T: std::cmp::Ord => std::collections::BTreeSet<T>];
-        [@Erase K: std::cmp::Ord, V => std::collections::BTreeMap<K, V>];
+        [@Erase T, A: std::alloc::Allocator => std::collections::LinkedList<T, A>];
+        [@Erase T: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeSet<T, A>];
+        [@Erase K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeMap<K, V, A>];
 
-        [@Contain T: std::cmp::PartialEq<T> => std::collections::VecDeque<T>];
-        [@Contain T: std::cmp::PartialEq<T> => std::collections::LinkedList<T>];
+        [@Contain T: std::cmp::PartialEq<T>, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
+        [@Contain T: std::cmp::PartialEq<T>, A: std::alloc::Allocator => std::collections::LinkedList<T, A>];
         [@Contain T: std::hash::Hash | std::cmp::Eq, S: std::hash::BuildHasher => std::collections::HashSet<T, S>];
-        [@Contain T: std::cmp::Ord => std::collections::BTreeSet<T>];
+        [@Contain T: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeSet<T, A>];
         [@MapContain K: std::hash::Hash | std::cmp::Eq, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>];
-        [@MapContain K: std::cmp::Ord, V => std::collections::BTreeMap<K, V>];
+        [@MapContain K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeMap<K, V, A>];
 
         [@Retain T, A: std::alloc::Allocator => Vec<T, A>];
-        [@Retain T: std::cmp::PartialEq<T> => std::collections::VecDeque<T>];
+        [@Retain T: std::cmp::PartialEq<T>, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
         [@Retain T: std::hash::Hash | std::cmp::Eq, S: std::hash::BuildHasher => std::collections::HashSet<T, S>];
-        [@Retain T: std::cmp::Ord => std::collections::BTreeSet<T>];
+        [@Retain T: std::cmp::Ord, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeSet<T, A>];
         [@MapRetain K: std::hash::Hash | std::cmp::Eq, V, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>];
-        [@MapRetain K: std::cmp::Ord, V => std::collections::BTreeMap<K, V>];
+        [@MapRetain K: std::cmp::Ord, V, A: std::alloc::Allocator | std::clone::Clone => std::collections::BTreeMap<K, V, A>];
 
         [@Resize T: Clone, A: std::alloc::Allocator => Vec<T, A>];
-        [@Resize T: Clone => std::collections::VecDeque<T>];
+        [@Resize T: Clone, A: std::alloc::Allocator => std::collections::VecDeque<T, A>];
     );
 }
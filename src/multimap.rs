@@ -0,0 +1,542 @@
+//! Maps from a key to many values, stored in a bucket collection per key.
+//!
+//! `Multimap` is implemented for any map whose values are `Bucket`s, e.g. `HashMap<K, Vec<V>>` or
+//! `BTreeMap<K, HashSet<V>>`, so "get or create then push" doesn't need to be written by hand.
+use crate::{
+    associated::{AssociatedMap, AssociatedSet},
+    elem::{AssociatedCollectionTrait, CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    iter::{AssociatedIterable, AssociatedIterableMut, Iterable, IterableMut},
+    misc::{EmptyConstructible, Erasable},
+    sequential::Sequential,
+};
+use std::{
+    collections::{hash_map::RandomState, BTreeMap, HashMap},
+    iter::FromIterator,
+};
+
+/// Collections holding the values of one key in a multimap.
+pub trait Bucket: Iterable + ExactSized<SizeType = usize> + EmptyConstructible + Owned {
+    /// Adds `value`, returns false if it was rejected, e.g. as a duplicate in sets.
+    fn insert_value(&mut self, value: Self::ElemType) -> bool;
+
+    /// Removes one value equal to `value`, returns whether it was present.
+    fn remove_value(&mut self, value: &Self::ElemType) -> bool
+    where
+        Self::ElemType: PartialEq;
+}
+
+/// Maps from a key to many values.
+pub trait Multimap {
+    /// Key type
+    type Key;
+
+    /// Value type, of each of the values of a key
+    type Value;
+
+    /// Bucket type holding the values of a key
+    type Values: Bucket<ElemType = Self::Value>;
+
+    /// Flattened iterator type
+    type IterFlat<'a>: Iterator<Item = (&'a Self::Key, &'a Self::Value)>
+    where
+        Self::Key: 'a,
+        Self::Value: 'a,
+        Self::Values: 'a;
+
+    /// Adds `value` to the values of `key`, returns false if the bucket rejected it.
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> bool;
+
+    /// Returns the values of `key`, `None` if it has none.
+    fn get_all(&self, key: &Self::Key) -> Option<&Self::Values>;
+
+    /// Removes one value equal to `value` from the values of `key`, and `key` itself if no value is left.
+    fn remove_one(&mut self, key: &Self::Key, value: &Self::Value) -> bool
+    where
+        Self::Value: PartialEq;
+
+    /// Removes `key`, returning all its values.
+    fn remove_all(&mut self, key: &Self::Key) -> Option<Self::Values>;
+
+    /// Returns the number of keys.
+    fn len_keys(&self) -> usize;
+
+    /// Returns the number of values of all keys.
+    ///
+    /// NOTE: maps of buckets count them one key at a time, `BucketMultimap<M>` keeps track of it.
+    fn len_values(&self) -> usize;
+
+    /// Iterates over all key-value pairs, yielding a key once for each of its values.
+    fn iter_flat(&self) -> Self::IterFlat<'_>;
+}
+
+impl<M> Multimap for M
+where
+    M: AssociatedMap + AssociatedIterable + ExactSized<SizeType = usize> + Owned,
+    M::ValueType: Bucket,
+{
+    type Key = M::KeyType;
+    type Value = <M::ValueType as CollectionTrait>::ElemType;
+    type Values = M::ValueType;
+    type IterFlat<'a>
+    where
+        M::KeyType: 'a,
+        <M::ValueType as CollectionTrait>::ElemType: 'a,
+        M::ValueType: 'a,
+    = IterFlat<'a, M::KeyType, M::ValueType, <M as AssociatedIterable>::Iter<'a>>;
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        match AssociatedMap::get_mut(self, &key) {
+            Some(values) => values.insert_value(value),
+            None => {
+                let mut values = <M::ValueType as EmptyConstructible>::new();
+                let inserted = values.insert_value(value);
+                if inserted {
+                    AssociatedMap::insert(self, key, values);
+                }
+                inserted
+            }
+        }
+    }
+
+    fn get_all(&self, key: &Self::Key) -> Option<&Self::Values> {
+        AssociatedMap::get(self, key)
+    }
+
+    fn remove_one(&mut self, key: &Self::Key, value: &Self::Value) -> bool
+    where
+        Self::Value: PartialEq,
+    {
+        let (removed, is_empty) = match AssociatedMap::get_mut(self, key) {
+            Some(values) => (values.remove_value(value), values.is_empty()),
+            None => return false,
+        };
+        if is_empty {
+            AssociatedMap::remove(self, key);
+        }
+        removed
+    }
+
+    fn remove_all(&mut self, key: &Self::Key) -> Option<Self::Values> {
+        AssociatedMap::remove(self, key)
+    }
+
+    fn len_keys(&self) -> usize {
+        ExactSized::len(self)
+    }
+
+    fn len_values(&self) -> usize {
+        AssociatedIterable::iter(self).map(|(_, values)| values.len()).sum()
+    }
+
+    fn iter_flat(&self) -> Self::IterFlat<'_> {
+        IterFlat::new(AssociatedIterable::iter(self), self.len_values())
+    }
+}
+
+/// Iterator over the values of each key of a map of buckets.
+pub struct IterFlat<'a, K: 'a, B: Iterable + 'a, I>
+where
+    B::ElemType: 'a,
+{
+    outer: I,
+    inner: Option<(&'a K, B::Iter<'a>)>,
+    len: usize,
+}
+
+impl<'a, K: 'a, B: Iterable + 'a, I> IterFlat<'a, K, B, I>
+where
+    B::ElemType: 'a,
+{
+    fn new(outer: I, len: usize) -> Self {
+        IterFlat {
+            outer,
+            inner: None,
+            len,
+        }
+    }
+}
+
+impl<'a, K: 'a, B: Iterable + 'a, I: Iterator<Item = (&'a K, &'a B)>> Iterator for IterFlat<'a, K, B, I>
+where
+    B::ElemType: 'a,
+{
+    type Item = (&'a K, &'a B::ElemType);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, values)) = &mut self.inner {
+                if let Some(value) = values.next() {
+                    self.len -= 1;
+                    return Some((*key, value));
+                }
+            }
+            let (key, values) = self.outer.next()?;
+            self.inner = Some((key, values.iter()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K: 'a, B: Iterable + 'a, I: Iterator<Item = (&'a K, &'a B)>> ExactSizeIterator for IterFlat<'a, K, B, I> where
+    B::ElemType: 'a
+{
+}
+
+/// Mutable iterator over the values of each key of a map of buckets.
+pub struct IterFlatMut<'a, K: 'a, B: IterableMut + 'a, I>
+where
+    B::ElemType: 'a,
+{
+    outer: I,
+    inner: Option<(&'a K, B::IterMut<'a>)>,
+    len: usize,
+}
+
+impl<'a, K: 'a, B: IterableMut + 'a, I: Iterator<Item = (&'a K, &'a mut B)>> Iterator for IterFlatMut<'a, K, B, I>
+where
+    B::ElemType: 'a,
+{
+    type Item = (&'a K, &'a mut B::ElemType);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, values)) = &mut self.inner {
+                if let Some(value) = values.next() {
+                    self.len -= 1;
+                    return Some((*key, value));
+                }
+            }
+            let (key, values) = self.outer.next()?;
+            self.inner = Some((key, values.iter_mut()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K: 'a, B: IterableMut + 'a, I: Iterator<Item = (&'a K, &'a mut B)>> ExactSizeIterator
+    for IterFlatMut<'a, K, B, I>
+where
+    B::ElemType: 'a,
+{
+}
+
+/// Owned iterator over the values of each key of a map of buckets, cloning the keys.
+pub struct IntoIterFlat<K, B: IntoIterator, I> {
+    outer: I,
+    inner: Option<(K, B::IntoIter)>,
+    len: usize,
+}
+
+impl<K: Clone, B: IntoIterator, I: Iterator<Item = (K, B)>> Iterator for IntoIterFlat<K, B, I> {
+    type Item = (K, B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, values)) = &mut self.inner {
+                if let Some(value) = values.next() {
+                    self.len -= 1;
+                    return Some((key.clone(), value));
+                }
+            }
+            let (key, values) = self.outer.next()?;
+            self.inner = Some((key, values.into_iter()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+/// Multimap over a map of buckets `M`, keeping track of the total number of values.
+///
+/// As an associated collection, its elements are the flattened key-value pairs.
+///
+/// NOTE: `AssociatedIterable`/`AssociatedIterableMut` are only implemented for maps of `Vec<V>`, i.e.
+/// `HashMultimap<K, V, S>` and `BTreeMultimap<K, V>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BucketMultimap<M> {
+    map: M,
+    len: usize,
+}
+
+/// Multimap storing the values of each key in insertion order.
+pub type HashMultimap<K, V, S = RandomState> = BucketMultimap<HashMap<K, Vec<V>, S>>;
+
+/// Multimap ordered by keys, storing the values of each key in insertion order.
+pub type BTreeMultimap<K, V> = BucketMultimap<BTreeMap<K, Vec<V>>>;
+
+impl<M> BucketMultimap<M> {
+    /// Returns the underlying map of buckets.
+    pub fn as_map(&self) -> &M {
+        &self.map
+    }
+
+    /// Unwraps the underlying map of buckets.
+    pub fn into_map(self) -> M {
+        self.map
+    }
+}
+
+impl<M> BucketMultimap<M>
+where
+    M: AssociatedMap + AssociatedIterable + ExactSized<SizeType = usize> + Owned,
+    M::ValueType: Bucket,
+{
+    /// Wraps a map of buckets.
+    ///
+    /// NOTE: empty buckets are kept until a value is removed from them.
+    pub fn from_map(map: M) -> Self {
+        BucketMultimap {
+            len: map.len_values(),
+            map,
+        }
+    }
+}
+
+impl<M> From<M> for BucketMultimap<M>
+where
+    M: AssociatedMap + AssociatedIterable + ExactSized<SizeType = usize> + Owned,
+    M::ValueType: Bucket,
+{
+    fn from(map: M) -> Self {
+        Self::from_map(map)
+    }
+}
+
+impl<K, V, M> Extend<(K, V)> for BucketMultimap<M>
+where
+    M: AssociatedMap<KeyType = K> + AssociatedIterable + ExactSized<SizeType = usize> + Owned,
+    M::ValueType: Bucket<ElemType = V>,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(key, value)| {
+            Multimap::insert(self, key, value);
+        })
+    }
+}
+
+impl<K, V, M> FromIterator<(K, V)> for BucketMultimap<M>
+where
+    M: AssociatedMap<KeyType = K> + AssociatedIterable + ExactSized<SizeType = usize> + Owned + Default,
+    M::ValueType: Bucket<ElemType = V>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut multimap = Self::default();
+        multimap.extend(iter);
+        multimap
+    }
+}
+
+impl<K: Clone, B: Bucket, M> IntoIterator for BucketMultimap<M>
+where
+    M: IntoIterator<Item = (K, B)>,
+{
+    type Item = (K, B::ElemType);
+    type IntoIter = IntoIterFlat<K, B, M::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterFlat {
+            outer: self.map.into_iter(),
+            inner: None,
+            len: self.len,
+        }
+    }
+}
+
+mod impls {
+    use super::*;
+
+    macro_rules! bucket_impls {
+        () => {};
+        ([@Seq $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> Bucket for $t {
+                fn insert_value(&mut self, value: Self::ElemType) -> bool {
+                    Sequential::push_back(self, value);
+                    true
+                }
+
+                fn remove_value(&mut self, value: &Self::ElemType) -> bool
+                where
+                    Self::ElemType: PartialEq,
+                {
+                    match <$t>::iter(self).position(|v| v == value) {
+                        Some(index) => Sequential::remove(self, index).is_some(),
+                        None => false,
+                    }
+                }
+            }
+            bucket_impls!($($tail)*);
+        };
+        ([@Set $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> Bucket for $t {
+                fn insert_value(&mut self, value: Self::ElemType) -> bool {
+                    AssociatedSet::insert(self, value)
+                }
+
+                fn remove_value(&mut self, value: &Self::ElemType) -> bool
+                where
+                    Self::ElemType: PartialEq,
+                {
+                    AssociatedSet::remove(self, value)
+                }
+            }
+            bucket_impls!($($tail)*);
+        };
+    }
+
+    bucket_impls!(
+        [@Seq T => Vec<T>];
+        [@Seq T => std::collections::VecDeque<T>];
+        [@Seq T => std::collections::LinkedList<T>];
+        [@Set T: std::cmp::Eq | std::hash::Hash, S: std::hash::BuildHasher | std::default::Default => std::collections::HashSet<T, S>];
+        [@Set T: std::cmp::Ord => std::collections::BTreeSet<T>];
+    );
+
+    impl<M: AssociatedCollectionTrait> CollectionTrait for BucketMultimap<M>
+    where
+        M::ValueType: CollectionTrait,
+    {
+        type ElemType = (M::KeyType, <M::ValueType as CollectionTrait>::ElemType);
+    }
+
+    impl<M: AssociatedCollectionTrait> AssociatedCollectionTrait for BucketMultimap<M>
+    where
+        M::ValueType: CollectionTrait,
+    {
+        type KeyType = M::KeyType;
+        type ValueType = <M::ValueType as CollectionTrait>::ElemType;
+    }
+
+    impl<M: AssociatedCollectionTrait + Mutable> Mutable for BucketMultimap<M> where M::ValueType: CollectionTrait {}
+
+    impl<K: Clone, B: Bucket, M> Owned for BucketMultimap<M> where
+        M: AssociatedCollectionTrait<KeyType = K, ValueType = B> + Mutable + IntoIterator<Item = (K, B)>
+    {
+    }
+
+    impl<M> Multimap for BucketMultimap<M>
+    where
+        M: AssociatedMap + AssociatedIterable + ExactSized<SizeType = usize> + Owned,
+        M::ValueType: Bucket,
+    {
+        type Key = M::KeyType;
+        type Value = <M::ValueType as CollectionTrait>::ElemType;
+        type Values = M::ValueType;
+        type IterFlat<'a>
+        where
+            M::KeyType: 'a,
+            <M::ValueType as CollectionTrait>::ElemType: 'a,
+            M::ValueType: 'a,
+        = IterFlat<'a, M::KeyType, M::ValueType, <M as AssociatedIterable>::Iter<'a>>;
+
+        fn insert(&mut self, key: Self::Key, value: Self::Value) -> bool {
+            let inserted = Multimap::insert(&mut self.map, key, value);
+            self.len += inserted as usize;
+            inserted
+        }
+
+        fn get_all(&self, key: &Self::Key) -> Option<&Self::Values> {
+            self.map.get_all(key)
+        }
+
+        fn remove_one(&mut self, key: &Self::Key, value: &Self::Value) -> bool
+        where
+            Self::Value: PartialEq,
+        {
+            let removed = self.map.remove_one(key, value);
+            self.len -= removed as usize;
+            removed
+        }
+
+        fn remove_all(&mut self, key: &Self::Key) -> Option<Self::Values> {
+            let values = self.map.remove_all(key)?;
+            self.len -= values.len();
+            Some(values)
+        }
+
+        fn len_keys(&self) -> usize {
+            self.map.len()
+        }
+
+        fn len_values(&self) -> usize {
+            self.len
+        }
+
+        fn iter_flat(&self) -> Self::IterFlat<'_> {
+            IterFlat::new(AssociatedIterable::iter(&self.map), self.len)
+        }
+    }
+
+    impl<M: AssociatedCollectionTrait> ExactSized for BucketMultimap<M>
+    where
+        M::ValueType: CollectionTrait,
+    {
+        fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        fn len(&self) -> usize {
+            self.len
+        }
+    }
+
+    // NOTE: The buckets are `Vec<V>`, so that their lifetime follows from `V: 'a`, as the iterator types of
+    // `AssociatedIterable` can only bound the key and value types.
+    macro_rules! flat_impls {
+        () => {};
+        ([$($args: ident $(: $bound: path $(| $others:path )*)?),* => $m: ty, $iter: ty, $iter_mut: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> AssociatedIterable for BucketMultimap<$m> {
+                type Iter<'a> where K: 'a, V: 'a = IterFlat<'a, K, Vec<V>, $iter>;
+
+                fn iter(&self) -> Self::Iter<'_> {
+                    IterFlat::new(self.map.iter(), self.len)
+                }
+            }
+
+            impl<$($args $(: $bound $(+ $others)*)?),*> AssociatedIterableMut for BucketMultimap<$m> {
+                type IterMut<'a> where K: 'a, V: 'a = IterFlatMut<'a, K, Vec<V>, $iter_mut>;
+
+                fn iter_mut(&mut self) -> Self::IterMut<'_> {
+                    IterFlatMut {
+                        outer: self.map.iter_mut(),
+                        inner: None,
+                        len: self.len,
+                    }
+                }
+            }
+            flat_impls!($($tail)*);
+        };
+    }
+
+    flat_impls!(
+        [K, V, S => HashMap<K, Vec<V>, S>, std::collections::hash_map::Iter<'a, K, Vec<V>>, std::collections::hash_map::IterMut<'a, K, Vec<V>>];
+        [K, V => BTreeMap<K, Vec<V>>, std::collections::btree_map::Iter<'a, K, Vec<V>>, std::collections::btree_map::IterMut<'a, K, Vec<V>>];
+    );
+
+    impl<M: AssociatedCollectionTrait + EmptyConstructible + Owned> EmptyConstructible for BucketMultimap<M>
+    where
+        M::ValueType: CollectionTrait,
+    {
+        fn new() -> Self {
+            BucketMultimap { map: M::new(), len: 0 }
+        }
+    }
+
+    impl<M: AssociatedCollectionTrait + Erasable + Owned> Erasable for BucketMultimap<M>
+    where
+        M::ValueType: CollectionTrait,
+    {
+        fn clear(&mut self) {
+            self.map.clear();
+            self.len = 0;
+        }
+    }
+}
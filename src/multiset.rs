@@ -0,0 +1,476 @@
+//! Sets allowing several occurrences of a value, stored as a count per distinct value.
+//!
+//! `Multiset` is implemented for any map from values to `usize` counts, e.g. `HashMap<T, usize>`, while
+//! `CountedMultiset<M>` wraps such a map into a collection of the repeated values.
+use crate::{
+    associated::{AssociatedMap, AssociatedMapOrd},
+    elem::{AssociatedCollectionTrait, CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    iter::{AssociatedIterable, Iterable},
+    misc::{AssociatedRetainable, Contains, EmptyConstructible, Erasable},
+};
+use std::{
+    collections::{hash_map::RandomState, BTreeMap, HashMap},
+    iter::FromIterator,
+};
+
+/// Sets counting the occurrences of each value.
+///
+/// NOTE: values with a count of zero are removed, implementations assume none is stored.
+pub trait Multiset {
+    /// Value type
+    type Value;
+
+    /// Iterator type over distinct values and their counts
+    type IterCounts<'a>: Iterator<Item = (&'a Self::Value, usize)>
+    where
+        Self::Value: 'a;
+
+    /// Adds `n` occurrences of `value`, returns its previous count.
+    fn insert_n(&mut self, value: Self::Value, n: usize) -> usize;
+
+    /// Removes up to `n` occurrences of `value`, returns how many were removed.
+    fn remove_n(&mut self, value: &Self::Value, n: usize) -> usize;
+
+    /// Returns the number of occurrences of `value`.
+    fn count(&self, value: &Self::Value) -> usize;
+
+    /// Returns the number of distinct values.
+    fn distinct_len(&self) -> usize;
+
+    /// Returns the number of occurrences of all values.
+    ///
+    /// NOTE: maps count them one value at a time, `CountedMultiset<M>` keeps track of it.
+    fn total_len(&self) -> usize;
+
+    /// Iterates over distinct values and their counts.
+    fn iter_counts(&self) -> Self::IterCounts<'_>;
+
+    /// Keeps the minimum of the counts of `self` and `other` for each value.
+    fn intersect_with(&mut self, other: &Self);
+
+    /// Removes one occurrence of `value`, returns whether it was present.
+    fn remove_one(&mut self, value: &Self::Value) -> bool {
+        self.remove_n(value, 1) == 1
+    }
+
+    /// Removes all occurrences of `value`, returns how many were removed.
+    fn remove_all(&mut self, value: &Self::Value) -> usize {
+        self.remove_n(value, usize::MAX)
+    }
+
+    /// Keeps the maximum of the counts of `self` and `other` for each value.
+    fn union_with(&mut self, other: &Self)
+    where
+        Self::Value: Clone,
+    {
+        for (value, n) in other.iter_counts() {
+            let count = self.count(value);
+            if n > count {
+                self.insert_n(value.clone(), n - count);
+            }
+        }
+    }
+
+    /// Subtracts the counts of `other` from those of `self`, removing values whose count drops to zero.
+    fn subtract(&mut self, other: &Self) {
+        for (value, n) in other.iter_counts() {
+            self.remove_n(value, n);
+        }
+    }
+
+    /// Returns the `n` values with the highest counts, in descending order of counts.
+    ///
+    /// Ties keep the iteration order, e.g. ascending values in ordered multisets.
+    fn most_common(&self, n: usize) -> Vec<(&Self::Value, usize)> {
+        let mut counts: Vec<_> = self.iter_counts().collect();
+        counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+        counts.truncate(n);
+        counts
+    }
+
+    /// Returns the `n` values with the lowest counts, in ascending order of counts.
+    ///
+    /// Ties keep the iteration order, e.g. ascending values in ordered multisets.
+    fn least_common(&self, n: usize) -> Vec<(&Self::Value, usize)> {
+        let mut counts: Vec<_> = self.iter_counts().collect();
+        counts.sort_by_key(|(_, count)| *count);
+        counts.truncate(n);
+        counts
+    }
+}
+
+/// Multisets ordered by values.
+pub trait OrderedMultiset: Multiset {
+    /// Returns the smallest value and its count.
+    fn first(&self) -> Option<(&Self::Value, usize)>;
+
+    /// Returns the largest value and its count.
+    fn last(&self) -> Option<(&Self::Value, usize)>;
+
+    /// Removes all occurrences of the smallest value, returning it and its count.
+    fn pop_first(&mut self) -> Option<(Self::Value, usize)>;
+
+    /// Removes all occurrences of the largest value, returning it and its count.
+    fn pop_last(&mut self) -> Option<(Self::Value, usize)>;
+}
+
+/// Iterator type over the counts of a map.
+pub type MapIterCounts<'a, M> = std::iter::Map<
+    <M as AssociatedIterable>::Iter<'a>,
+    fn(
+        (&'a <M as AssociatedCollectionTrait>::KeyType, &'a usize),
+    ) -> (&'a <M as AssociatedCollectionTrait>::KeyType, usize),
+>;
+
+impl<M> Multiset for M
+where
+    M: AssociatedMap<ValueType = usize>
+        + AssociatedIterable
+        + AssociatedRetainable
+        + ExactSized<SizeType = usize>
+        + Owned,
+{
+    type Value = M::KeyType;
+    type IterCounts<'a>
+    where
+        M::KeyType: 'a,
+    = MapIterCounts<'a, M>;
+
+    fn insert_n(&mut self, value: Self::Value, n: usize) -> usize {
+        match AssociatedMap::get_mut(self, &value) {
+            Some(count) => {
+                let old = *count;
+                *count += n;
+                old
+            }
+            None => {
+                if n > 0 {
+                    AssociatedMap::insert(self, value, n);
+                }
+                0
+            }
+        }
+    }
+
+    fn remove_n(&mut self, value: &Self::Value, n: usize) -> usize {
+        let (removed, is_empty) = match AssociatedMap::get_mut(self, value) {
+            Some(count) => {
+                let removed = n.min(*count);
+                *count -= removed;
+                (removed, *count == 0)
+            }
+            None => return 0,
+        };
+        if is_empty {
+            AssociatedMap::remove(self, value);
+        }
+        removed
+    }
+
+    fn count(&self, value: &Self::Value) -> usize {
+        AssociatedMap::get(self, value).copied().unwrap_or(0)
+    }
+
+    fn distinct_len(&self) -> usize {
+        ExactSized::len(self)
+    }
+
+    fn total_len(&self) -> usize {
+        AssociatedIterable::iter(self).map(|(_, count)| count).sum()
+    }
+
+    fn iter_counts(&self) -> Self::IterCounts<'_> {
+        AssociatedIterable::iter(self).map(|(value, count)| (value, *count))
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        AssociatedRetainable::retain(self, |value, count| {
+            *count = other.count(value).min(*count);
+            *count > 0
+        })
+    }
+}
+
+impl<M> OrderedMultiset for M
+where
+    M: AssociatedMapOrd<ValueType = usize>
+        + AssociatedIterable
+        + AssociatedRetainable
+        + ExactSized<SizeType = usize>
+        + Owned,
+{
+    fn first(&self) -> Option<(&Self::Value, usize)> {
+        AssociatedMapOrd::first_key_value(self).map(|(value, count)| (value, *count))
+    }
+
+    fn last(&self) -> Option<(&Self::Value, usize)> {
+        AssociatedMapOrd::last_key_value(self).map(|(value, count)| (value, *count))
+    }
+
+    fn pop_first(&mut self) -> Option<(Self::Value, usize)> {
+        AssociatedMapOrd::pop_first(self)
+    }
+
+    fn pop_last(&mut self) -> Option<(Self::Value, usize)> {
+        AssociatedMapOrd::pop_last(self)
+    }
+}
+
+/// Iterator over the values of a map of counts, yielding each value as many times as counted.
+pub struct Iter<'a, T: 'a, I> {
+    counts: I,
+    current: Option<(&'a T, usize)>,
+    len: usize,
+}
+
+impl<'a, T: 'a, I: Iterator<Item = (&'a T, &'a usize)>> Iterator for Iter<'a, T, I> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((value, count)) = &mut self.current {
+                if *count > 0 {
+                    *count -= 1;
+                    self.len -= 1;
+                    return Some(*value);
+                }
+            }
+            let (value, count) = self.counts.next()?;
+            self.current = Some((value, *count));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T: 'a, I: Iterator<Item = (&'a T, &'a usize)>> ExactSizeIterator for Iter<'a, T, I> {}
+
+/// Owned iterator over the values of a map of counts, cloning values counted more than once.
+pub struct IntoIter<T, I> {
+    counts: I,
+    current: Option<(T, usize)>,
+    len: usize,
+}
+
+impl<T: Clone, I: Iterator<Item = (T, usize)>> Iterator for IntoIter<T, I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((value, count)) = self.current.take() {
+                if count > 0 {
+                    self.len -= 1;
+                    if count > 1 {
+                        self.current = Some((value.clone(), count - 1));
+                    }
+                    return Some(value);
+                }
+            }
+            self.current = Some(self.counts.next()?);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: Clone, I: Iterator<Item = (T, usize)>> ExactSizeIterator for IntoIter<T, I> {}
+
+/// Multiset over a map of counts `M`, keeping track of the total number of occurrences.
+///
+/// As a collection, its elements are the values repeated as many times as counted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CountedMultiset<M> {
+    map: M,
+    len: usize,
+}
+
+/// Multiset of hashable values.
+pub type HashMultiset<T, S = RandomState> = CountedMultiset<HashMap<T, usize, S>>;
+
+/// Multiset ordered by values.
+pub type BTreeMultiset<T> = CountedMultiset<BTreeMap<T, usize>>;
+
+impl<M> CountedMultiset<M> {
+    /// Returns the underlying map of counts.
+    pub fn as_map(&self) -> &M {
+        &self.map
+    }
+
+    /// Unwraps the underlying map of counts.
+    pub fn into_map(self) -> M {
+        self.map
+    }
+}
+
+impl<M: Multiset> CountedMultiset<M> {
+    /// Wraps a map of counts.
+    ///
+    /// NOTE: zero counts are kept until an occurrence is removed from them.
+    pub fn from_map(map: M) -> Self {
+        CountedMultiset {
+            len: map.total_len(),
+            map,
+        }
+    }
+}
+
+impl<M: Multiset> From<M> for CountedMultiset<M> {
+    fn from(map: M) -> Self {
+        Self::from_map(map)
+    }
+}
+
+impl<T, M: Multiset<Value = T>> Extend<T> for CountedMultiset<M> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| {
+            self.insert_n(value, 1);
+        })
+    }
+}
+
+impl<T, M: Multiset<Value = T> + Default> FromIterator<T> for CountedMultiset<M> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut multiset = Self::default();
+        multiset.extend(iter);
+        multiset
+    }
+}
+
+impl<T: Clone, M: IntoIterator<Item = (T, usize)>> IntoIterator for CountedMultiset<M> {
+    type Item = T;
+    type IntoIter = IntoIter<T, M::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            counts: self.map.into_iter(),
+            current: None,
+            len: self.len,
+        }
+    }
+}
+
+mod impls {
+    use super::*;
+
+    impl<M: AssociatedCollectionTrait> CollectionTrait for CountedMultiset<M> {
+        type ElemType = M::KeyType;
+    }
+
+    impl<M: AssociatedCollectionTrait + Mutable> Mutable for CountedMultiset<M> {}
+
+    impl<T: Clone, M> Owned for CountedMultiset<M> where
+        M: AssociatedCollectionTrait<KeyType = T, ValueType = usize> + Mutable + IntoIterator<Item = (T, usize)>
+    {
+    }
+
+    impl<M: Multiset> Multiset for CountedMultiset<M> {
+        type Value = M::Value;
+        type IterCounts<'a>
+        where
+            M::Value: 'a,
+        = M::IterCounts<'a>;
+
+        fn insert_n(&mut self, value: Self::Value, n: usize) -> usize {
+            self.len += n;
+            self.map.insert_n(value, n)
+        }
+
+        fn remove_n(&mut self, value: &Self::Value, n: usize) -> usize {
+            let removed = self.map.remove_n(value, n);
+            self.len -= removed;
+            removed
+        }
+
+        fn count(&self, value: &Self::Value) -> usize {
+            self.map.count(value)
+        }
+
+        fn distinct_len(&self) -> usize {
+            self.map.distinct_len()
+        }
+
+        fn total_len(&self) -> usize {
+            self.len
+        }
+
+        fn iter_counts(&self) -> Self::IterCounts<'_> {
+            self.map.iter_counts()
+        }
+
+        fn intersect_with(&mut self, other: &Self) {
+            self.map.intersect_with(&other.map);
+            self.len = self.map.total_len();
+        }
+    }
+
+    impl<M: OrderedMultiset> OrderedMultiset for CountedMultiset<M> {
+        fn first(&self) -> Option<(&Self::Value, usize)> {
+            self.map.first()
+        }
+
+        fn last(&self) -> Option<(&Self::Value, usize)> {
+            self.map.last()
+        }
+
+        fn pop_first(&mut self) -> Option<(Self::Value, usize)> {
+            let (value, count) = self.map.pop_first()?;
+            self.len -= count;
+            Some((value, count))
+        }
+
+        fn pop_last(&mut self) -> Option<(Self::Value, usize)> {
+            let (value, count) = self.map.pop_last()?;
+            self.len -= count;
+            Some((value, count))
+        }
+    }
+
+    impl<M: AssociatedCollectionTrait> ExactSized for CountedMultiset<M> {
+        fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        fn len(&self) -> usize {
+            self.len
+        }
+    }
+
+    impl<M: AssociatedIterable<ValueType = usize>> Iterable for CountedMultiset<M> {
+        type Iter<'a>
+        where
+            M::KeyType: 'a,
+        = Iter<'a, M::KeyType, <M as AssociatedIterable>::Iter<'a>>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            Iter {
+                counts: self.map.iter(),
+                current: None,
+                len: self.len,
+            }
+        }
+    }
+
+    impl<M: AssociatedMap<ValueType = usize>> Contains for CountedMultiset<M> {
+        fn contains(&self, value: &M::KeyType) -> bool {
+            self.map.contains_key(value)
+        }
+    }
+
+    impl<M: AssociatedCollectionTrait + EmptyConstructible + Owned> EmptyConstructible for CountedMultiset<M> {
+        fn new() -> Self {
+            CountedMultiset { map: M::new(), len: 0 }
+        }
+    }
+
+    impl<M: AssociatedCollectionTrait + Erasable + Owned> Erasable for CountedMultiset<M> {
+        fn clear(&mut self) {
+            self.map.clear();
+            self.len = 0;
+        }
+    }
+}
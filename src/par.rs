@@ -0,0 +1,217 @@
+//! Parallel iteration over collections, backed by `rayon`.
+//!
+//! NOTE: the method names follow `rayon`, so calling them is ambiguous if `rayon::prelude` is imported as well.
+use crate::elem::{AssociatedCollectionTrait, CollectionTrait, Mutable, Owned};
+use rayon::iter::{IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+/// Collections that can iterate in parallel as sequence of `&T`.
+pub trait ParIterable: CollectionTrait {
+    /// Immutable parallel iterator type
+    type ParIter<'a>: ParallelIterator<Item = &'a Self::ElemType>
+    where
+        Self::ElemType: 'a;
+
+    /// Iterates over immutable reference in parallel
+    fn par_iter(&self) -> Self::ParIter<'_>;
+}
+
+/// Collections that can iterate in parallel as sequence of `(&K, &V)`.
+pub trait AssociatedParIterable: AssociatedCollectionTrait {
+    /// Immutable parallel map iterator type
+    type ParIter<'a>: ParallelIterator<Item = (&'a Self::KeyType, &'a Self::ValueType)>
+    where
+        Self::KeyType: 'a,
+        Self::ValueType: 'a;
+
+    /// Iterates over immutable reference in parallel
+    fn par_iter(&self) -> Self::ParIter<'_>;
+}
+
+/// Collections that can iterate in parallel as sequence of `&mut T`.
+pub trait ParIterableMut: CollectionTrait + Mutable {
+    /// Mutable parallel iterator type
+    type ParIterMut<'a>: ParallelIterator<Item = &'a mut Self::ElemType>
+    where
+        Self::ElemType: 'a;
+
+    /// Iterates over mutable reference in parallel
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_>;
+}
+
+/// Collections that can iterate in parallel as sequence of `(&K, &mut V)`.
+pub trait AssociatedParIterableMut: AssociatedCollectionTrait + Mutable {
+    /// Mutable parallel map iterator type
+    type ParIterMut<'a>: ParallelIterator<Item = (&'a Self::KeyType, &'a mut Self::ValueType)>
+    where
+        Self::KeyType: 'a,
+        Self::ValueType: 'a;
+
+    /// Iterates over mutable reference in parallel
+    fn par_iter_mut(&mut self) -> Self::ParIterMut<'_>;
+}
+
+/// Collections that can drain all elements in parallel
+pub trait ParDrainFull: CollectionTrait + Mutable {
+    /// Parallel draining iterator type
+    type ParDrainIter<'a>: ParallelIterator<Item = Self::ElemType>
+    where
+        Self::ElemType: 'a;
+
+    /// Creates a parallel draining iterator that removes all elements in `self` and yields the removed items.
+    fn par_drain(&mut self) -> Self::ParDrainIter<'_>;
+}
+
+/// Collections that can be extended by a parallel iterator
+pub trait ParExtendOwned: CollectionTrait + Owned {
+    /// Extends `self` with the items of `par_iter`.
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = Self::ElemType>;
+}
+
+impl<C: CollectionTrait + ParallelExtend<C::ElemType> + Owned> ParExtendOwned for C
+where
+    C::ElemType: Send,
+{
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = Self::ElemType>,
+    {
+        ParallelExtend::par_extend(self, par_iter)
+    }
+}
+
+mod impls {
+    use super::*;
+
+    macro_rules! par_impls {
+        () => {};
+        ([@Slice $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> ParIterable for $t {
+                type ParIter<'a> where T: 'a = rayon::slice::Iter<'a, T>;
+
+                fn par_iter(&self) -> Self::ParIter<'_> {
+                    IntoParallelIterator::into_par_iter(&self[..])
+                }
+            }
+            par_impls!($($tail)*);
+        };
+        ([@SliceMut $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> ParIterableMut for $t {
+                type ParIterMut<'a> where T: 'a = rayon::slice::IterMut<'a, T>;
+
+                fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+                    IntoParallelIterator::into_par_iter(&mut self[..])
+                }
+            }
+            par_impls!($($tail)*);
+        };
+        ([@Delegate $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty, $iter: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> ParIterable for $t {
+                type ParIter<'a> where T: 'a = $iter;
+
+                fn par_iter(&self) -> Self::ParIter<'_> {
+                    IntoParallelIterator::into_par_iter(self)
+                }
+            }
+            par_impls!($($tail)*);
+        };
+        ([@DelegateMut $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty, $iter_mut: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> ParIterableMut for $t {
+                type ParIterMut<'a> where T: 'a = $iter_mut;
+
+                fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+                    IntoParallelIterator::into_par_iter(self)
+                }
+            }
+            par_impls!($($tail)*);
+        };
+        ([@DelegateMap $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty, $iter: ty, $iter_mut: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> AssociatedParIterable for $t {
+                type ParIter<'a> where K: 'a, V: 'a = $iter;
+
+                fn par_iter(&self) -> Self::ParIter<'_> {
+                    IntoParallelIterator::into_par_iter(self)
+                }
+            }
+
+            impl<$($args $(: $bound $(+ $others)*)?),*> AssociatedParIterableMut for $t {
+                type ParIterMut<'a> where K: 'a, V: 'a = $iter_mut;
+
+                fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+                    IntoParallelIterator::into_par_iter(self)
+                }
+            }
+            par_impls!($($tail)*);
+        };
+        ([@DrainFull $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty, $iter: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> ParDrainFull for $t {
+                type ParDrainIter<'a> where $($args: 'a),* = $iter;
+
+                fn par_drain(&mut self) -> Self::ParDrainIter<'_> {
+                    rayon::iter::ParallelDrainFull::par_drain(self)
+                }
+            }
+            par_impls!($($tail)*);
+        };
+        ([@DrainRange $($args: ident $(: $bound: path $(| $others:path )*)?),* => $t: ty, $iter: ty]; $($tail:tt)*) => {
+            impl<$($args $(: $bound $(+ $others)*)?),*> ParDrainFull for $t {
+                type ParDrainIter<'a> where $($args: 'a),* = $iter;
+
+                fn par_drain(&mut self) -> Self::ParDrainIter<'_> {
+                    rayon::iter::ParallelDrainRange::par_drain(self, ..)
+                }
+            }
+            par_impls!($($tail)*);
+        };
+    }
+
+    impl<T: Sync, const N: usize> ParIterable for [T; N] {
+        type ParIter<'a>
+        where
+            T: 'a,
+        = rayon::slice::Iter<'a, T>;
+
+        fn par_iter(&self) -> Self::ParIter<'_> {
+            IntoParallelIterator::into_par_iter(&self[..])
+        }
+    }
+
+    impl<T: Send, const N: usize> ParIterableMut for [T; N] {
+        type ParIterMut<'a>
+        where
+            T: 'a,
+        = rayon::slice::IterMut<'a, T>;
+
+        fn par_iter_mut(&mut self) -> Self::ParIterMut<'_> {
+            IntoParallelIterator::into_par_iter(&mut self[..])
+        }
+    }
+
+    par_impls!(
+        [@Slice T: Sync => &[T]];
+        [@Slice T: Sync => &mut [T]];
+        [@Slice T: Sync => Box<[T]>];
+        [@Slice T: Sync => Vec<T>];
+        [@SliceMut T: Send => &mut [T]];
+        [@SliceMut T: Send => Box<[T]>];
+        [@SliceMut T: Send => Vec<T>];
+
+        [@Delegate T: Sync => std::collections::VecDeque<T>, rayon::collections::vec_deque::Iter<'a, T>];
+        [@Delegate T: Sync => std::collections::LinkedList<T>, rayon::collections::linked_list::Iter<'a, T>];
+        [@Delegate T: std::cmp::Ord | Sync => std::collections::BinaryHeap<T>, rayon::collections::binary_heap::Iter<'a, T>];
+        [@Delegate T: std::cmp::Ord | Sync => std::collections::BTreeSet<T>, rayon::collections::btree_set::Iter<'a, T>];
+        [@Delegate T: std::hash::Hash | Eq | Sync, S: std::hash::BuildHasher => std::collections::HashSet<T, S>, rayon::collections::hash_set::Iter<'a, T>];
+        [@DelegateMut T: Send => std::collections::VecDeque<T>, rayon::collections::vec_deque::IterMut<'a, T>];
+        [@DelegateMut T: Send => std::collections::LinkedList<T>, rayon::collections::linked_list::IterMut<'a, T>];
+
+        [@DelegateMap K: std::cmp::Ord | Sync, V: Send | Sync => std::collections::BTreeMap<K, V>, rayon::collections::btree_map::Iter<'a, K, V>, rayon::collections::btree_map::IterMut<'a, K, V>];
+        [@DelegateMap K: std::hash::Hash | Eq | Sync, V: Send | Sync, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>, rayon::collections::hash_map::Iter<'a, K, V>, rayon::collections::hash_map::IterMut<'a, K, V>];
+
+        [@DrainRange T: Send => Vec<T>, rayon::vec::Drain<'a, T>];
+        [@DrainRange T: Send => std::collections::VecDeque<T>, rayon::collections::vec_deque::Drain<'a, T>];
+        [@DrainFull T: std::cmp::Ord | Send => std::collections::BinaryHeap<T>, rayon::collections::binary_heap::Drain<'a, T>];
+        [@DrainFull T: std::hash::Hash | Eq | Send, S: std::hash::BuildHasher => std::collections::HashSet<T, S>, rayon::collections::hash_set::Drain<'a, T>];
+        [@DrainFull K: std::hash::Hash | Eq | Send, V: Send, S: std::hash::BuildHasher => std::collections::HashMap<K, V, S>, rayon::collections::hash_map::Drain<'a, K, V>];
+    );
+}
@@ -0,0 +1,28 @@
+//! Persistent collections, whose modifications return new versions sharing most of their structure with the old ones.
+//!
+//! Old versions stay valid and unchanged, which makes them suitable for undo histories or snapshots. Unlike
+//! `shared::Shared<C>`, a modification only copies the O(log n) nodes on the path to the modified element.
+use crate::elem::CollectionTrait;
+
+pub mod pmap;
+pub mod pvec;
+
+pub use self::{pmap::PMap, pvec::PVec};
+
+/// Immutable collections returning modified versions of themselves.
+pub trait Persistent: CollectionTrait + Sized {
+    /// Positions of sequences, or keys of maps.
+    type Key;
+
+    /// Elements of sequences, or values of maps.
+    type Value;
+
+    /// Returns a new version with `elem` inserted, appended to sequences or replacing the value of its key in maps.
+    fn with_inserted(&self, elem: Self::ElemType) -> Self;
+
+    /// Returns a new version without the element at `key`, or `self` unchanged if absent.
+    fn without(&self, key: &Self::Key) -> Self;
+
+    /// Returns a new version with the element at `key` updated by `f`, or `self` unchanged if absent.
+    fn with_updated<F: FnOnce(&mut Self::Value)>(&self, key: &Self::Key, f: F) -> Self;
+}
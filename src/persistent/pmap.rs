@@ -0,0 +1,520 @@
+//! Persistent hash map as a hash array mapped trie (HAMT).
+use super::Persistent;
+use crate::{
+    associated::AssociatedMap,
+    elem::{AssociatedCollectionTrait, CollectionTrait, ExtendOwned, IntoIteratorOwned},
+    exact_size::ExactSized,
+    hasher::HasherAware,
+    iter::AssociatedIterable,
+    misc::{AssociatedContains, EmptyConstructible, Erasable},
+};
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hash},
+    iter::FromIterator,
+    sync::Arc,
+};
+
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+#[derive(Clone)]
+enum Entry<K, V> {
+    Leaf(u64, K, V),
+    /// Distinct keys with the same hash.
+    Collision(u64, Vec<(K, V)>),
+    Node(Arc<Node<K, V>>),
+}
+
+impl<K, V> Entry<K, V> {
+    fn hash(&self) -> u64 {
+        match self {
+            Entry::Leaf(hash, ..) | Entry::Collision(hash, _) => *hash,
+            Entry::Node(_) => unreachable!("nodes don't have a hash"),
+        }
+    }
+}
+
+/// Trie node, holding the entries of the bits set in `bitmap`.
+#[derive(Clone)]
+struct Node<K, V> {
+    bitmap: u32,
+    entries: Vec<Entry<K, V>>,
+}
+
+impl<K, V> Node<K, V> {
+    fn empty() -> Self {
+        Node {
+            bitmap: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the bit of `hash` at `shift`, and the index of its entry if present.
+    fn position(&self, hash: u64, shift: u32) -> (u32, usize) {
+        let bit = 1 << ((hash >> shift) & MASK);
+        (bit, (self.bitmap & (bit - 1)).count_ones() as usize)
+    }
+
+    /// Creates a node holding two entries of different hashes.
+    fn pair(shift: u32, first: Entry<K, V>, second: Entry<K, V>) -> Self {
+        let (first_bit, second_bit) = (
+            1 << ((first.hash() >> shift) & MASK),
+            1 << ((second.hash() >> shift) & MASK),
+        );
+        let entries = match first_bit.cmp(&second_bit) {
+            std::cmp::Ordering::Less => vec![first, second],
+            std::cmp::Ordering::Greater => vec![second, first],
+            std::cmp::Ordering::Equal => vec![Entry::Node(Arc::new(Self::pair(shift + BITS, first, second)))],
+        };
+        Node {
+            bitmap: first_bit | second_bit,
+            entries,
+        }
+    }
+
+    fn get<'a>(&'a self, hash: u64, key: &K) -> Option<(&'a K, &'a V)>
+    where
+        K: Eq,
+    {
+        let mut node = self;
+        let mut shift = 0;
+        loop {
+            let (bit, index) = node.position(hash, shift);
+            if node.bitmap & bit == 0 {
+                return None;
+            }
+            match &node.entries[index] {
+                Entry::Leaf(h, k, v) => return if *h == hash && k == key { Some((k, v)) } else { None },
+                Entry::Collision(h, entries) => {
+                    return if *h == hash {
+                        entries.iter().find(|(k, _)| k == key).map(|(k, v)| (k, v))
+                    } else {
+                        None
+                    };
+                }
+                Entry::Node(child) => {
+                    node = child;
+                    shift += BITS;
+                }
+            }
+        }
+    }
+}
+
+impl<K: Eq + Clone, V: Clone> Node<K, V> {
+    /// Inserts in place, copying the nodes shared with other versions.
+    fn insert(node: &mut Arc<Self>, shift: u32, hash: u64, key: K, value: V) -> Option<V> {
+        let node = Arc::make_mut(node);
+        let (bit, index) = node.position(hash, shift);
+        if node.bitmap & bit == 0 {
+            node.bitmap |= bit;
+            node.entries.insert(index, Entry::Leaf(hash, key, value));
+            return None;
+        }
+        match &mut node.entries[index] {
+            Entry::Node(child) => return Self::insert(child, shift + BITS, hash, key, value),
+            Entry::Leaf(h, k, v) if *h == hash && *k == key => return Some(std::mem::replace(v, value)),
+            Entry::Collision(h, entries) if *h == hash => {
+                return match entries.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, v)) => Some(std::mem::replace(v, value)),
+                    None => {
+                        entries.push((key, value));
+                        None
+                    }
+                };
+            }
+            _ => {}
+        }
+        let entry = match node.entries.remove(index) {
+            Entry::Leaf(h, k, v) if h == hash => Entry::Collision(hash, vec![(k, v), (key, value)]),
+            other => Entry::Node(Arc::new(Self::pair(shift + BITS, other, Entry::Leaf(hash, key, value)))),
+        };
+        node.entries.insert(index, entry);
+        None
+    }
+
+    /// Removes in place, copying the nodes shared with other versions.
+    ///
+    /// NOTE: the key must be present, otherwise the nodes on its path are copied for nothing.
+    fn remove(node: &mut Arc<Self>, shift: u32, hash: u64, key: &K) -> Option<(K, V)> {
+        let node = Arc::make_mut(node);
+        let (bit, index) = node.position(hash, shift);
+        if node.bitmap & bit == 0 {
+            return None;
+        }
+        let removed = match &mut node.entries[index] {
+            Entry::Node(child) => {
+                let removed = Self::remove(child, shift + BITS, hash, key);
+                // NOTE: lift a single remaining leaf, so the trie stays as shallow as with only insertions
+                if child.entries.len() == 1 && !matches!(child.entries[0], Entry::Node(_)) {
+                    let entry = Arc::make_mut(child).entries.pop();
+                    node.entries[index] = entry.expect("child has an entry");
+                }
+                return removed;
+            }
+            Entry::Collision(h, entries) if *h == hash => {
+                let position = entries.iter().position(|(k, _)| k == key)?;
+                let removed = entries.swap_remove(position);
+                if let [(k, v)] = entries.as_slice() {
+                    node.entries[index] = Entry::Leaf(hash, k.clone(), v.clone());
+                }
+                return Some(removed);
+            }
+            Entry::Leaf(h, k, _) if *h == hash && k == key => node.entries.remove(index),
+            _ => return None,
+        };
+        node.bitmap &= !bit;
+        match removed {
+            Entry::Leaf(_, k, v) => Some((k, v)),
+            _ => unreachable!("removed a leaf"),
+        }
+    }
+
+    /// Returns the value of `key` mutably, copying the nodes shared with other versions.
+    ///
+    /// NOTE: the key must be present, otherwise the nodes on its path are copied for nothing.
+    fn get_mut<'a>(node: &'a mut Arc<Self>, shift: u32, hash: u64, key: &K) -> Option<&'a mut V> {
+        let node = Arc::make_mut(node);
+        let (bit, index) = node.position(hash, shift);
+        if node.bitmap & bit == 0 {
+            return None;
+        }
+        match &mut node.entries[index] {
+            Entry::Leaf(h, k, v) if *h == hash && k == key => Some(v),
+            Entry::Collision(h, entries) if *h == hash => entries.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v),
+            Entry::Node(child) => Self::get_mut(child, shift + BITS, hash, key),
+            _ => None,
+        }
+    }
+}
+
+/// Immutable hash map, where `insert`, `remove` and `update` return new versions in O(log n).
+///
+/// Entries are stored in a 32-ary trie indexed by 5 bits of their hashes at each level. Versions share every
+/// unmodified node, and cloning one is O(1).
+pub struct PMap<K, V, S = RandomState> {
+    len: usize,
+    root: Arc<Node<K, V>>,
+    hash_builder: S,
+}
+
+impl<K, V> PMap<K, V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> PMap<K, V, S> {
+    /// Creates an empty map hashing keys with `hash_builder`.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        PMap {
+            len: 0,
+            root: Arc::new(Node::empty()),
+            hash_builder,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there is no entry.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if `self` and `other` are the same version.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.root, &other.root)
+    }
+
+    /// Iterates over the entries, in an unspecified order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            stack: vec![self.root.entries.iter()],
+            collision: [].iter(),
+            len: self.len,
+        }
+    }
+
+    /// Iterates over the keys, in an unspecified order.
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// Iterates over the values, in an unspecified order.
+    pub fn values(&self) -> impl ExactSizeIterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> PMap<K, V, S> {
+    /// Returns the value of `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns the entry of `key`.
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        self.root.get(self.hash_builder.hash_one(key), key)
+    }
+
+    /// Returns true if the map has an entry for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get_key_value(key).is_some()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone, S: BuildHasher + Clone> PMap<K, V, S> {
+    /// Returns a new version with `value` for `key`.
+    pub fn insert(&self, key: K, value: V) -> Self {
+        let mut map = self.clone();
+        map.insert_mut(key, value);
+        map
+    }
+
+    /// Returns a new version without `key`, or `None` if absent.
+    pub fn remove(&self, key: &K) -> Option<Self> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let mut map = self.clone();
+        map.len -= 1;
+        Node::remove(&mut map.root, 0, self.hash_builder.hash_one(key), key);
+        Some(map)
+    }
+
+    /// Returns a new version with the value of `key` updated by `f`, or `None` if absent.
+    pub fn update<F: FnOnce(&mut V)>(&self, key: &K, f: F) -> Option<Self> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let mut map = self.clone();
+        Node::get_mut(&mut map.root, 0, self.hash_builder.hash_one(key), key).map(f);
+        Some(map)
+    }
+
+    /// Inserts in place, copying the nodes shared with other versions.
+    fn insert_mut(&mut self, key: K, value: V) -> Option<V> {
+        let old = Node::insert(&mut self.root, 0, self.hash_builder.hash_one(&key), key, value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+}
+
+impl<K, V, S: Clone> Clone for PMap<K, V, S> {
+    fn clone(&self) -> Self {
+        PMap {
+            len: self.len,
+            root: Arc::clone(&self.root),
+            hash_builder: self.hash_builder.clone(),
+        }
+    }
+}
+
+impl<K, V, S: Default> Default for PMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for PMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Eq + Hash, V: PartialEq, S: BuildHasher> PartialEq for PMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || (self.len == other.len && self.iter().all(|(k, v)| other.get(k) == Some(v)))
+    }
+}
+
+impl<K: Eq + Hash, V: Eq, S: BuildHasher> Eq for PMap<K, V, S> {}
+
+impl<K: Eq + Hash + Clone, V: Clone, S: BuildHasher + Clone + Default> FromIterator<(K, V)> for PMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        Extend::extend(&mut map, iter);
+        map
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone, S: BuildHasher + Clone> Extend<(K, V)> for PMap<K, V, S> {
+    /// Inserts the entries of `iter` in place, which doesn't affect other versions.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(key, value)| {
+            self.insert_mut(key, value);
+        })
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a PMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a `PMap<K, V, S>`, walking the trie depth-first.
+pub struct Iter<'a, K, V> {
+    stack: Vec<std::slice::Iter<'a, Entry<K, V>>>,
+    collision: std::slice::Iter<'a, (K, V)>,
+    len: usize,
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Self {
+        Iter {
+            stack: self.stack.clone(),
+            collision: self.collision.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        loop {
+            if let Some((k, v)) = self.collision.next() {
+                self.len -= 1;
+                return Some((k, v));
+            }
+            match self.stack.last_mut()?.next() {
+                Some(Entry::Leaf(_, k, v)) => {
+                    self.len -= 1;
+                    return Some((k, v));
+                }
+                Some(Entry::Collision(_, entries)) => self.collision = entries.iter(),
+                Some(Entry::Node(child)) => self.stack.push(child.entries.iter()),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+impl<'a, K, V> std::iter::FusedIterator for Iter<'a, K, V> {}
+
+mod impls {
+    use super::*;
+
+    impl<K, V, S> CollectionTrait for PMap<K, V, S> {
+        type ElemType = (K, V);
+    }
+
+    impl<K, V, S> AssociatedCollectionTrait for PMap<K, V, S> {
+        type KeyType = K;
+        type ValueType = V;
+    }
+
+    impl<K, V, S> ExtendOwned for PMap<K, V, S> {}
+    impl<K, V, S> IntoIteratorOwned for PMap<K, V, S> {}
+    impl<K, V, S> EmptyConstructible for PMap<K, V, S> {}
+    impl<K, V, S> Erasable for PMap<K, V, S> {}
+
+    impl<K, V, S> ExactSized for PMap<K, V, S> {
+        fn is_empty(&self) -> bool {
+            self.is_empty()
+        }
+
+        fn len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<K, V, S> AssociatedIterable for PMap<K, V, S> {
+        type Iter<'a>
+        where
+            K: 'a,
+            V: 'a,
+        = Iter<'a, K, V>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.iter()
+        }
+    }
+
+    impl<K: Eq + Hash, V, S: BuildHasher> AssociatedContains for PMap<K, V, S> {
+        fn contains_key(&self, value: &K) -> bool {
+            self.contains_key(value)
+        }
+    }
+
+    // NOTE: `PMap<K, V, S>` is neither `Owned` nor `Mutable`, so the in-place operations can't be called.
+    impl<K: Eq + Hash, V, S: BuildHasher> AssociatedMap for PMap<K, V, S> {
+        fn insert(&mut self, _: K, _: V) -> Option<V> {
+            unreachable!("PMap is not Owned")
+        }
+
+        fn remove(&mut self, _: &K) -> Option<V> {
+            unreachable!("PMap is not Owned")
+        }
+
+        fn remove_entry(&mut self, _: &K) -> Option<(K, V)> {
+            unreachable!("PMap is not Owned")
+        }
+
+        fn get_mut(&mut self, _: &K) -> Option<&mut V> {
+            unreachable!("PMap is not Mutable")
+        }
+
+        fn get(&self, key: &K) -> Option<&V> {
+            self.get(key)
+        }
+
+        fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+            self.get_key_value(key)
+        }
+    }
+
+    impl<K: Eq + Hash, V, S: BuildHasher + Clone> HasherAware for PMap<K, V, S> {
+        type Hasher = S;
+
+        fn hasher(&self) -> &S {
+            &self.hash_builder
+        }
+
+        fn with_hasher(hasher: S) -> Self {
+            Self::with_hasher(hasher)
+        }
+
+        fn with_capacity_and_hasher(_: usize, hasher: S) -> Self {
+            Self::with_hasher(hasher)
+        }
+    }
+
+    impl<K: Eq + Hash + Clone, V: Clone, S: BuildHasher + Clone> Persistent for PMap<K, V, S> {
+        type Key = K;
+        type Value = V;
+
+        fn with_inserted(&self, (key, value): (K, V)) -> Self {
+            self.insert(key, value)
+        }
+
+        fn without(&self, key: &K) -> Self {
+            self.remove(key).unwrap_or_else(|| self.clone())
+        }
+
+        fn with_updated<F: FnOnce(&mut V)>(&self, key: &K, f: F) -> Self {
+            self.update(key, f).unwrap_or_else(|| self.clone())
+        }
+    }
+}
@@ -0,0 +1,509 @@
+//! Persistent vector as a bit-partitioned trie, like Clojure's `PersistentVector`.
+use super::Persistent;
+use crate::{
+    elem::{CollectionTrait, ExtendOwned, IntoIteratorOwned},
+    exact_size::ExactSized,
+    iter::Iterable,
+    misc::{Contains, EmptyConstructible, Erasable},
+    sequential::{DoubleEnded, RandomAccess, Sequential},
+};
+use std::{fmt, iter::FromIterator, sync::Arc};
+
+const BITS: u32 = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+/// Trie node, where leaves hold exactly `WIDTH` elements.
+#[derive(Clone)]
+enum Node<T> {
+    Branch(Vec<Arc<Node<T>>>),
+    Leaf(Vec<T>),
+}
+
+impl<T> Node<T> {
+    fn children_mut(&mut self) -> &mut Vec<Arc<Node<T>>> {
+        match self {
+            Node::Branch(children) => children,
+            Node::Leaf(_) => unreachable!("expected a branch node"),
+        }
+    }
+}
+
+/// Immutable vector, where `push_back`, `pop_back` and `set` return new versions in O(log n).
+///
+/// Elements are stored in a 32-ary trie, except the last (up to 32) ones kept in a tail, so appending is amortized
+/// O(1). Versions share every unmodified node, and cloning one is O(1).
+pub struct PVec<T> {
+    len: usize,
+    shift: u32,
+    root: Arc<Node<T>>,
+    tail: Arc<Vec<T>>,
+}
+
+impl<T> PVec<T> {
+    /// Creates an empty vector.
+    pub fn new() -> Self {
+        PVec {
+            len: 0,
+            shift: BITS,
+            root: Arc::new(Node::Branch(Vec::new())),
+            tail: Arc::new(Vec::new()),
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there is no element.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if `self` and `other` are the same version.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.len == other.len && Arc::ptr_eq(&self.root, &other.root) && Arc::ptr_eq(&self.tail, &other.tail)
+    }
+
+    /// Returns the element at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(&self.chunk_from(index)[0])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first element.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the last element.
+    pub fn back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Iterates over the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            vec: self,
+            front: 0,
+            back: self.len,
+            front_chunk: &[],
+            back_chunk: &[],
+        }
+    }
+
+    /// Index of the first element of the tail.
+    fn tail_offset(&self) -> usize {
+        if self.len < WIDTH {
+            0
+        } else {
+            ((self.len - 1) >> BITS) << BITS
+        }
+    }
+
+    /// Returns the elements from `index` to the end of its leaf or of the tail.
+    fn chunk_from(&self, index: usize) -> &[T] {
+        let tail_offset = self.tail_offset();
+        if index >= tail_offset {
+            return &self.tail[index - tail_offset..];
+        }
+        &self.leaf(index)[index & MASK..]
+    }
+
+    /// Returns the elements from the start of the leaf or of the tail holding `end - 1`, to `end` excluded.
+    fn chunk_to(&self, end: usize) -> &[T] {
+        let tail_offset = self.tail_offset();
+        if end > tail_offset {
+            return &self.tail[..end - tail_offset];
+        }
+        &self.leaf(end - 1)[..((end - 1) & MASK) + 1]
+    }
+
+    /// Returns the leaf holding `index`, which must be before the tail.
+    fn leaf(&self, index: usize) -> &[T] {
+        let mut node = &*self.root;
+        let mut shift = self.shift;
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    node = &children[(index >> shift) & MASK];
+                    shift -= BITS;
+                }
+                Node::Leaf(elems) => return elems,
+            }
+        }
+    }
+}
+
+impl<T: Clone> PVec<T> {
+    /// Returns a new version with `value` appended.
+    pub fn push_back(&self, value: T) -> Self {
+        let mut vec = self.clone();
+        vec.push_mut(value);
+        vec
+    }
+
+    /// Returns a new version without the last element, or `None` if empty.
+    pub fn pop_back(&self) -> Option<Self> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut vec = self.clone();
+        vec.pop_mut();
+        Some(vec)
+    }
+
+    /// Returns a new version with the element at `index` replaced by `value`.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&self, index: usize, value: T) -> Self {
+        assert!(index < self.len, "index {} out of bounds of length {}", index, self.len);
+        let mut vec = self.clone();
+        *vec.get_mut(index) = value;
+        vec
+    }
+
+    /// Appends `value` in place, copying the nodes shared with other versions.
+    fn push_mut(&mut self, value: T) {
+        if self.len - self.tail_offset() < WIDTH {
+            Arc::make_mut(&mut self.tail).push(value);
+            self.len += 1;
+            return;
+        }
+        let tail = std::mem::replace(&mut self.tail, Arc::new(Vec::with_capacity(WIDTH)));
+        let leaf = Arc::new(Node::Leaf(
+            Arc::try_unwrap(tail).unwrap_or_else(|tail| Vec::clone(&tail)),
+        ));
+        if (self.len >> BITS) > (1 << self.shift) {
+            let root = std::mem::replace(&mut self.root, Arc::new(Node::Branch(Vec::new())));
+            self.root = Arc::new(Node::Branch(vec![root, new_path(self.shift, leaf)]));
+            self.shift += BITS;
+        } else {
+            push_leaf(&mut self.root, self.shift, self.len - 1, leaf);
+        }
+        Arc::make_mut(&mut self.tail).push(value);
+        self.len += 1;
+    }
+
+    /// Removes the last element in place, copying the nodes shared with other versions.
+    fn pop_mut(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let value = Arc::make_mut(&mut self.tail).pop();
+        self.len -= 1;
+        if self.tail.is_empty() && self.len > 0 {
+            // NOTE: the last leaf of the trie becomes the tail
+            self.tail = Arc::new(pop_leaf(&mut self.root, self.shift, self.len - 1));
+            if let Node::Branch(children) = &*self.root {
+                if self.shift > BITS && children.len() == 1 {
+                    self.root = Arc::clone(&children[0]);
+                    self.shift -= BITS;
+                }
+            }
+        }
+        value
+    }
+
+    /// Returns the element at `index` mutably, copying the nodes shared with other versions.
+    fn get_mut(&mut self, index: usize) -> &mut T {
+        let tail_offset = self.tail_offset();
+        if index >= tail_offset {
+            return &mut Arc::make_mut(&mut self.tail)[index - tail_offset];
+        }
+        leaf_mut(&mut self.root, self.shift, index)
+    }
+}
+
+/// Wraps `leaf` in single-child branches up to `shift`.
+fn new_path<T>(shift: u32, leaf: Arc<Node<T>>) -> Arc<Node<T>> {
+    if shift == 0 {
+        leaf
+    } else {
+        Arc::new(Node::Branch(vec![new_path(shift - BITS, leaf)]))
+    }
+}
+
+/// Appends `leaf`, holding elements up to `last`, to the trie rooted at `node`.
+fn push_leaf<T: Clone>(node: &mut Arc<Node<T>>, shift: u32, last: usize, leaf: Arc<Node<T>>) {
+    let children = Arc::make_mut(node).children_mut();
+    let index = (last >> shift) & MASK;
+    if shift == BITS {
+        children.push(leaf);
+    } else if index < children.len() {
+        push_leaf(&mut children[index], shift - BITS, last, leaf);
+    } else {
+        children.push(new_path(shift - BITS, leaf));
+    }
+}
+
+/// Removes the last leaf, holding elements up to `last`, from the trie rooted at `node`.
+fn pop_leaf<T: Clone>(node: &mut Arc<Node<T>>, shift: u32, last: usize) -> Vec<T> {
+    let children = Arc::make_mut(node).children_mut();
+    let index = (last >> shift) & MASK;
+    if shift > BITS {
+        let leaf = pop_leaf(&mut children[index], shift - BITS, last);
+        if let Node::Branch(grandchildren) = &*children[index] {
+            if grandchildren.is_empty() {
+                children.pop();
+            }
+        }
+        return leaf;
+    }
+    match children
+        .pop()
+        .map(|leaf| Arc::try_unwrap(leaf).unwrap_or_else(|leaf| Node::clone(&leaf)))
+    {
+        Some(Node::Leaf(elems)) => elems,
+        _ => unreachable!("expected a leaf node"),
+    }
+}
+
+/// Returns the element at `index` mutably from the trie rooted at `node`, copying the shared nodes on the way.
+fn leaf_mut<T: Clone>(node: &mut Arc<Node<T>>, shift: u32, index: usize) -> &mut T {
+    match Arc::make_mut(node) {
+        Node::Branch(children) => leaf_mut(&mut children[(index >> shift) & MASK], shift - BITS, index),
+        Node::Leaf(elems) => &mut elems[index & MASK],
+    }
+}
+
+impl<T> Clone for PVec<T> {
+    fn clone(&self) -> Self {
+        PVec {
+            len: self.len,
+            shift: self.shift,
+            root: Arc::clone(&self.root),
+            tail: Arc::clone(&self.tail),
+        }
+    }
+}
+
+impl<T> Default for PVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || (self.len == other.len && self.iter().eq(other.iter()))
+    }
+}
+
+impl<T: Eq> Eq for PVec<T> {}
+
+impl<T> std::ops::Index<usize> for PVec<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds of length {}", index, self.len),
+        }
+    }
+}
+
+impl<T: Clone> FromIterator<T> for PVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        Extend::extend(&mut vec, iter);
+        vec
+    }
+}
+
+impl<T: Clone> Extend<T> for PVec<T> {
+    /// Appends the elements of `iter` in place, which doesn't affect other versions.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push_mut(value))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PVec<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of a `PVec<T>`, walking the trie one leaf at a time.
+pub struct Iter<'a, T> {
+    vec: &'a PVec<T>,
+    front: usize,
+    back: usize,
+    front_chunk: &'a [T],
+    back_chunk: &'a [T],
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        if self.front_chunk.is_empty() {
+            let chunk = self.vec.chunk_from(self.front);
+            self.front_chunk = &chunk[..chunk.len().min(self.back - self.front)];
+        }
+        let (value, rest) = self.front_chunk.split_first()?;
+        self.front_chunk = rest;
+        self.front += 1;
+        // NOTE: don't yield again the elements already yielded from the front
+        let remaining = self.back - self.front;
+        if self.back_chunk.len() > remaining {
+            self.back_chunk = &self.back_chunk[self.back_chunk.len() - remaining..];
+        }
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        if self.back_chunk.is_empty() {
+            let chunk = self.vec.chunk_to(self.back);
+            self.back_chunk = &chunk[chunk.len() - chunk.len().min(self.back - self.front)..];
+        }
+        let (value, rest) = self.back_chunk.split_last()?;
+        self.back_chunk = rest;
+        self.back -= 1;
+        let remaining = self.back - self.front;
+        if self.front_chunk.len() > remaining {
+            self.front_chunk = &self.front_chunk[..remaining];
+        }
+        Some(value)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> std::iter::FusedIterator for Iter<'a, T> {}
+
+mod impls {
+    use super::*;
+
+    impl<T> CollectionTrait for PVec<T> {
+        type ElemType = T;
+    }
+
+    impl<T> ExtendOwned for PVec<T> {}
+    impl<T> IntoIteratorOwned for PVec<T> {}
+    impl<T> EmptyConstructible for PVec<T> {}
+    impl<T> Erasable for PVec<T> {}
+
+    impl<T> ExactSized for PVec<T> {
+        fn is_empty(&self) -> bool {
+            self.is_empty()
+        }
+
+        fn len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<T> Iterable for PVec<T> {
+        type Iter<'a>
+        where
+            T: 'a,
+        = Iter<'a, T>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.iter()
+        }
+    }
+
+    impl<T: PartialEq> Contains for PVec<T> {
+        fn contains(&self, value: &T) -> bool {
+            self.iter().any(|elem| elem == value)
+        }
+    }
+
+    impl<T> Sequential for PVec<T> {
+        fn back(&self) -> Option<&Self::ElemType> {
+            self.back()
+        }
+    }
+
+    impl<T> DoubleEnded for PVec<T> {
+        fn front(&self) -> Option<&Self::ElemType> {
+            self.front()
+        }
+    }
+
+    impl<T> RandomAccess for PVec<T> {
+        fn partition_point<P: FnMut(&Self::ElemType) -> bool>(&self, mut pred: P) -> Self::SizeType {
+            let (mut left, mut right) = (0, self.len);
+            while left < right {
+                let mid = left + (right - left) / 2;
+                if pred(&self[mid]) {
+                    left = mid + 1;
+                } else {
+                    right = mid;
+                }
+            }
+            left
+        }
+
+        fn get(&self, index: Self::SizeType) -> Option<&Self::ElemType> {
+            self.get(index)
+        }
+    }
+
+    impl<T: Clone> Persistent for PVec<T> {
+        type Key = usize;
+        type Value = T;
+
+        fn with_inserted(&self, elem: T) -> Self {
+            self.push_back(elem)
+        }
+
+        /// Returns a new version without the element at `key`.
+        ///
+        /// NOTE: only removing the last element is O(log n), other elements are shifted by rebuilding the vector.
+        fn without(&self, key: &usize) -> Self {
+            match self.len.checked_sub(1) {
+                Some(last) if *key == last => self.pop_back().unwrap_or_default(),
+                Some(last) if *key < last => {
+                    let mut vec = Self::new();
+                    Extend::extend(&mut vec, self.iter().take(*key).cloned());
+                    Extend::extend(&mut vec, self.iter().skip(*key + 1).cloned());
+                    vec
+                }
+                _ => self.clone(),
+            }
+        }
+
+        fn with_updated<F: FnOnce(&mut T)>(&self, key: &usize, f: F) -> Self {
+            let mut vec = self.clone();
+            if *key < self.len {
+                f(vec.get_mut(*key));
+            }
+            vec
+        }
+    }
+}
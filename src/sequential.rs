@@ -327,6 +327,26 @@ mod impls {
         }
     }
 
+    impl<T, A: std::alloc::Allocator> Contiguous for Box<[T], A> {
+        fn as_slice(&self) -> &[Self::ElemType] {
+            self
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [Self::ElemType] {
+            self
+        }
+    }
+
+    impl<T, A: std::alloc::Allocator> Sequential for Box<[T], A> {
+        fn back(&self) -> Option<&Self::ElemType> {
+            self.last()
+        }
+
+        fn back_mut(&mut self) -> Option<&mut Self::ElemType> {
+            self.last_mut()
+        }
+    }
+
     impl<T, const N: usize> Sequential for [T; N] {
         fn back(&self) -> Option<&Self::ElemType> {
             self.last()
@@ -368,7 +388,7 @@ mod impls {
         }
     }
 
-    impl<T> Sequential for std::collections::LinkedList<T> {
+    impl<T, A: std::alloc::Allocator + std::clone::Clone> Sequential for std::collections::LinkedList<T, A> {
         fn back(&self) -> Option<&Self::ElemType> {
             self.back()
         }
@@ -377,8 +397,16 @@ mod impls {
             self.back_mut()
         }
 
+        // FIXME: std defines `append` and `CursorMut::splice_after` in `impl<T> LinkedList<T>` only, so
+        // `self.append(other)` would resolve to this method and recurse. Move the elements one by one, or swap if
+        // `self` is empty.
         fn append(&mut self, other: &mut Self) {
-            self.append(other)
+            if self.is_empty() {
+                return std::mem::swap(self, other);
+            }
+            while let Some(elt) = other.pop_front() {
+                self.push_back(elt);
+            }
         }
 
         fn push_back(&mut self, elt: Self::ElemType) {
@@ -451,6 +479,32 @@ mod impls {
         }
     }
 
+    impl<T, A: std::alloc::Allocator> RandomAccess for Box<[T], A> {
+        fn rotate_left(&mut self, mid: Self::SizeType) {
+            <[T]>::rotate_left(self, mid)
+        }
+
+        fn rotate_right(&mut self, mid: Self::SizeType) {
+            <[T]>::rotate_right(self, mid)
+        }
+
+        fn partition_point<P: FnMut(&Self::ElemType) -> bool>(&self, pred: P) -> Self::SizeType {
+            <[T]>::partition_point(self, pred)
+        }
+
+        fn swap(&mut self, i: Self::SizeType, j: Self::SizeType) {
+            <[T]>::swap(self, i, j)
+        }
+
+        fn get(&self, index: Self::SizeType) -> Option<&Self::ElemType> {
+            <[T]>::get(self, index)
+        }
+
+        fn get_mut(&mut self, index: Self::SizeType) -> Option<&mut Self::ElemType> {
+            <[T]>::get_mut(self, index)
+        }
+    }
+
     impl<T, A: std::alloc::Allocator + std::clone::Clone> RandomAccess for Vec<T, A> {
         fn rotate_left(&mut self, mid: Self::SizeType) {
             <[T]>::rotate_left(self, mid)
@@ -488,11 +542,11 @@ mod impls {
     sequential_impls!(
         [@Contiguous T, A: std::alloc::Allocator => Vec<T, A>];
 
-        [@Sequential T => std::collections::VecDeque<T>];
+        [@Sequential T, A: std::alloc::Allocator | std::clone::Clone => std::collections::VecDeque<T, A>];
 
-        [@DoubleEnded T => std::collections::VecDeque<T>];
-        [@DoubleEnded T => std::collections::LinkedList<T>];
+        [@DoubleEnded T, A: std::alloc::Allocator | std::clone::Clone => std::collections::VecDeque<T, A>];
+        [@DoubleEnded T, A: std::alloc::Allocator | std::clone::Clone => std::collections::LinkedList<T, A>];
 
-        [@RandomAccess T => std::collections::VecDeque<T>];
+        [@RandomAccess T, A: std::alloc::Allocator | std::clone::Clone => std::collections::VecDeque<T, A>];
     );
 }
@@ -0,0 +1,579 @@
+//! Copy-on-write collections sharing their contents through `Arc<C>`.
+//!
+//! Cloning a `Shared<C>` only bumps a reference count, so readers can keep consistent snapshots while a writer
+//! goes on modifying its own copy. Reads are delegated to `C` directly, while modifications go through
+//! `Arc::make_mut`, cloning `C` only when another snapshot still shares it.
+use crate::{
+    associated::{AssociatedMap, AssociatedMapIter, AssociatedSet, AssociatedSetAlgebra, AssociatedSetOperation},
+    capacity::CapacityAware,
+    elem::{AssociatedCollectionTrait, CollectionTrait, Mutable, Owned},
+    exact_size::ExactSized,
+    iter::{AssociatedIterable, AssociatedIterableMut, Iterable, IterableMut},
+    misc::{AssociatedContains, AssociatedRetainable, Contains, EmptyConstructible, Erasable, Retainable},
+    sequential::{DoubleEnded, RandomAccess, Sequential},
+};
+use std::{fmt, iter::FromIterator, sync::Arc};
+
+/// Collection shared through `Arc<C>`, cloned on the first modification while shared.
+pub struct Shared<C> {
+    inner: Arc<C>,
+}
+
+impl<C> Shared<C> {
+    /// Wraps `collection`.
+    pub fn new(collection: C) -> Self {
+        Shared {
+            inner: Arc::new(collection),
+        }
+    }
+
+    /// Returns a snapshot sharing the same contents, in O(1).
+    pub fn snapshot(&self) -> Self {
+        Shared {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    /// Returns true if `self` and `other` share the same contents.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Returns the number of snapshots sharing the contents, including `self`.
+    pub fn share_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
+    /// Returns the shared `Arc<C>`.
+    pub fn as_arc(&self) -> &Arc<C> {
+        &self.inner
+    }
+}
+
+impl<C: Clone> Shared<C> {
+    /// Returns the collection mutably, cloning it first if other snapshots share it.
+    pub fn make_mut(&mut self) -> &mut C {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// Unwraps the collection, cloning it if other snapshots share it.
+    pub fn into_inner(self) -> C {
+        Arc::try_unwrap(self.inner).unwrap_or_else(|inner| C::clone(&inner))
+    }
+}
+
+impl<C> Clone for Shared<C> {
+    fn clone(&self) -> Self {
+        self.snapshot()
+    }
+}
+
+impl<C: Default> Default for Shared<C> {
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for Shared<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        C::fmt(&self.inner, f)
+    }
+}
+
+impl<C: PartialEq> PartialEq for Shared<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.inner == other.inner
+    }
+}
+
+impl<C: Eq> Eq for Shared<C> {}
+
+impl<C> std::ops::Deref for Shared<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C> AsRef<C> for Shared<C> {
+    fn as_ref(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C> From<C> for Shared<C> {
+    fn from(collection: C) -> Self {
+        Self::new(collection)
+    }
+}
+
+impl<C> From<Arc<C>> for Shared<C> {
+    fn from(inner: Arc<C>) -> Self {
+        Shared { inner }
+    }
+}
+
+impl<T, C: FromIterator<T>> FromIterator<T> for Shared<C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(C::from_iter(iter))
+    }
+}
+
+impl<T, C: Extend<T> + Clone> Extend<T> for Shared<C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.make_mut().extend(iter)
+    }
+
+    fn extend_one(&mut self, item: T) {
+        self.make_mut().extend_one(item)
+    }
+
+    fn extend_reserve(&mut self, additional: usize) {
+        self.make_mut().extend_reserve(additional)
+    }
+}
+
+impl<C: IntoIterator + Clone> IntoIterator for Shared<C> {
+    type Item = C::Item;
+    type IntoIter = C::IntoIter;
+
+    /// Consumes the collection, cloning it if other snapshots share it.
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+impl<'a, C> IntoIterator for &'a Shared<C>
+where
+    &'a C: IntoIterator,
+{
+    type Item = <&'a C as IntoIterator>::Item;
+    type IntoIter = <&'a C as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.as_ref().into_iter()
+    }
+}
+
+mod impls {
+    use super::*;
+
+    impl<C: CollectionTrait> CollectionTrait for Shared<C> {
+        type ElemType = C::ElemType;
+    }
+
+    impl<C: AssociatedCollectionTrait> AssociatedCollectionTrait for Shared<C> {
+        type KeyType = C::KeyType;
+        type ValueType = C::ValueType;
+    }
+
+    impl<C: Mutable + Clone> Mutable for Shared<C> {}
+    impl<C: Owned + Clone> Owned for Shared<C> {}
+
+    impl<C: ExactSized> ExactSized for Shared<C> {
+        type SizeType = C::SizeType;
+
+        fn is_empty(&self) -> bool {
+            self.inner.is_empty()
+        }
+
+        fn len(&self) -> Self::SizeType {
+            self.inner.len()
+        }
+    }
+
+    impl<C: CapacityAware> CapacityAware for Shared<C> {
+        type CapacityType = C::CapacityType;
+
+        fn capacity(&self) -> Self::CapacityType {
+            self.inner.capacity()
+        }
+    }
+
+    impl<C: Iterable> Iterable for Shared<C> {
+        type Iter<'a>
+        where
+            C::ElemType: 'a,
+        = C::Iter<'a>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.inner.iter()
+        }
+    }
+
+    impl<C: IterableMut + Clone> IterableMut for Shared<C> {
+        type IterMut<'a>
+        where
+            C::ElemType: 'a,
+        = C::IterMut<'a>;
+
+        fn iter_mut(&mut self) -> Self::IterMut<'_> {
+            self.make_mut().iter_mut()
+        }
+    }
+
+    impl<C: AssociatedIterable> AssociatedIterable for Shared<C> {
+        type Iter<'a>
+        where
+            C::KeyType: 'a,
+            C::ValueType: 'a,
+        = C::Iter<'a>;
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.inner.iter()
+        }
+    }
+
+    impl<C: AssociatedIterableMut + Clone> AssociatedIterableMut for Shared<C> {
+        type IterMut<'a>
+        where
+            C::KeyType: 'a,
+            C::ValueType: 'a,
+        = C::IterMut<'a>;
+
+        fn iter_mut(&mut self) -> Self::IterMut<'_> {
+            self.make_mut().iter_mut()
+        }
+    }
+
+    impl<C: EmptyConstructible + Owned> EmptyConstructible for Shared<C> {
+        fn new() -> Self {
+            Self::new(C::new())
+        }
+    }
+
+    impl<C: Erasable + EmptyConstructible + Owned + Clone> Erasable for Shared<C> {
+        /// Clears the collection in place if not shared, otherwise starts over from an empty one.
+        ///
+        /// NOTE: the fresh collection doesn't keep the capacity or the hasher of the shared one.
+        fn clear(&mut self) {
+            match Arc::get_mut(&mut self.inner) {
+                Some(inner) => inner.clear(),
+                None => self.inner = Arc::new(C::new()),
+            }
+        }
+    }
+
+    impl<C: Contains> Contains for Shared<C> {
+        fn contains(&self, value: &Self::ElemType) -> bool {
+            self.inner.contains(value)
+        }
+    }
+
+    impl<C: AssociatedContains> AssociatedContains for Shared<C> {
+        fn contains_key(&self, value: &Self::KeyType) -> bool {
+            self.inner.contains_key(value)
+        }
+    }
+
+    impl<C: Retainable + Clone> Retainable for Shared<C> {
+        fn retain<F: FnMut(&Self::ElemType) -> bool>(&mut self, pred: F) {
+            self.make_mut().retain(pred)
+        }
+    }
+
+    impl<C: AssociatedRetainable + Clone> AssociatedRetainable for Shared<C> {
+        fn retain<F: FnMut(&Self::KeyType, &mut Self::ValueType) -> bool>(&mut self, pred: F) {
+            self.make_mut().retain(pred)
+        }
+    }
+
+    impl<C: Sequential + Owned + Clone> Sequential for Shared<C> {
+        fn back(&self) -> Option<&Self::ElemType> {
+            self.inner.back()
+        }
+
+        fn back_mut(&mut self) -> Option<&mut Self::ElemType> {
+            self.make_mut().back_mut()
+        }
+
+        fn append(&mut self, other: &mut Self) {
+            if !other.is_empty() {
+                self.make_mut().append(other.make_mut())
+            }
+        }
+
+        fn push_back(&mut self, elt: Self::ElemType) {
+            self.make_mut().push_back(elt)
+        }
+
+        fn pop_back(&mut self) -> Option<Self::ElemType> {
+            self.make_mut().pop_back()
+        }
+
+        fn remove(&mut self, at: Self::SizeType) -> Option<Self::ElemType> {
+            Sequential::remove(self.make_mut(), at)
+        }
+
+        fn split_off(&mut self, at: Self::SizeType) -> Self {
+            Self::new(self.make_mut().split_off(at))
+        }
+    }
+
+    impl<C: DoubleEnded + Owned + Clone> DoubleEnded for Shared<C> {
+        fn front(&self) -> Option<&Self::ElemType> {
+            self.inner.front()
+        }
+
+        fn front_mut(&mut self) -> Option<&mut Self::ElemType> {
+            self.make_mut().front_mut()
+        }
+
+        fn push_front(&mut self, value: Self::ElemType) {
+            self.make_mut().push_front(value)
+        }
+
+        fn pop_front(&mut self) -> Option<Self::ElemType> {
+            self.make_mut().pop_front()
+        }
+    }
+
+    impl<C: RandomAccess + Owned + Clone> RandomAccess for Shared<C> {
+        fn rotate_left(&mut self, mid: Self::SizeType) {
+            self.make_mut().rotate_left(mid)
+        }
+
+        fn rotate_right(&mut self, mid: Self::SizeType) {
+            self.make_mut().rotate_right(mid)
+        }
+
+        fn partition_point<P: FnMut(&Self::ElemType) -> bool>(&self, pred: P) -> Self::SizeType {
+            self.inner.partition_point(pred)
+        }
+
+        fn swap(&mut self, i: Self::SizeType, j: Self::SizeType) {
+            self.make_mut().swap(i, j)
+        }
+
+        fn get(&self, index: Self::SizeType) -> Option<&Self::ElemType> {
+            RandomAccess::get(&*self.inner, index)
+        }
+
+        fn get_mut(&mut self, index: Self::SizeType) -> Option<&mut Self::ElemType> {
+            RandomAccess::get_mut(self.make_mut(), index)
+        }
+
+        fn insert(&mut self, index: Self::SizeType, value: Self::ElemType) {
+            RandomAccess::insert(self.make_mut(), index, value)
+        }
+
+        fn swap_remove_back(&mut self, index: Self::SizeType) -> Option<Self::ElemType> {
+            self.make_mut().swap_remove_back(index)
+        }
+    }
+
+    impl<C: AssociatedSet + Owned + Clone> AssociatedSet for Shared<C> {
+        fn is_disjoint(&self, other: &Self) -> bool {
+            self.inner.is_disjoint(&other.inner)
+        }
+
+        fn is_subset(&self, other: &Self) -> bool {
+            self.ptr_eq(other) || self.inner.is_subset(&other.inner)
+        }
+
+        fn is_superset(&self, other: &Self) -> bool {
+            self.ptr_eq(other) || self.inner.is_superset(&other.inner)
+        }
+
+        fn take(&mut self, value: &Self::ElemType) -> Option<Self::ElemType> {
+            if self.inner.contains(value) {
+                self.make_mut().take(value)
+            } else {
+                None
+            }
+        }
+
+        fn insert(&mut self, value: Self::ElemType) -> bool {
+            AssociatedSet::insert(self.make_mut(), value)
+        }
+
+        fn get(&self, value: &Self::ElemType) -> Option<&Self::ElemType> {
+            AssociatedSet::get(&*self.inner, value)
+        }
+
+        fn remove(&mut self, value: &Self::ElemType) -> bool {
+            // NOTE: avoid cloning a shared set when there is nothing to remove
+            self.inner.contains(value) && AssociatedSet::remove(self.make_mut(), value)
+        }
+
+        fn replace(&mut self, value: Self::ElemType) -> Option<Self::ElemType> {
+            self.make_mut().replace(value)
+        }
+    }
+
+    impl<S, C: AssociatedSetOperation<S> + Owned + Clone> AssociatedSetOperation<S> for Shared<C> {
+        type DifferenceIter<'a>
+        where
+            C::ElemType: 'a,
+            S: 'a,
+        = C::DifferenceIter<'a>;
+
+        type IntersectionIter<'a>
+        where
+            C::ElemType: 'a,
+            S: 'a,
+        = C::IntersectionIter<'a>;
+
+        type SymmetricDifferenceIter<'a>
+        where
+            C::ElemType: 'a,
+            S: 'a,
+        = C::SymmetricDifferenceIter<'a>;
+
+        type UnionIter<'a>
+        where
+            C::ElemType: 'a,
+            S: 'a,
+        = C::UnionIter<'a>;
+
+        fn difference<'a>(&'a self, other: &'a Self) -> Self::DifferenceIter<'a> {
+            self.inner.difference(&other.inner)
+        }
+
+        fn intersection<'a>(&'a self, other: &'a Self) -> Self::IntersectionIter<'a> {
+            self.inner.intersection(&other.inner)
+        }
+
+        fn symmetric_difference<'a>(&'a self, other: &'a Self) -> Self::SymmetricDifferenceIter<'a> {
+            self.inner.symmetric_difference(&other.inner)
+        }
+
+        fn union<'a>(&'a self, other: &'a Self) -> Self::UnionIter<'a> {
+            self.inner.union(&other.inner)
+        }
+    }
+
+    impl<C: AssociatedSetAlgebra + Owned + Clone> AssociatedSetAlgebra for Shared<C> {
+        fn union_owned(self, other: Self) -> Self {
+            Self::new(self.into_inner().union_owned(other.into_inner()))
+        }
+
+        fn intersection_owned(self, other: Self) -> Self {
+            Self::new(self.into_inner().intersection_owned(other.into_inner()))
+        }
+
+        fn difference_owned(self, other: Self) -> Self {
+            Self::new(self.into_inner().difference_owned(other.into_inner()))
+        }
+
+        fn symmetric_difference_owned(self, other: Self) -> Self {
+            Self::new(self.into_inner().symmetric_difference_owned(other.into_inner()))
+        }
+
+        fn union_with(&mut self, other: &Self)
+        where
+            Self::ElemType: Clone,
+        {
+            if !self.ptr_eq(other) {
+                self.make_mut().union_with(&other.inner)
+            }
+        }
+
+        fn intersect_with(&mut self, other: &Self) {
+            if !self.ptr_eq(other) {
+                self.make_mut().intersect_with(&other.inner)
+            }
+        }
+
+        fn subtract(&mut self, other: &Self) {
+            self.make_mut().subtract(&other.inner)
+        }
+
+        fn symmetric_difference_with(&mut self, other: &Self)
+        where
+            Self::ElemType: Clone,
+        {
+            self.make_mut().symmetric_difference_with(&other.inner)
+        }
+    }
+
+    impl<C: AssociatedMap + Owned + Clone> AssociatedMap for Shared<C> {
+        fn insert(&mut self, key: Self::KeyType, value: Self::ValueType) -> Option<Self::ValueType> {
+            AssociatedMap::insert(self.make_mut(), key, value)
+        }
+
+        fn remove(&mut self, key: &Self::KeyType) -> Option<Self::ValueType> {
+            // NOTE: avoid cloning a shared map when there is nothing to remove
+            if self.inner.contains_key(key) {
+                AssociatedMap::remove(self.make_mut(), key)
+            } else {
+                None
+            }
+        }
+
+        fn remove_entry(&mut self, key: &Self::KeyType) -> Option<(Self::KeyType, Self::ValueType)> {
+            if self.inner.contains_key(key) {
+                self.make_mut().remove_entry(key)
+            } else {
+                None
+            }
+        }
+
+        fn get(&self, key: &Self::KeyType) -> Option<&Self::ValueType> {
+            AssociatedMap::get(&*self.inner, key)
+        }
+
+        fn get_mut(&mut self, key: &Self::KeyType) -> Option<&mut Self::ValueType> {
+            if self.inner.contains_key(key) {
+                AssociatedMap::get_mut(self.make_mut(), key)
+            } else {
+                None
+            }
+        }
+
+        fn get_key_value(&self, key: &Self::KeyType) -> Option<(&Self::KeyType, &Self::ValueType)> {
+            self.inner.get_key_value(key)
+        }
+    }
+
+    impl<S, C: AssociatedMapIter<S> + Owned + Clone> AssociatedMapIter<S> for Shared<C> {
+        type TryInsertError<'a>
+        where
+            C::KeyType: 'a,
+            C::ValueType: 'a,
+            S: 'a,
+        = C::TryInsertError<'a>;
+
+        type KeyIter<'a>
+        where
+            C::KeyType: 'a,
+            C::ValueType: 'a,
+            S: 'a,
+        = C::KeyIter<'a>;
+
+        type ValueIter<'a>
+        where
+            C::KeyType: 'a,
+            C::ValueType: 'a,
+            S: 'a,
+        = C::ValueIter<'a>;
+
+        type ValueIterMut<'a>
+        where
+            C::KeyType: 'a,
+            C::ValueType: 'a,
+            S: 'a,
+        = C::ValueIterMut<'a>;
+
+        fn try_insert(
+            &mut self,
+            key: Self::KeyType,
+            value: Self::ValueType,
+        ) -> Result<&mut Self::ValueType, Self::TryInsertError<'_>> {
+            self.make_mut().try_insert(key, value)
+        }
+
+        fn keys(&self) -> Self::KeyIter<'_> {
+            self.inner.keys()
+        }
+
+        fn values(&self) -> Self::ValueIter<'_> {
+            self.inner.values()
+        }
+
+        fn values_mut(&mut self) -> Self::ValueIterMut<'_> {
+            self.make_mut().values_mut()
+        }
+    }
+}
@@ -0,0 +1,460 @@
+//! Divide-and-conquer over borrowed views of collections.
+//!
+//! `Splittable` views can be cut into two halves cheaply, which is all recursive algorithms need. `par_fold`
+//! drives them on scoped threads, without depending on a thread pool.
+use std::{
+    alloc::Allocator,
+    collections::{btree_map, btree_set, BTreeMap, BTreeSet, VecDeque},
+    iter::Chain,
+    ops::Bound,
+};
+
+/// Borrowed views that can be split into two halves.
+pub trait Splittable: Sized {
+    /// Number of elements in the view, used to decide whether to split further.
+    fn len_hint(&self) -> usize;
+
+    /// Splits the view into two views with about half of the elements each, in order.
+    fn split_at_mid(self) -> (Self, Self);
+}
+
+/// Collections that can be viewed as a `Splittable`.
+pub trait SplitView {
+    /// View of the whole collection
+    type View<'a>: Splittable
+    where
+        Self: 'a;
+
+    /// Borrows the whole collection as a `Splittable` view.
+    fn split_view(&self) -> Self::View<'_>;
+}
+
+/// Collections that can be mutably viewed as a `Splittable`.
+pub trait SplitViewMut {
+    /// Mutable view of the whole collection
+    type ViewMut<'a>: Splittable
+    where
+        Self: 'a;
+
+    /// Mutably borrows the whole collection as a `Splittable` view.
+    fn split_view_mut(&mut self) -> Self::ViewMut<'_>;
+}
+
+/// Two consecutive slices viewed as one sequence, e.g. `VecDeque::as_slices`.
+#[derive(Debug)]
+pub struct SlicePair<'a, T> {
+    /// Front part of the sequence
+    pub front: &'a [T],
+    /// Back part of the sequence
+    pub back: &'a [T],
+}
+
+impl<'a, T> Clone for SlicePair<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for SlicePair<'a, T> {}
+
+impl<'a, T> SlicePair<'a, T> {
+    /// Iterates over the elements in order.
+    pub fn iter(&self) -> Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>> {
+        self.front.iter().chain(self.back.iter())
+    }
+}
+
+impl<'a, T, A: Allocator> From<&'a VecDeque<T, A>> for SlicePair<'a, T> {
+    fn from(deque: &'a VecDeque<T, A>) -> Self {
+        let (front, back) = deque.as_slices();
+        SlicePair { front, back }
+    }
+}
+
+impl<'a, T> IntoIterator for SlicePair<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Two consecutive mutable slices viewed as one sequence, e.g. `VecDeque::as_mut_slices`.
+#[derive(Debug)]
+pub struct SlicePairMut<'a, T> {
+    /// Front part of the sequence
+    pub front: &'a mut [T],
+    /// Back part of the sequence
+    pub back: &'a mut [T],
+}
+
+impl<'a, T, A: Allocator> From<&'a mut VecDeque<T, A>> for SlicePairMut<'a, T> {
+    fn from(deque: &'a mut VecDeque<T, A>) -> Self {
+        let (front, back) = deque.as_mut_slices();
+        SlicePairMut { front, back }
+    }
+}
+
+impl<'a, T> IntoIterator for SlicePairMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = Chain<std::slice::IterMut<'a, T>, std::slice::IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.front.iter_mut().chain(self.back.iter_mut())
+    }
+}
+
+/// View of the entries of a `BTreeMap<K, V>` between two key bounds.
+///
+/// NOTE: B-trees don't track the number of elements in a subtree, so finding the middle key walks half of the
+/// range. Splitting down to single elements costs `O(n log n)` in total.
+#[derive(Debug)]
+pub struct BTreeMapRange<'a, K, V, A: Allocator + Clone = std::alloc::Global> {
+    map: &'a BTreeMap<K, V, A>,
+    lower: Bound<&'a K>,
+    upper: Bound<&'a K>,
+    len: usize,
+}
+
+impl<'a, K, V, A: Allocator + Clone> Clone for BTreeMapRange<'a, K, V, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V, A: Allocator + Clone> Copy for BTreeMapRange<'a, K, V, A> {}
+
+impl<'a, K: Ord, V, A: Allocator + Clone> BTreeMapRange<'a, K, V, A> {
+    /// Creates a view of the entries whose keys are within `lower` and `upper`.
+    pub fn new(map: &'a BTreeMap<K, V, A>, lower: Bound<&'a K>, upper: Bound<&'a K>) -> Self {
+        let len = if matches!((lower, upper), (Bound::Unbounded, Bound::Unbounded)) {
+            map.len()
+        } else {
+            map.range::<K, _>((lower, upper)).count()
+        };
+        BTreeMapRange { map, lower, upper, len }
+    }
+
+    /// Iterates over the entries in the view.
+    pub fn iter(&self) -> btree_map::Range<'a, K, V> {
+        self.map.range::<K, _>((self.lower, self.upper))
+    }
+}
+
+impl<'a, K: Ord, V, A: Allocator + Clone> IntoIterator for BTreeMapRange<'a, K, V, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = btree_map::Range<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// View of the values of a `BTreeSet<T>` between two bounds.
+///
+/// NOTE: see `BTreeMapRange` for the cost of splitting.
+#[derive(Debug)]
+pub struct BTreeSetRange<'a, T, A: Allocator + Clone = std::alloc::Global> {
+    set: &'a BTreeSet<T, A>,
+    lower: Bound<&'a T>,
+    upper: Bound<&'a T>,
+    len: usize,
+}
+
+impl<'a, T, A: Allocator + Clone> Clone for BTreeSetRange<'a, T, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, A: Allocator + Clone> Copy for BTreeSetRange<'a, T, A> {}
+
+impl<'a, T: Ord, A: Allocator + Clone> BTreeSetRange<'a, T, A> {
+    /// Creates a view of the values within `lower` and `upper`.
+    pub fn new(set: &'a BTreeSet<T, A>, lower: Bound<&'a T>, upper: Bound<&'a T>) -> Self {
+        let len = if matches!((lower, upper), (Bound::Unbounded, Bound::Unbounded)) {
+            set.len()
+        } else {
+            set.range::<T, _>((lower, upper)).count()
+        };
+        BTreeSetRange { set, lower, upper, len }
+    }
+
+    /// Iterates over the values in the view.
+    pub fn iter(&self) -> btree_set::Range<'a, T> {
+        self.set.range::<T, _>((self.lower, self.upper))
+    }
+}
+
+impl<'a, T: Ord, A: Allocator + Clone> IntoIterator for BTreeSetRange<'a, T, A> {
+    type Item = &'a T;
+    type IntoIter = btree_set::Range<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Folds `view` by splitting it until views have at most `min_len` elements, folding those with `fold` on scoped
+/// threads, and combining the results in order with `reduce`.
+///
+/// Threads are only spawned for the first few levels of splitting, enough to occupy the available parallelism.
+pub fn par_fold<S, R, F, C>(view: S, min_len: usize, fold: F, reduce: C) -> R
+where
+    S: Splittable + Send,
+    R: Send,
+    F: Fn(S) -> R + Sync,
+    C: Fn(R, R) -> R + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let depth = (usize::BITS - threads.leading_zeros()) as usize;
+    fold_rec(view, min_len.max(1), depth, &fold, &reduce)
+}
+
+fn fold_rec<S, R, F, C>(view: S, min_len: usize, depth: usize, fold: &F, reduce: &C) -> R
+where
+    S: Splittable + Send,
+    R: Send,
+    F: Fn(S) -> R + Sync,
+    C: Fn(R, R) -> R + Sync,
+{
+    if view.len_hint() <= min_len {
+        return fold(view);
+    }
+    let (left, right) = view.split_at_mid();
+    if depth == 0 {
+        let left = fold_rec(left, min_len, 0, fold, reduce);
+        return reduce(left, fold_rec(right, min_len, 0, fold, reduce));
+    }
+    std::thread::scope(|s| {
+        let handle = s.spawn(move || fold_rec(left, min_len, depth - 1, fold, reduce));
+        let right = fold_rec(right, min_len, depth - 1, fold, reduce);
+        let left = handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e));
+        reduce(left, right)
+    })
+}
+
+mod impls {
+    use super::*;
+
+    impl<T> Splittable for &[T] {
+        fn len_hint(&self) -> usize {
+            self.len()
+        }
+
+        fn split_at_mid(self) -> (Self, Self) {
+            self.split_at(self.len() / 2)
+        }
+    }
+
+    impl<T> Splittable for &mut [T] {
+        fn len_hint(&self) -> usize {
+            self.len()
+        }
+
+        fn split_at_mid(self) -> (Self, Self) {
+            let mid = self.len() / 2;
+            self.split_at_mut(mid)
+        }
+    }
+
+    impl<'a, T> Splittable for SlicePair<'a, T> {
+        fn len_hint(&self) -> usize {
+            self.front.len() + self.back.len()
+        }
+
+        fn split_at_mid(self) -> (Self, Self) {
+            let mid = self.len_hint() / 2;
+            let SlicePair { front, back } = self;
+            if mid <= front.len() {
+                let (left, right) = front.split_at(mid);
+                (SlicePair { front: left, back: &[] }, SlicePair { front: right, back })
+            } else {
+                let (left, right) = back.split_at(mid - front.len());
+                (
+                    SlicePair { front, back: left },
+                    SlicePair {
+                        front: right,
+                        back: &[],
+                    },
+                )
+            }
+        }
+    }
+
+    impl<'a, T> Splittable for SlicePairMut<'a, T> {
+        fn len_hint(&self) -> usize {
+            self.front.len() + self.back.len()
+        }
+
+        fn split_at_mid(self) -> (Self, Self) {
+            let mid = self.len_hint() / 2;
+            let SlicePairMut { front, back } = self;
+            if mid <= front.len() {
+                let (left, right) = front.split_at_mut(mid);
+                (
+                    SlicePairMut {
+                        front: left,
+                        back: &mut [],
+                    },
+                    SlicePairMut { front: right, back },
+                )
+            } else {
+                let (left, right) = back.split_at_mut(mid - front.len());
+                (
+                    SlicePairMut { front, back: left },
+                    SlicePairMut {
+                        front: right,
+                        back: &mut [],
+                    },
+                )
+            }
+        }
+    }
+
+    impl<'a, K: Ord, V, A: Allocator + Clone> Splittable for BTreeMapRange<'a, K, V, A> {
+        fn len_hint(&self) -> usize {
+            self.len
+        }
+
+        fn split_at_mid(self) -> (Self, Self) {
+            let mid = self.len / 2;
+            match self.iter().nth(mid) {
+                Some((key, _)) => (
+                    BTreeMapRange {
+                        upper: Bound::Excluded(key),
+                        len: mid,
+                        ..self
+                    },
+                    BTreeMapRange {
+                        lower: Bound::Included(key),
+                        len: self.len - mid,
+                        ..self
+                    },
+                ),
+                None => (BTreeMapRange { len: 0, ..self }, self),
+            }
+        }
+    }
+
+    impl<'a, T: Ord, A: Allocator + Clone> Splittable for BTreeSetRange<'a, T, A> {
+        fn len_hint(&self) -> usize {
+            self.len
+        }
+
+        fn split_at_mid(self) -> (Self, Self) {
+            let mid = self.len / 2;
+            match self.iter().nth(mid) {
+                Some(value) => (
+                    BTreeSetRange {
+                        upper: Bound::Excluded(value),
+                        len: mid,
+                        ..self
+                    },
+                    BTreeSetRange {
+                        lower: Bound::Included(value),
+                        len: self.len - mid,
+                        ..self
+                    },
+                ),
+                None => (BTreeSetRange { len: 0, ..self }, self),
+            }
+        }
+    }
+
+    impl<T, const N: usize> SplitView for [T; N] {
+        type View<'a>
+        where
+            T: 'a,
+        = &'a [T];
+
+        fn split_view(&self) -> Self::View<'_> {
+            self
+        }
+    }
+
+    impl<T, A: Allocator> SplitView for Vec<T, A> {
+        type View<'a>
+        where
+            T: 'a,
+            A: 'a,
+        = &'a [T];
+
+        fn split_view(&self) -> Self::View<'_> {
+            self
+        }
+    }
+
+    impl<T, A: Allocator> SplitView for VecDeque<T, A> {
+        type View<'a>
+        where
+            T: 'a,
+            A: 'a,
+        = SlicePair<'a, T>;
+
+        fn split_view(&self) -> Self::View<'_> {
+            self.into()
+        }
+    }
+
+    impl<K: Ord, V, A: Allocator + Clone> SplitView for BTreeMap<K, V, A> {
+        type View<'a>
+        where
+            K: 'a,
+            V: 'a,
+            A: 'a,
+        = BTreeMapRange<'a, K, V, A>;
+
+        fn split_view(&self) -> Self::View<'_> {
+            BTreeMapRange::new(self, Bound::Unbounded, Bound::Unbounded)
+        }
+    }
+
+    impl<T: Ord, A: Allocator + Clone> SplitView for BTreeSet<T, A> {
+        type View<'a>
+        where
+            T: 'a,
+            A: 'a,
+        = BTreeSetRange<'a, T, A>;
+
+        fn split_view(&self) -> Self::View<'_> {
+            BTreeSetRange::new(self, Bound::Unbounded, Bound::Unbounded)
+        }
+    }
+
+    impl<T, const N: usize> SplitViewMut for [T; N] {
+        type ViewMut<'a>
+        where
+            T: 'a,
+        = &'a mut [T];
+
+        fn split_view_mut(&mut self) -> Self::ViewMut<'_> {
+            self
+        }
+    }
+
+    impl<T, A: Allocator> SplitViewMut for Vec<T, A> {
+        type ViewMut<'a>
+        where
+            T: 'a,
+            A: 'a,
+        = &'a mut [T];
+
+        fn split_view_mut(&mut self) -> Self::ViewMut<'_> {
+            self
+        }
+    }
+
+    impl<T, A: Allocator> SplitViewMut for VecDeque<T, A> {
+        type ViewMut<'a>
+        where
+            T: 'a,
+            A: 'a,
+        = SlicePairMut<'a, T>;
+
+        fn split_view_mut(&mut self) -> Self::ViewMut<'_> {
+            self.into()
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Lock-based wrappers sharing collections across threads.
+//!
+//! NOTE: a panic while holding the lock doesn't poison the wrappers. `std` collections stay valid after a panic,
+//! though a closure passed to `with_write` may leave its modifications half done.
+use crate::{
+    associated::AssociatedMap,
+    elem::{AssociatedCollectionTrait, CollectionTrait, ExtendOwned, Owned},
+    exact_size::ExactSized,
+    misc::{AssociatedRetainable, Contains, Erasable},
+};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+pub mod sharded;
+
+pub use self::sharded::ShardedMap;
+
+/// Maps supporting atomic operations through shared references.
+pub trait ConcurrentMap: AssociatedCollectionTrait {
+    /// Returns a clone of the value of `key`.
+    fn get_cloned(&self, key: &Self::KeyType) -> Option<Self::ValueType>
+    where
+        Self::ValueType: Clone;
+
+    /// Inserts a key-value pair, returning the old value.
+    fn insert(&self, key: Self::KeyType, value: Self::ValueType) -> Option<Self::ValueType>;
+
+    /// Removes `key`, returning its value.
+    fn remove(&self, key: &Self::KeyType) -> Option<Self::ValueType>;
+
+    /// Returns true if the map contains a value for `key`.
+    fn contains_key(&self, key: &Self::KeyType) -> bool;
+
+    /// Returns the number of entries.
+    ///
+    /// NOTE: the result may be outdated as soon as it's returned if other threads modify the map. Sharded maps don't
+    /// lock all shards at once, so it's only an approximation under concurrent modifications.
+    fn len(&self) -> usize;
+
+    /// Returns true if there is no entry.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Updates the value of `key` with `f` if present, returning its result.
+    fn update<R, F>(&self, key: &Self::KeyType, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut Self::ValueType) -> R;
+
+    /// Retains only the entries specified by the predicate.
+    fn retain<F>(&self, f: F)
+    where
+        F: FnMut(&Self::KeyType, &mut Self::ValueType) -> bool;
+
+    /// Updates the value of `key` with `update`, or inserts the one created by `insert` if absent.
+    fn upsert<I, U>(&self, key: Self::KeyType, insert: I, update: U)
+    where
+        I: FnOnce() -> Self::ValueType,
+        U: FnOnce(&mut Self::ValueType);
+
+    /// Returns a clone of the value of `key`, inserting the one created by `f` if absent.
+    ///
+    /// `f` is called at most once, and only if no other thread inserted `key` first.
+    fn compute_if_absent<F>(&self, key: Self::KeyType, f: F) -> Self::ValueType
+    where
+        F: FnOnce(&Self::KeyType) -> Self::ValueType,
+        Self::ValueType: Clone;
+}
+
+/// Acquires a read lock, ignoring poisoning.
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Acquires a write lock, ignoring poisoning.
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Collection behind a `RwLock`.
+#[derive(Debug, Default)]
+pub struct SyncCollection<C> {
+    inner: RwLock<C>,
+}
+
+impl<C> SyncCollection<C> {
+    /// Wraps `collection`.
+    pub fn new(collection: C) -> Self {
+        SyncCollection {
+            inner: RwLock::new(collection),
+        }
+    }
+
+    /// Returns the wrapped collection.
+    pub fn into_inner(self) -> C {
+        self.inner.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the wrapped collection mutably, without locking.
+    pub fn get_mut(&mut self) -> &mut C {
+        self.inner.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, C> {
+        read(&self.inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, C> {
+        write(&self.inner)
+    }
+
+    /// Calls `f` with the collection under a read lock.
+    pub fn with_read<R, F: FnOnce(&C) -> R>(&self, f: F) -> R {
+        f(&self.read())
+    }
+
+    /// Calls `f` with the collection under a write lock.
+    pub fn with_write<R, F: FnOnce(&mut C) -> R>(&self, f: F) -> R {
+        f(&mut self.write())
+    }
+}
+
+impl<C: CollectionTrait + ExactSized> SyncCollection<C> {
+    /// Returns the number of elements.
+    pub fn len(&self) -> C::SizeType {
+        self.read().len()
+    }
+
+    /// Returns true if there is no element.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Returns true if the collection contains `value`.
+    pub fn contains(&self, value: &C::ElemType) -> bool
+    where
+        C: Contains,
+    {
+        self.read().contains(value)
+    }
+
+    /// Extends the collection with the contents of `iter`, under one write lock.
+    pub fn extend<I: IntoIterator<Item = C::ElemType>>(&self, iter: I)
+    where
+        C: ExtendOwned + Owned,
+    {
+        ExtendOwned::extend(&mut *self.write(), iter)
+    }
+
+    /// Removes all elements.
+    pub fn clear(&self)
+    where
+        C: Erasable + Owned,
+    {
+        self.write().clear()
+    }
+}
+
+impl<C> From<C> for SyncCollection<C> {
+    fn from(collection: C) -> Self {
+        Self::new(collection)
+    }
+}
+
+/// Map behind a `RwLock`.
+#[derive(Debug, Default)]
+pub struct SyncMap<M> {
+    inner: SyncCollection<M>,
+}
+
+impl<M> SyncMap<M> {
+    /// Wraps `map`.
+    pub fn new(map: M) -> Self {
+        SyncMap {
+            inner: SyncCollection::new(map),
+        }
+    }
+
+    /// Returns the wrapped map.
+    pub fn into_inner(self) -> M {
+        self.inner.into_inner()
+    }
+
+    /// Returns the wrapped map mutably, without locking.
+    pub fn get_mut(&mut self) -> &mut M {
+        self.inner.get_mut()
+    }
+
+    /// Calls `f` with the map under a read lock.
+    pub fn with_read<R, F: FnOnce(&M) -> R>(&self, f: F) -> R {
+        self.inner.with_read(f)
+    }
+
+    /// Calls `f` with the map under a write lock.
+    pub fn with_write<R, F: FnOnce(&mut M) -> R>(&self, f: F) -> R {
+        self.inner.with_write(f)
+    }
+}
+
+impl<M: AssociatedMap + ExactSized<SizeType = usize> + Owned> SyncMap<M> {
+    /// Inserts a key-value pair, returning the old value.
+    pub fn insert(&self, key: M::KeyType, value: M::ValueType) -> Option<M::ValueType> {
+        self.inner.write().insert(key, value)
+    }
+
+    /// Removes `key`, returning its value.
+    pub fn remove(&self, key: &M::KeyType) -> Option<M::ValueType> {
+        self.inner.write().remove(key)
+    }
+
+    /// Returns true if the map contains a value for `key`.
+    pub fn contains_key(&self, key: &M::KeyType) -> bool {
+        self.inner.read().contains_key(key)
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.inner.read().len()
+    }
+
+    /// Returns true if there is no entry.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().is_empty()
+    }
+}
+
+impl<M> From<M> for SyncMap<M> {
+    fn from(map: M) -> Self {
+        Self::new(map)
+    }
+}
+
+mod impls {
+    use super::*;
+
+    impl<M: AssociatedCollectionTrait> AssociatedCollectionTrait for SyncMap<M> {
+        type KeyType = M::KeyType;
+        type ValueType = M::ValueType;
+    }
+
+    impl<M: AssociatedMap + AssociatedRetainable + ExactSized<SizeType = usize> + Owned> ConcurrentMap for SyncMap<M> {
+        fn get_cloned(&self, key: &Self::KeyType) -> Option<Self::ValueType>
+        where
+            Self::ValueType: Clone,
+        {
+            self.inner.read().get(key).cloned()
+        }
+
+        fn insert(&self, key: Self::KeyType, value: Self::ValueType) -> Option<Self::ValueType> {
+            self.insert(key, value)
+        }
+
+        fn remove(&self, key: &Self::KeyType) -> Option<Self::ValueType> {
+            self.remove(key)
+        }
+
+        fn contains_key(&self, key: &Self::KeyType) -> bool {
+            self.contains_key(key)
+        }
+
+        fn len(&self) -> usize {
+            self.len()
+        }
+
+        fn update<R, F>(&self, key: &Self::KeyType, f: F) -> Option<R>
+        where
+            F: FnOnce(&mut Self::ValueType) -> R,
+        {
+            self.inner.write().get_mut(key).map(f)
+        }
+
+        fn retain<F>(&self, f: F)
+        where
+            F: FnMut(&Self::KeyType, &mut Self::ValueType) -> bool,
+        {
+            self.inner.write().retain(f)
+        }
+
+        fn upsert<I, U>(&self, key: Self::KeyType, insert: I, update: U)
+        where
+            I: FnOnce() -> Self::ValueType,
+            U: FnOnce(&mut Self::ValueType),
+        {
+            let mut map = self.inner.write();
+            match map.get_mut(&key) {
+                Some(value) => update(value),
+                None => {
+                    map.insert(key, insert());
+                }
+            }
+        }
+
+        fn compute_if_absent<F>(&self, key: Self::KeyType, f: F) -> Self::ValueType
+        where
+            F: FnOnce(&Self::KeyType) -> Self::ValueType,
+            Self::ValueType: Clone,
+        {
+            if let Some(value) = self.inner.read().get(&key) {
+                return value.clone();
+            }
+            let mut map = self.inner.write();
+            // NOTE: another thread may have inserted `key` between releasing the read lock and acquiring this one.
+            if let Some(value) = map.get(&key) {
+                return value.clone();
+            }
+            let value = f(&key);
+            map.insert(key, value.clone());
+            value
+        }
+    }
+}
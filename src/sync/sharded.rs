@@ -0,0 +1,338 @@
+//! Concurrent hash map split into independently locked shards.
+use super::{read, write, ConcurrentMap};
+use crate::{
+    capacity::{CapacityAware, CapacityConstructible, CapacityReserve, CapacityShrink},
+    elem::AssociatedCollectionTrait,
+    exact_size::ExactSized,
+    hasher::HasherAware,
+};
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    fmt,
+    hash::{BuildHasher, Hash},
+    iter::FromIterator,
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/// Hash map made of `HashMap<K, V, S>` shards, each behind its own `RwLock`.
+///
+/// Keys are assigned to shards by their hash, so operations on keys in different shards don't contend.
+pub struct ShardedMap<K, V, S = RandomState> {
+    hash_builder: S,
+    shift: u32,
+    shards: Box<[RwLock<HashMap<K, V, S>>]>,
+}
+
+/// Default number of shards, a few times the available parallelism.
+fn default_shard_amount() -> usize {
+    (std::thread::available_parallelism().map_or(1, |n| n.get()) * 4).next_power_of_two()
+}
+
+impl<K, V> ShardedMap<K, V> {
+    /// Creates an empty map with the default number of shards.
+    pub fn new() -> Self {
+        Self::with_shards_and_hasher(default_shard_amount(), RandomState::new())
+    }
+
+    /// Creates an empty map with at least `shard_amount` shards.
+    pub fn with_shards(shard_amount: usize) -> Self {
+        Self::with_shards_and_hasher(shard_amount, RandomState::new())
+    }
+
+    /// Creates an empty map with the default number of shards, able to hold `capacity` entries in total.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S: Clone> ShardedMap<K, V, S> {
+    /// Creates an empty map with at least `shard_amount` shards, rounded up to a power of two.
+    pub fn with_shards_and_hasher(shard_amount: usize, hash_builder: S) -> Self {
+        Self::with_shards_capacity_and_hasher(shard_amount, 0, hash_builder)
+    }
+
+    /// Creates an empty map with the default number of shards, able to hold `capacity` entries in total.
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self::with_shards_capacity_and_hasher(default_shard_amount(), capacity, hash_builder)
+    }
+
+    fn with_shards_capacity_and_hasher(shard_amount: usize, capacity: usize, hash_builder: S) -> Self {
+        let shard_amount = shard_amount.max(1).next_power_of_two();
+        let per_shard = per_shard(capacity, shard_amount);
+        ShardedMap {
+            shift: u64::BITS - shard_amount.trailing_zeros(),
+            shards: (0..shard_amount)
+                .map(|_| RwLock::new(HashMap::with_capacity_and_hasher(per_shard, hash_builder.clone())))
+                .collect(),
+            hash_builder,
+        }
+    }
+}
+
+/// Capacity of each shard to hold `capacity` entries in total.
+fn per_shard(capacity: usize, shard_amount: usize) -> usize {
+    if capacity == 0 {
+        0
+    } else {
+        (capacity - 1) / shard_amount + 1
+    }
+}
+
+impl<K, V, S> ShardedMap<K, V, S> {
+    /// Returns the number of shards.
+    pub fn shard_amount(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the number of entries, locking one shard at a time.
+    ///
+    /// NOTE: entries moved in or out of other shards meanwhile are counted or not, so it's only an approximation
+    /// under concurrent modifications.
+    pub fn len(&self) -> usize {
+        self.read_shards().map(|shard| shard.len()).sum()
+    }
+
+    /// Returns true if no shard has an entry, locking one shard at a time.
+    pub fn is_empty(&self) -> bool {
+        self.read_shards().all(|shard| shard.is_empty())
+    }
+
+    /// Calls `f` with the `index`-th shard under a read lock.
+    pub fn with_shard_read<R, F: FnOnce(&HashMap<K, V, S>) -> R>(&self, index: usize, f: F) -> R {
+        f(&read(&self.shards[index]))
+    }
+
+    /// Calls `f` with the `index`-th shard under a write lock.
+    pub fn with_shard_write<R, F: FnOnce(&mut HashMap<K, V, S>) -> R>(&self, index: usize, f: F) -> R {
+        f(&mut write(&self.shards[index]))
+    }
+
+    /// Iterates over the shards, read-locking one at a time.
+    ///
+    /// NOTE: holding a guard while calling other methods of `self` on the same thread may deadlock.
+    pub fn read_shards(&self) -> impl Iterator<Item = RwLockReadGuard<'_, HashMap<K, V, S>>> {
+        self.shards.iter().map(read)
+    }
+
+    /// Iterates over the shards, write-locking one at a time.
+    ///
+    /// NOTE: holding a guard while calling other methods of `self` on the same thread may deadlock.
+    pub fn write_shards(&self) -> impl Iterator<Item = RwLockWriteGuard<'_, HashMap<K, V, S>>> {
+        self.shards.iter().map(write)
+    }
+
+    /// Iterates over the shards mutably, without locking.
+    pub fn shards_mut(&mut self) -> impl Iterator<Item = &mut HashMap<K, V, S>> {
+        self.shards
+            .iter_mut()
+            .map(|shard| shard.get_mut().unwrap_or_else(std::sync::PoisonError::into_inner))
+    }
+
+    /// Removes all entries, locking one shard at a time.
+    pub fn clear(&self) {
+        self.write_shards().for_each(|mut shard| shard.clear())
+    }
+
+    /// Merges the shards into one `HashMap<K, V, S>`.
+    pub fn into_map(self) -> HashMap<K, V, S>
+    where
+        K: Eq + Hash,
+        S: BuildHasher,
+    {
+        let mut map = HashMap::with_hasher(self.hash_builder);
+        for shard in self.shards.into_vec() {
+            map.extend(shard.into_inner().unwrap_or_else(std::sync::PoisonError::into_inner));
+        }
+        map
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> ShardedMap<K, V, S> {
+    /// Returns the index of the shard holding `key`.
+    pub fn shard_index(&self, key: &K) -> usize {
+        // NOTE: the shards hash keys with the same builder, and `HashMap` uses the lowest bits for buckets and the
+        // highest 7 bits for tags. Take the shard from the bits in between, so keys don't cluster within shards.
+        ((self.hash_builder.hash_one(key) << 7)
+            .checked_shr(self.shift)
+            .unwrap_or(0)) as usize
+    }
+
+    fn shard(&self, key: &K) -> &RwLock<HashMap<K, V, S>> {
+        &self.shards[self.shard_index(key)]
+    }
+}
+
+impl<K, V, S: Default + Clone> Default for ShardedMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_shards_and_hasher(default_shard_amount(), S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for ShardedMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        for shard in self.read_shards() {
+            map.entries(shard.iter());
+        }
+        map.finish()
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> Extend<(K, V)> for ShardedMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            let index = self.shard_index(&key);
+            self.shards[index]
+                .get_mut()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .insert(key, value);
+        }
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher + Default + Clone> FromIterator<(K, V)> for ShardedMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        map.extend(iter);
+        map
+    }
+}
+
+mod impls {
+    use super::*;
+
+    impl<K, V, S> AssociatedCollectionTrait for ShardedMap<K, V, S> {
+        type KeyType = K;
+        type ValueType = V;
+    }
+
+    impl<K: Eq + Hash, V, S: BuildHasher> ConcurrentMap for ShardedMap<K, V, S> {
+        fn get_cloned(&self, key: &K) -> Option<V>
+        where
+            V: Clone,
+        {
+            read(self.shard(key)).get(key).cloned()
+        }
+
+        fn insert(&self, key: K, value: V) -> Option<V> {
+            write(self.shard(&key)).insert(key, value)
+        }
+
+        fn remove(&self, key: &K) -> Option<V> {
+            write(self.shard(key)).remove(key)
+        }
+
+        fn contains_key(&self, key: &K) -> bool {
+            read(self.shard(key)).contains_key(key)
+        }
+
+        fn len(&self) -> usize {
+            self.len()
+        }
+
+        fn is_empty(&self) -> bool {
+            self.is_empty()
+        }
+
+        fn update<R, F>(&self, key: &K, f: F) -> Option<R>
+        where
+            F: FnOnce(&mut V) -> R,
+        {
+            write(self.shard(key)).get_mut(key).map(f)
+        }
+
+        fn retain<F>(&self, mut f: F)
+        where
+            F: FnMut(&K, &mut V) -> bool,
+        {
+            self.write_shards().for_each(|mut shard| shard.retain(&mut f))
+        }
+
+        fn upsert<I, U>(&self, key: K, insert: I, update: U)
+        where
+            I: FnOnce() -> V,
+            U: FnOnce(&mut V),
+        {
+            let mut shard = write(self.shard(&key));
+            match shard.get_mut(&key) {
+                Some(value) => update(value),
+                None => {
+                    shard.insert(key, insert());
+                }
+            }
+        }
+
+        fn compute_if_absent<F>(&self, key: K, f: F) -> V
+        where
+            F: FnOnce(&K) -> V,
+            V: Clone,
+        {
+            let lock = self.shard(&key);
+            if let Some(value) = read(lock).get(&key) {
+                return value.clone();
+            }
+            write(lock).entry(key).or_insert_with_key(f).clone()
+        }
+    }
+
+    impl<K, V, S> ExactSized for ShardedMap<K, V, S> {
+        type SizeType = usize;
+
+        fn is_empty(&self) -> bool {
+            self.is_empty()
+        }
+
+        fn len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<K, V, S> CapacityAware for ShardedMap<K, V, S> {
+        type CapacityType = usize;
+
+        fn capacity(&self) -> usize {
+            self.read_shards().map(|shard| shard.capacity()).sum()
+        }
+    }
+
+    impl<K, V> CapacityConstructible for ShardedMap<K, V> {
+        fn with_capacity(capacity: usize) -> Self {
+            Self::with_capacity(capacity)
+        }
+    }
+
+    impl<K: Eq + Hash, V, S: BuildHasher> CapacityShrink for ShardedMap<K, V, S> {
+        fn shrink_to_fit(&mut self) {
+            self.shards_mut().for_each(HashMap::shrink_to_fit)
+        }
+
+        fn shrink_to(&mut self, min_capacity: usize) {
+            let per_shard = per_shard(min_capacity, self.shard_amount());
+            self.shards_mut().for_each(|shard| shard.shrink_to(per_shard))
+        }
+    }
+
+    impl<K: Eq + Hash, V, S: BuildHasher> CapacityReserve for ShardedMap<K, V, S> {
+        /// Reserves capacity in every shard, assuming keys are evenly distributed.
+        fn reserve(&mut self, additional: usize) {
+            let per_shard = per_shard(additional, self.shard_amount());
+            self.shards_mut().for_each(|shard| shard.reserve(per_shard))
+        }
+    }
+
+    impl<K, V, S: BuildHasher + Clone> HasherAware for ShardedMap<K, V, S> {
+        type Hasher = S;
+
+        fn hasher(&self) -> &S {
+            &self.hash_builder
+        }
+
+        fn with_hasher(hasher: S) -> Self {
+            Self::with_shards_and_hasher(default_shard_amount(), hasher)
+        }
+
+        fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+            Self::with_capacity_and_hasher(capacity, hasher)
+        }
+    }
+}
@@ -0,0 +1,2 @@
+//! Utilities for verifying the guarantees of collection implementations in tests.
+pub mod alloc;
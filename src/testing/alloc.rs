@@ -0,0 +1,289 @@
+//! Allocators recording or failing allocations, and generic assertions on the capacity guarantees of
+//! allocator-aware collections.
+use crate::{
+    allocator::AllocatorAware,
+    capacity::CapacityTryReserve,
+    elem::{ExtendOwned, Owned},
+    AllocatorAwareCollection, CapacityAwareCollection,
+};
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Statistics shared by all clones of a `CountingAllocator`.
+#[derive(Debug, Default)]
+pub struct AllocStats {
+    live_bytes: AtomicUsize,
+    allocations: AtomicUsize,
+    reallocations: AtomicUsize,
+    deallocations: AtomicUsize,
+}
+
+impl AllocStats {
+    /// Returns the number of bytes currently allocated.
+    pub fn live_bytes(&self) -> usize {
+        self.live_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of successful fresh allocations.
+    pub fn allocations(&self) -> usize {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of successful grows and shrinks.
+    pub fn reallocations(&self) -> usize {
+        self.reallocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of deallocations.
+    pub fn deallocations(&self) -> usize {
+        self.deallocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of allocations and reallocations, i.e., every time memory was (re)acquired.
+    pub fn allocation_events(&self) -> usize {
+        self.allocations() + self.reallocations()
+    }
+
+    fn resized(&self, old: Layout, new: Layout) {
+        self.reallocations.fetch_add(1, Ordering::Relaxed);
+        self.live_bytes.fetch_add(new.size(), Ordering::Relaxed);
+        self.live_bytes.fetch_sub(old.size(), Ordering::Relaxed);
+    }
+}
+
+/// Allocator forwarding to `A` while recording statistics.
+///
+/// Clones share the same `AllocStats`, so a collection can be given a clone and inspected afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct CountingAllocator<A = Global> {
+    inner: A,
+    stats: Arc<AllocStats>,
+}
+
+impl CountingAllocator {
+    /// Creates a counting allocator forwarding to the global allocator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<A> CountingAllocator<A> {
+    /// Creates a counting allocator forwarding to `inner`.
+    pub fn with_inner(inner: A) -> Self {
+        CountingAllocator {
+            inner,
+            stats: Arc::default(),
+        }
+    }
+
+    /// Returns the statistics recorded so far.
+    pub fn stats(&self) -> &AllocStats {
+        &self.stats
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for CountingAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.inner.allocate(layout)?;
+        self.stats.allocations.fetch_add(1, Ordering::Relaxed);
+        self.stats.live_bytes.fetch_add(layout.size(), Ordering::Relaxed);
+        Ok(ptr)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.inner.allocate_zeroed(layout)?;
+        self.stats.allocations.fetch_add(1, Ordering::Relaxed);
+        self.stats.live_bytes.fetch_add(layout.size(), Ordering::Relaxed);
+        Ok(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.inner.deallocate(ptr, layout);
+        self.stats.deallocations.fetch_add(1, Ordering::Relaxed);
+        self.stats.live_bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn grow(&self, ptr: NonNull<u8>, old: Layout, new: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.inner.grow(ptr, old, new)?;
+        self.stats.resized(old, new);
+        Ok(ptr)
+    }
+
+    unsafe fn grow_zeroed(&self, ptr: NonNull<u8>, old: Layout, new: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.inner.grow_zeroed(ptr, old, new)?;
+        self.stats.resized(old, new);
+        Ok(ptr)
+    }
+
+    unsafe fn shrink(&self, ptr: NonNull<u8>, old: Layout, new: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.inner.shrink(ptr, old, new)?;
+        self.stats.resized(old, new);
+        Ok(ptr)
+    }
+}
+
+/// Allocator forwarding to `A`, except for the `n`-th (zero-based) allocation request, which fails.
+///
+/// Fresh allocations and grows count as requests, shrinks never fail. Clones share the same request counter.
+/// The default allocator never fails.
+#[derive(Debug, Clone)]
+pub struct FailingAllocator<A = Global> {
+    inner: A,
+    fail_at: usize,
+    requests: Arc<AtomicUsize>,
+}
+
+impl Default for FailingAllocator {
+    fn default() -> Self {
+        Self::fail_nth(usize::MAX)
+    }
+}
+
+impl FailingAllocator {
+    /// Creates an allocator failing the `n`-th allocation request, forwarding the others to the global allocator.
+    pub fn fail_nth(n: usize) -> Self {
+        Self::with_inner(Global, n)
+    }
+}
+
+impl<A> FailingAllocator<A> {
+    /// Creates an allocator failing the `n`-th allocation request, forwarding the others to `inner`.
+    pub fn with_inner(inner: A, n: usize) -> Self {
+        FailingAllocator {
+            inner,
+            fail_at: n,
+            requests: Arc::default(),
+        }
+    }
+
+    /// Returns the number of allocation requests so far, including the failed one.
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    fn request(&self) -> Result<(), AllocError> {
+        if self.requests.fetch_add(1, Ordering::Relaxed) == self.fail_at {
+            Err(AllocError)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for FailingAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.request()?;
+        self.inner.allocate(layout)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.request()?;
+        self.inner.allocate_zeroed(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.inner.deallocate(ptr, layout)
+    }
+
+    unsafe fn grow(&self, ptr: NonNull<u8>, old: Layout, new: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.request()?;
+        self.inner.grow(ptr, old, new)
+    }
+
+    unsafe fn grow_zeroed(&self, ptr: NonNull<u8>, old: Layout, new: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.request()?;
+        self.inner.grow_zeroed(ptr, old, new)
+    }
+
+    unsafe fn shrink(&self, ptr: NonNull<u8>, old: Layout, new: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner.shrink(ptr, old, new)
+    }
+}
+
+/// Asserts that after `reserve(additional)`, inserting `additional` elements doesn't allocate.
+///
+/// `elem(i)` creates the `i`-th element. The collection must not leak once dropped.
+pub fn assert_reserve_without_realloc<C, F>(additional: usize, mut elem: F)
+where
+    C: AllocatorAwareCollection<Allocator = CountingAllocator> + CapacityAwareCollection<CapacityType = usize> + Owned,
+    F: FnMut(usize) -> C::ElemType,
+{
+    let alloc = CountingAllocator::new();
+    let mut c = C::new_in(alloc.clone());
+    c.reserve(additional);
+    assert!(c.capacity() >= additional, "reserve({}) left capacity {}", additional, c.capacity());
+
+    let before = alloc.stats().allocation_events();
+    for i in 0..additional {
+        ExtendOwned::extend_one(&mut c, elem(i));
+    }
+    assert_eq!(alloc.stats().allocation_events(), before, "pushes after reserve({}) reallocated", additional);
+
+    drop(c);
+    assert_eq!(alloc.stats().live_bytes(), 0, "collection leaked memory");
+}
+
+/// Asserts that `shrink_to(min_capacity)` keeps at least `max(len, min_capacity)` capacity without using more memory.
+pub fn assert_shrink_to<C, F>(len: usize, min_capacity: usize, mut elem: F)
+where
+    C: AllocatorAwareCollection<Allocator = CountingAllocator> + CapacityAwareCollection<CapacityType = usize> + Owned,
+    F: FnMut(usize) -> C::ElemType,
+{
+    let alloc = CountingAllocator::new();
+    let mut c = C::new_in(alloc.clone());
+    ExtendOwned::extend(&mut c, (0..len).map(&mut elem));
+    c.reserve(len + min_capacity + 1);
+
+    let before = alloc.stats().live_bytes();
+    c.shrink_to(min_capacity);
+    assert!(c.capacity() >= len.max(min_capacity), "shrink_to({}) dropped below the bound", min_capacity);
+    assert!(alloc.stats().live_bytes() <= before, "shrink_to({}) used more memory", min_capacity);
+
+    drop(c);
+    assert_eq!(alloc.stats().live_bytes(), 0, "collection leaked memory");
+}
+
+/// Asserts that `try_reserve` reports allocation failures as errors instead of aborting, and leaves the collection
+/// usable.
+///
+/// NOTE: the element type must not be zero-sized, since those never allocate.
+pub fn assert_try_reserve_fails<C>(additional: usize)
+where
+    C: AllocatorAwareCollection<Allocator = FailingAllocator>
+        + CapacityAwareCollection<CapacityType = usize>
+        + CapacityTryReserve,
+{
+    assert!(additional > 0, "reserving nothing never allocates");
+    let mut c = C::new_in(FailingAllocator::fail_nth(0));
+    let capacity = c.capacity();
+    assert!(c.try_reserve(additional).is_err(), "try_reserve({}) ignored the allocation failure", additional);
+    assert_eq!(c.capacity(), capacity, "failed try_reserve changed the capacity");
+    assert!(c.try_reserve(additional).is_ok(), "try_reserve({}) failed after the injected failure", additional);
+    assert!(c.capacity() >= additional);
+    assert!(c.is_empty());
+}
+
+/// Asserts that `try_reserve` reports capacity overflows as errors without touching the allocator.
+pub fn assert_try_reserve_overflow<C>()
+where
+    C: AllocatorAwareCollection<Allocator = CountingAllocator>
+        + CapacityAwareCollection<CapacityType = usize>
+        + CapacityTryReserve,
+{
+    let alloc = CountingAllocator::new();
+    let mut c = C::new_in(alloc.clone());
+    assert!(c.try_reserve(usize::MAX).is_err(), "try_reserve(usize::MAX) succeeded");
+    assert_eq!(alloc.stats().allocation_events(), 0);
+    assert!(c.is_empty());
+}
+
+/// Returns the statistics of the allocator of `c`, e.g., of a collection created elsewhere.
+pub fn stats_of<C: AllocatorAware<Allocator = CountingAllocator>>(c: &C) -> &AllocStats {
+    c.allocator().stats()
+}
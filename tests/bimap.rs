@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+use std_collection_traits::{
+    associated::AssociatedMap,
+    bimap::{BiMap, InsertPolicy, Overwritten},
+    exact_size::ExactSized,
+    iter::AssociatedIterable,
+};
+
+fn lookup<L, R, ML, MR>(m: &BiMap<L, R, ML, MR>, keys: &[L]) -> String
+where
+    R: Copy + Into<char>,
+    ML: AssociatedMap<KeyType = L, ValueType = R>,
+{
+    keys.iter().filter_map(|k| m.get(k)).map(|&v| v.into()).collect()
+}
+
+fn keys<C: AssociatedIterable<KeyType = char, ValueType = u32>>(c: &C) -> Vec<char> {
+    let mut keys: Vec<_> = c.iter().map(|(k, _)| *k).collect();
+    keys.sort_unstable();
+    keys
+}
+
+#[test]
+fn test_policies() {
+    let mut m: BiMap<u32, char> = BiMap::new();
+    assert_eq!(m.insert_overwrite(1, 'a'), Overwritten::Neither);
+    assert_eq!(m.insert_overwrite(2, 'b'), Overwritten::Neither);
+    assert_eq!(m.insert_no_overwrite(1, 'c'), Err((1, 'c')));
+    assert_eq!(m.insert_no_overwrite(3, 'b'), Err((3, 'b')));
+    assert_eq!(
+        m.insert_with(3, 'b', InsertPolicy::RejectLeft),
+        Ok(Overwritten::Right(2, 'b'))
+    );
+    assert_eq!(
+        m.insert_with(3, 'c', InsertPolicy::RejectRight),
+        Ok(Overwritten::Left(3, 'b'))
+    );
+    assert_eq!(m.insert_overwrite(3, 'c'), Overwritten::Pair(3, 'c'));
+    assert_eq!(m.insert_overwrite(1, 'c'), Overwritten::Both((1, 'a'), (3, 'c')));
+    assert_eq!(m.len(), 1);
+    assert_eq!(m.inverse().get(&'c'), Some(&1));
+
+    assert_eq!(m.insert(2, 'c'), None);
+    assert_eq!(m.insert(2, 'd'), Some('c'));
+    assert_eq!(m.inverse().get(&'c'), None);
+    assert_eq!(m.get_key_value(&2), Some((&2, &'d')));
+    assert_eq!(m.remove_entry(&2), Some((2, 'd')));
+    assert_eq!(m.inverse().get(&'d'), None);
+}
+
+#[test]
+fn test_inverse() {
+    let mut m: BiMap<u32, char, BTreeMap<u32, char>, BTreeMap<char, u32>> =
+        (0..5).map(|i| (i, (b'a' + i as u8) as char)).collect();
+    assert_eq!(lookup(&m, &[4, 0, 7]), "ea");
+    assert_eq!(keys(m.inverse()), vec!['a', 'b', 'c', 'd', 'e']);
+
+    let inverse = m.inverse_mut();
+    assert_eq!(inverse.remove(&'b'), Some(1));
+    assert_eq!(inverse.insert('z', 0), None);
+    assert_eq!(lookup(inverse.inverse(), &[0, 1, 2]), "zc");
+    assert_eq!(m.right_map().len(), m.left_map().len());
+
+    let mut inverted = m.clone().into_inverse();
+    assert_eq!(inverted.get(&'z'), Some(&0));
+    inverted.clear();
+    assert!(inverted.is_empty() && inverted.inverse().is_empty());
+    assert_eq!(m.len(), 4);
+}
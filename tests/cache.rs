@@ -0,0 +1,89 @@
+use std::sync::{Arc, Mutex};
+use std_collection_traits::{
+    associated::AssociatedMap,
+    cache::{Cache, LfuCache, LruCache},
+    capacity::CapacityAware,
+    iter::AssociatedIterable,
+};
+
+fn keys<C: AssociatedIterable<KeyType = u32>>(c: &C) -> Vec<u32> {
+    c.iter().map(|(k, _)| *k).collect()
+}
+
+fn fill<C: Cache<KeyType = u32, ValueType = u32> + CapacityAware<CapacityType = usize>>(c: &mut C) {
+    for i in 0..c.capacity() as u32 {
+        c.put(i, i * 10);
+    }
+}
+
+fn evicted(c: &mut impl Cache<KeyType = u32, ValueType = u32>) -> Arc<Mutex<Vec<u32>>> {
+    let evicted = Arc::new(Mutex::new(Vec::new()));
+    let log = evicted.clone();
+    c.set_on_evict(move |k, _| log.lock().unwrap().push(k));
+    evicted
+}
+
+#[test]
+fn test_lru() {
+    let mut c = LruCache::new(3);
+    let log = evicted(&mut c);
+    fill(&mut c);
+    assert_eq!(keys(&c), vec![2, 1, 0]);
+
+    assert_eq!(c.peek(&0), Some(&0));
+    assert_eq!(c.peek_victim(), Some((&0, &0)));
+    assert_eq!(Cache::get(&mut c, &0), Some(&0));
+    assert_eq!(keys(&c), vec![0, 2, 1]);
+
+    assert_eq!(c.put(3, 30), None);
+    assert_eq!(c.insert(2, 21), Some(20));
+    assert_eq!(keys(&c), vec![2, 3, 0]);
+    assert_eq!(*log.lock().unwrap(), vec![1]);
+
+    *c.get_mut(&0).unwrap() += 1;
+    assert_eq!(c.remove(&3), Some(30));
+    c.set_capacity(1);
+    assert_eq!((c.len(), c.capacity()), (1, 1));
+    assert_eq!(*log.lock().unwrap(), vec![1, 0]);
+    assert_eq!(c.into_iter().collect::<Vec<_>>(), vec![(2, 21)]);
+}
+
+#[test]
+fn test_lfu() {
+    let mut c = LfuCache::new(3);
+    let log = evicted(&mut c);
+    fill(&mut c);
+    assert_eq!(keys(&c), vec![2, 1, 0]);
+
+    Cache::get(&mut c, &0);
+    Cache::get(&mut c, &0);
+    Cache::get(&mut c, &1);
+    assert_eq!(
+        (c.frequency(&0), c.frequency(&1), c.frequency(&2)),
+        (Some(3), Some(2), Some(1))
+    );
+    assert_eq!(keys(&c), vec![0, 1, 2]);
+    assert_eq!(c.peek(&2), Some(&20));
+    assert_eq!(c.peek_victim(), Some((&2, &20)));
+
+    c.put(3, 30);
+    c.put(4, 40);
+    assert_eq!(*log.lock().unwrap(), vec![2, 3]);
+    c.put(1, 11);
+    assert_eq!(keys(&c), vec![1, 0, 4]);
+    assert_eq!(c.frequency(&1), Some(3));
+
+    assert_eq!(c.pop_victim(), Some((4, 40)));
+    assert_eq!(c.remove_entry(&0), Some((0, 0)));
+    c.extend(vec![(5, 50), (6, 60)]);
+    assert_eq!(c.into_iter().collect::<Vec<_>>(), vec![(1, 11), (6, 60), (5, 50)]);
+}
+
+#[test]
+fn test_zero_capacity() {
+    let mut c = LruCache::new(0);
+    let log = evicted(&mut c);
+    assert_eq!(c.put(1, 1), None);
+    assert!(c.is_empty() && !c.contains_key(&1));
+    assert_eq!(*log.lock().unwrap(), vec![1]);
+}
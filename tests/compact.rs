@@ -0,0 +1,117 @@
+use std::{cell::Cell, iter::FromIterator, mem::size_of, rc::Rc};
+use std_collection_traits::{
+    capacity::{CapacityAware, CapacityTryReserve},
+    compact::{CompactReserveError, Deque32, Vec32},
+    elem::Owned,
+    iter::DrainRange,
+    sequential::{DoubleEnded, RandomAccess, Sequential},
+};
+
+struct DropCounter(Rc<Cell<usize>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn test_vec32() {
+    assert_eq!(size_of::<Vec32<u8>>(), 16);
+    assert_eq!(size_of::<Deque32<u8>>(), 24);
+
+    let mut v = Vec32::from_iter(0..10u32);
+    assert_eq!(v.len(), 10);
+    assert_eq!(v.drain(2..5).collect::<Vec<_>>(), vec![2, 3, 4]);
+    assert_eq!(v.as_slice(), &[0, 1, 5, 6, 7, 8, 9]);
+    v.insert(1, 42);
+    assert_eq!(v.remove(0), 0);
+    assert_eq!(v.swap_remove(0), 42);
+    v.retain(|x| x % 2 == 1);
+    assert_eq!(v.as_slice(), &[9, 1, 5, 7]);
+    let tail = v.split_off(2);
+    assert_eq!(tail.as_slice(), &[5, 7]);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 2);
+
+    let mut z = Vec32::new();
+    z.resize(100, ());
+    assert_eq!(z.capacity(), u32::MAX);
+    assert_eq!(z.into_iter().count(), 100);
+}
+
+#[test]
+fn test_deque32() {
+    let mut d = Deque32::with_capacity(4);
+    d.push_back(1);
+    d.push_back(2);
+    d.push_front(0);
+    d.push_front(-1);
+    assert_eq!(d.capacity(), 4);
+    assert_eq!(d.as_slices(), (&[-1, 0][..], &[1, 2][..]));
+
+    // Growing a wrapped ring keeps the order.
+    d.push_back(3);
+    assert_eq!(d.iter().copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2, 3]);
+    assert_eq!(d.iter().rev().copied().collect::<Vec<_>>(), vec![3, 2, 1, 0, -1]);
+    assert_eq!(d.range(1..4).copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+    d.pop_front();
+    d.push_front(-2);
+    d.range_mut(..2).for_each(|x| *x *= 10);
+    assert_eq!(d.drain_range(1..3).collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(Vec::from_iter(d.clone()), vec![-20, 2, 3]);
+
+    d.rotate_left(1);
+    assert_eq!(d.make_contiguous(), &[2, 3, -20]);
+    d.insert(0, 1);
+    assert_eq!(RandomAccess::get(&d, 3), Some(&-20));
+    assert_eq!(d.swap_remove_back(0), Some(1));
+    assert_eq!(d.partition_point(|x| *x < 0), 1);
+    assert_eq!(Vec32::from(d).as_slice(), &[-20, 2, 3]);
+}
+
+#[test]
+fn test_compact_traits() {
+    fn exercise<C: Sequential<SizeType = u32> + DoubleEnded + DrainRange + Owned>(c: &mut C)
+    where
+        C::ElemType: From<u8>,
+    {
+        for i in 0..8u8 {
+            c.push_back(i.into());
+            c.push_front(i.into());
+        }
+        assert_eq!(c.len(), 16);
+        assert_eq!(c.drain_range(4..12).count(), 8);
+        assert_eq!(c.split_off(4).len(), 4);
+    }
+    exercise(&mut Deque32::<u32>::new());
+
+    let mut v: Vec32<u32> = Vec32::new();
+    assert_eq!(v.try_reserve_exact(1), Ok(()));
+    v.push(1);
+    assert_eq!(v.try_reserve(u32::MAX), Err(CompactReserveError::CapacityOverflow));
+    let mut d = Deque32::from(v);
+    assert_eq!(
+        CapacityTryReserve::try_reserve(&mut d, u32::MAX),
+        Err(CompactReserveError::CapacityOverflow)
+    );
+    assert_eq!(CapacityAware::capacity(&d), d.capacity());
+}
+
+#[test]
+fn test_compact_drop() {
+    let drops = Rc::new(Cell::new(0));
+    let mut d = Deque32::new();
+    for _ in 0..6 {
+        d.push_front(DropCounter(drops.clone()));
+    }
+    drop(d.pop_back());
+    assert_eq!(drops.get(), 1);
+    drop(d.drain(1..3).next());
+    assert_eq!(drops.get(), 3);
+    let mut it = Vec32::from(d).into_iter();
+    drop(it.next());
+    drop(it);
+    assert_eq!(drops.get(), 6);
+}
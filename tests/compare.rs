@@ -0,0 +1,53 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
+    hash::{Hash, Hasher},
+};
+use std_collection_traits::compare::*;
+
+fn hash_of<T: Hash>(v: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    v.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_sequence() {
+    let v = vec![1, 2, 3];
+    let d: VecDeque<_> = v.iter().copied().collect();
+    let l: LinkedList<_> = vec![1, 2, 4].into_iter().collect();
+
+    assert!(v.collection_eq(&d));
+    assert!(v.collection_ne(&l));
+    assert_eq!(v.sequence_cmp(&l), std::cmp::Ordering::Less);
+    assert_eq!(d.sequence_partial_cmp(&v), Some(std::cmp::Ordering::Equal));
+
+    assert!(BySequence(&v[..]) == BySequence(d.clone()));
+    assert!(BySequence(l.clone()) > BySequence(d.clone()));
+    assert_eq!(hash_of(&BySequence(v)), hash_of(&BySequence(d)));
+}
+
+#[test]
+fn test_set() {
+    let h: HashSet<u32> = (0..100).collect();
+    let b: BTreeSet<u32> = (0..100).rev().collect();
+    let c: BTreeSet<u32> = (1..101).collect();
+
+    assert!(h.set_eq(&b));
+    assert!(!h.set_eq(&c));
+    assert!(BySet(h.clone()) == BySet(b.clone()));
+    assert_eq!(hash_of(&BySet(h)), hash_of(&BySet(b)));
+}
+
+#[test]
+fn test_map() {
+    let h: HashMap<u32, String> = (0..100).map(|i| (i, i.to_string())).collect();
+    let b: BTreeMap<u32, String> = (0..100).map(|i| (i, i.to_string())).collect();
+    let mut c = b.clone();
+    c.insert(42, String::new());
+
+    assert!(h.map_eq(&b));
+    assert!(!h.map_eq(&c));
+    assert!(ByMap(b.clone()) != ByMap(c));
+    assert!(ByMap(h.clone()) == ByMap(b.clone()));
+    assert_eq!(hash_of(&ByMap(h)), hash_of(&ByMap(b)));
+}
@@ -0,0 +1,43 @@
+use std::collections::{BTreeMap, HashSet};
+use std_collection_traits::{
+    associated::AssociatedMap, counter::Counter, exact_size::ExactSized, iter::AssociatedIterable,
+    misc::AssociatedContains,
+};
+
+fn positive<M: AssociatedIterable<KeyType = char, ValueType = i64>>(m: &M) -> usize {
+    m.iter().filter(|(_, count)| **count > 0).count()
+}
+
+#[test]
+fn test_counter() {
+    let mut c: Counter<char> = "abracadabra".chars().collect();
+    assert_eq!((c.count(&'a'), c.count(&'z'), c.total(), c.len()), (5, 0, 11, 5));
+    assert_eq!(c.most_common(1), vec![(&'a', 5)]);
+    assert_eq!(c.most_common(2)[1].1, 2);
+    assert_eq!(c.most_common(0), vec![]);
+    assert_eq!(c.most_common(10).len(), 5);
+
+    c.subtract("aaaaaaz".chars());
+    assert_eq!((c.count(&'a'), c.count(&'z')), (-1, -1));
+    assert_eq!(positive(&c), 4);
+    assert_eq!(AssociatedMap::insert(&mut c, 'z', 3), Some(-1));
+
+    let set: HashSet<char> = "xyz".chars().collect();
+    let other = Counter::from_iterable(&set);
+    let sum = c.clone() + other.clone();
+    assert_eq!((sum.count(&'a'), sum.count(&'z'), sum.count(&'x')), (0, 4, 1));
+    assert!(!sum.contains_key(&'a'));
+    let diff = c - other;
+    assert_eq!((diff.count(&'z'), diff.count(&'y'), diff.len()), (2, 0, 5));
+}
+
+#[test]
+fn test_ordered_counter() {
+    let mut c: Counter<u32, BTreeMap<u32, i64>> = Counter::new();
+    c.update(vec![5, 3, 5, 1, 3, 7]);
+    c.extend(vec![(9, 2), (1, -1)]);
+    assert_eq!(c.most_common(3), vec![(&3, 2), (&5, 2), (&9, 2)]);
+    assert_eq!(c.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![1, 3, 5, 7, 9]);
+    assert_eq!(c.total(), 7);
+    assert_eq!(c.into_map().get(&1), Some(&0));
+}
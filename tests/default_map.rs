@@ -0,0 +1,50 @@
+use std::collections::{BTreeMap, HashMap};
+use std_collection_traits::{
+    associated::AssociatedMap,
+    default_map::DefaultMap,
+    exact_size::ExactSized,
+    iter::{AssociatedIterable, AssociatedIterableMut},
+};
+
+fn lengths<M: AssociatedIterable<KeyType = usize, ValueType = Vec<&'static str>>>(m: &M) -> Vec<(usize, usize)> {
+    let mut lengths: Vec<_> = m.iter().map(|(k, v)| (*k, v.len())).collect();
+    lengths.sort_unstable();
+    lengths
+}
+
+#[test]
+fn test_default_values() {
+    let mut groups = DefaultMap::with_default(HashMap::<usize, Vec<_>>::new());
+    for word in ["a", "bb", "cc", "d", "eee"].iter() {
+        groups.get_or_default_mut(word.len()).push(*word);
+    }
+    assert_eq!(lengths(&groups), vec![(1, 2), (2, 2), (3, 1)]);
+    assert_eq!(groups[&2], vec!["bb", "cc"]);
+    assert!(groups.get(&4).is_none());
+
+    groups[&4].push("ffff");
+    groups.iter_mut().for_each(|(_, v)| v.truncate(1));
+    assert_eq!(lengths(&groups), vec![(1, 1), (2, 1), (3, 1), (4, 1)]);
+    assert_eq!(groups.into_map().remove(&4), Some(vec!["ffff"]));
+}
+
+#[test]
+fn test_key_factory() {
+    let mut squares = DefaultMap::new(BTreeMap::new(), |k: &u64| k * k);
+    *squares.get_or_default_mut(3) += 1;
+    squares[&5] += 0;
+    assert_eq!(squares.insert(5, 0), Some(25));
+    assert_eq!(squares.len(), 2);
+    assert_eq!(
+        squares.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+        vec![(3, 10), (5, 0)]
+    );
+    assert_eq!((squares.factory())(&7), 49);
+}
+
+#[test]
+#[should_panic(expected = "key not found")]
+fn test_index_absent() {
+    let squares = DefaultMap::new(BTreeMap::new(), |k: &u64| k * k);
+    let _ = squares[&2];
+}
@@ -0,0 +1,60 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std_collection_traits::algorithms::diff::*;
+
+fn snapshots() -> (BTreeMap<&'static str, u32>, BTreeMap<&'static str, u32>) {
+    let old = vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+    let new = vec![("b", 2), ("c", 4), ("d", 5)].into_iter().collect();
+    (old, new)
+}
+
+#[test]
+fn test_map_diff() {
+    let (old, new) = snapshots();
+    let diff = ordered_map_diff(&old, &new);
+    assert_eq!(diff.added, vec![("d", 5)]);
+    assert_eq!(diff.removed, vec![("a", 1)]);
+    assert_eq!(diff.modified, vec![("c", 3, 4)]);
+    assert_eq!(diff.len(), 3);
+
+    let hashed_old: HashMap<_, _> = old.clone().into_iter().collect();
+    let hashed_new: HashMap<_, _> = new.clone().into_iter().collect();
+    let mut unordered = map_diff(&hashed_old, &hashed_new);
+    unordered.added.sort_unstable();
+    unordered.removed.sort_unstable();
+    unordered.modified.sort_unstable();
+    assert_eq!(unordered, diff);
+
+    let mut patched = hashed_old.clone();
+    diff.apply_diff(&mut patched);
+    assert_eq!(patched, hashed_new);
+
+    let mut reverted = new.clone();
+    diff.invert().apply_diff(&mut reverted);
+    assert_eq!(reverted, old);
+
+    assert!(map_diff(&old, &old).is_empty());
+}
+
+#[test]
+fn test_set_diff() {
+    let old: BTreeSet<u32> = vec![1, 2, 3].into_iter().collect();
+    let new: BTreeSet<u32> = vec![2, 3, 4, 5].into_iter().collect();
+
+    let diff = ordered_set_diff(&old, &new);
+    assert_eq!(diff.added, vec![4, 5]);
+    assert_eq!(diff.removed, vec![1]);
+
+    let hashed_old: HashSet<u32> = old.iter().copied().collect();
+    let hashed_new: HashSet<u32> = new.iter().copied().collect();
+    let mut unordered = set_diff(&hashed_old, &hashed_new);
+    unordered.added.sort_unstable();
+    assert_eq!(unordered, diff);
+
+    let mut patched = hashed_old;
+    diff.apply_diff(&mut patched);
+    assert_eq!(patched, hashed_new);
+
+    let mut reverted = new;
+    diff.invert().apply_diff(&mut reverted);
+    assert_eq!(reverted, old);
+}
@@ -0,0 +1,50 @@
+#![feature(allocator_api)]
+use std::collections::{HashMap, HashSet, VecDeque};
+use std_collection_traits::{
+    allocator::AllocatorConstructible,
+    fallible::{AssociatedTryInsert, TryExtend, TryInsert, TryPush},
+    testing::alloc::FailingAllocator,
+};
+
+#[test]
+fn test_try_push() {
+    let mut v = vec![1];
+    assert!(v.try_push_back(2).is_ok());
+    let mut d: VecDeque<_> = v.into_iter().collect();
+    assert!(d.try_push_front(0).is_ok());
+    assert!(d.iter().eq(&[0, 1, 2]));
+
+    let mut v: Vec<String, _> = AllocatorConstructible::new_in(FailingAllocator::fail_nth(0));
+    let err = v.try_push_back("a".to_string()).unwrap_err();
+    assert_eq!(err.into_elem(), "a");
+    assert!(v.try_push_back("b".to_string()).is_ok());
+    assert_eq!(v, ["b"]);
+
+    let mut d: VecDeque<u8, _> = AllocatorConstructible::new_in(FailingAllocator::fail_nth(0));
+    assert_eq!(d.try_push_front(1).unwrap_err().elem, 1);
+}
+
+#[test]
+fn test_try_insert() {
+    let mut s = HashSet::new();
+    assert_eq!(s.try_insert_value(1).ok(), Some(true));
+    assert_eq!(s.try_insert_value(1).ok(), Some(false));
+
+    let mut m = HashMap::new();
+    assert_eq!(m.try_insert_pair(1, "a").ok(), Some(None));
+    assert_eq!(m.try_insert_pair(1, "b").ok(), Some(Some("a")));
+}
+
+#[test]
+fn test_try_extend() {
+    let mut v: Vec<u32, _> = AllocatorConstructible::new_in(FailingAllocator::fail_nth(1));
+    let rest = v.try_extend(0..100).unwrap_err().into_elem();
+    let (head, rest): (Vec<_>, Vec<_>) = (v.to_vec(), rest.collect());
+    assert_eq!(head.len() + rest.len(), 100);
+    assert_eq!(rest[0] as usize, head.len());
+    assert!(head.iter().chain(rest.iter()).copied().eq(0..100));
+
+    let mut s = HashSet::new();
+    assert!(s.try_extend(vec![1, 2, 2]).is_ok());
+    assert_eq!(s.len(), 2);
+}
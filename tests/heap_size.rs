@@ -0,0 +1,48 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, LinkedList, VecDeque};
+use std_collection_traits::heap_size::HeapSize;
+
+#[test]
+fn test_heap_size() {
+    assert_eq!(1u64.heap_size_of_children(), 0);
+    assert_eq!((1u8, 'a').total_size(), std::mem::size_of::<(u8, char)>());
+
+    let s = String::with_capacity(10);
+    assert_eq!(s.heap_size_of_children(), 10);
+    let b: Box<str> = "abc".into();
+    assert_eq!(b.heap_size_of_children(), 3);
+    let b = Box::new(String::with_capacity(4));
+    assert_eq!(b.heap_size_of_children(), std::mem::size_of::<String>() + 4);
+
+    let mut v: Vec<Vec<u64>> = Vec::with_capacity(4);
+    v.push(Vec::with_capacity(8));
+    v.push(Vec::new());
+    assert_eq!(v.heap_size_of_children(), 4 * std::mem::size_of::<Vec<u64>>() + 8 * 8);
+    assert_eq!(v.total_size(), std::mem::size_of::<Vec<Vec<u64>>>() + v.heap_size_of_children());
+
+    let d: VecDeque<(u32, Option<String>)> = vec![(1, Some(String::with_capacity(5))), (2, None)].into_iter().collect();
+    assert!(d.heap_size_of_children() >= d.capacity() * std::mem::size_of::<(u32, Option<String>)>() + 5);
+
+    let l: LinkedList<u8> = vec![1, 2, 3].into_iter().collect();
+    assert!(l.heap_size_of_children() >= 3 * 3 * std::mem::size_of::<usize>());
+}
+
+#[test]
+fn test_heap_size_associated() {
+    let mut m: HashMap<String, Vec<u64>> = HashMap::new();
+    assert_eq!(m.heap_size_of_children(), 0);
+    m.insert(String::with_capacity(3), Vec::with_capacity(2));
+    let slots = m.capacity() * std::mem::size_of::<(String, Vec<u64>)>();
+    assert!(m.heap_size_of_children() >= slots + m.capacity() + 3 + 16);
+
+    let mut s: BTreeSet<u64> = BTreeSet::new();
+    assert_eq!(s.heap_size_of_children(), 0);
+    s.insert(1);
+    let one = s.heap_size_of_children();
+    assert!(one >= 11 * 8);
+    s.extend(0..1000);
+    assert!(s.heap_size_of_children() >= 1000 * 8);
+
+    let mut t: BTreeMap<u8, String> = BTreeMap::new();
+    t.insert(1, String::with_capacity(100));
+    assert!(t.heap_size_of_children() >= 100 + 11 * std::mem::size_of::<String>());
+}
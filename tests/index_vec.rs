@@ -0,0 +1,56 @@
+use std_collection_traits::{
+    define_index,
+    exact_size::ExactSized,
+    index_vec::{Idx, TypedIndexVec},
+    iter::{DrainRange, Range},
+    sequential::{RandomAccess, Sequential},
+    CapacityAwareCollection, RandomAccessCollection, SequentialCollection,
+};
+
+define_index!(
+    /// Entity ID
+    pub struct EntityId;
+);
+
+define_index!(struct OtherId;);
+
+#[test]
+fn test_index_vec() {
+    let mut v: TypedIndexVec<EntityId, &str> = TypedIndexVec::new();
+    let a = v.push("a");
+    let b = v.push("b");
+    assert_eq!(a, EntityId::new(0));
+    assert_eq!(v[b], "b");
+    assert_eq!(v.get(EntityId::new(2)), None);
+    assert_eq!(v.len(), EntityId::new(2));
+    assert_eq!(v.indices().collect::<Vec<_>>(), vec![a, b]);
+    assert_eq!(v.iter_enumerated().map(|(i, _)| i.index()).collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(OtherId::new(1).index(), 1);
+}
+
+#[test]
+fn test_index_vec_traits() {
+    fn random_access<C: RandomAccessCollection + SequentialCollection + CapacityAwareCollection>() {}
+    random_access::<TypedIndexVec<EntityId, usize>>();
+    random_access::<TypedIndexVec<u32, usize>>();
+
+    fn swap_ends<C: RandomAccess + DrainRange + Range>(c: &mut C, last: C::SizeType)
+    where
+        C::SizeType: Idx,
+    {
+        let first = Idx::new(0);
+        c.swap(first, last);
+        assert_eq!(c.range(first..=last).count(), last.index() + 1);
+    }
+
+    let mut v: TypedIndexVec<EntityId, usize> = (0..5).collect();
+    swap_ends(&mut v, EntityId::new(4));
+    assert_eq!(v.raw(), &vec![4, 1, 2, 3, 0]);
+
+    assert_eq!(v.drain_range(EntityId::new(1)..EntityId::new(3)).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(Sequential::remove(&mut v, EntityId::new(0)), Some(4));
+    v.swap(EntityId::new(0), EntityId::new(1));
+    assert_eq!(v.partition_point(|x| *x < 3), EntityId::new(1));
+    let tail = Sequential::split_off(&mut v, EntityId::new(1));
+    assert_eq!(tail.into_raw(), vec![3]);
+}
@@ -0,0 +1,67 @@
+use std::collections::{BTreeMap, HashMap};
+use std_collection_traits::algorithms::join::*;
+
+fn scores() -> BTreeMap<u32, &'static str> {
+    vec![(1, "a"), (2, "b"), (4, "d")].into_iter().collect()
+}
+
+fn ranks() -> BTreeMap<u32, i64> {
+    vec![(2, -2), (3, -3), (4, -4)].into_iter().collect()
+}
+
+#[test]
+fn test_probe_join() {
+    let left: HashMap<_, _> = scores().into_iter().collect();
+    let right = ranks();
+
+    let mut inner = inner_join(&left, &right).map(|(k, v, w)| (*k, *v, *w)).collect::<Vec<_>>();
+    inner.sort_unstable();
+    assert_eq!(inner, vec![(2, "b", -2), (4, "d", -4)]);
+
+    let mut lj = left_join(&left, &right).map(|(k, v, w)| (*k, *v, w.copied())).collect::<Vec<_>>();
+    lj.sort_unstable();
+    assert_eq!(lj, vec![(1, "a", None), (2, "b", Some(-2)), (4, "d", Some(-4))]);
+
+    let rj = right_join(&left, &right).map(|(k, v, w)| (*k, v.copied(), *w)).collect::<Vec<_>>();
+    assert_eq!(rj, vec![(2, Some("b"), -2), (3, None, -3), (4, Some("d"), -4)]);
+
+    let mut outer = outer_join(&left, &right)
+        .map(|(k, e)| (*k, e.left().copied(), e.right().copied()))
+        .collect::<Vec<_>>();
+    outer.sort_unstable();
+    assert_eq!(outer, vec![
+        (1, Some("a"), None),
+        (2, Some("b"), Some(-2)),
+        (3, None, Some(-3)),
+        (4, Some("d"), Some(-4))
+    ]);
+
+    let anti = anti_join(&left, &right).map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+    assert_eq!(anti, vec![(1, "a")]);
+}
+
+#[test]
+fn test_merge_join() {
+    let left = scores();
+    let right = ranks();
+
+    let inner = merge_inner_join(&left, &right).map(|(k, v, w)| (*k, *v, *w)).collect::<Vec<_>>();
+    assert_eq!(inner, vec![(2, "b", -2), (4, "d", -4)]);
+
+    let lj = merge_left_join(&left, &right).map(|(k, v, w)| (*k, *v, w.copied())).collect::<Vec<_>>();
+    assert_eq!(lj, vec![(1, "a", None), (2, "b", Some(-2)), (4, "d", Some(-4))]);
+
+    let rj = merge_right_join(&left, &right).map(|(k, v, w)| (*k, v.copied(), *w)).collect::<Vec<_>>();
+    assert_eq!(rj, vec![(2, Some("b"), -2), (3, None, -3), (4, Some("d"), -4)]);
+
+    let outer = merge_outer_join(&left, &right).map(|(k, e)| (*k, e)).collect::<Vec<_>>();
+    assert_eq!(outer, vec![
+        (1, EitherOrBoth::Left(&"a")),
+        (2, EitherOrBoth::Both(&"b", &-2)),
+        (3, EitherOrBoth::Right(&-3)),
+        (4, EitherOrBoth::Both(&"d", &-4))
+    ]);
+
+    let anti = merge_anti_join(&left, &right).map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+    assert_eq!(anti, vec![(1, "a")]);
+}
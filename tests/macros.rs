@@ -0,0 +1,51 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std_collection_traits::{collection, elem::Owned, map, Map, SequentialCollection};
+
+#[test]
+fn test_collection_literal() {
+    let v: Vec<u32> = collection![1, 2, 3];
+    assert_eq!(v, vec![1, 2, 3]);
+    assert!(v.capacity() >= 3);
+
+    let d: VecDeque<u32> = collection![1, 2, 3,];
+    assert_eq!(d, vec![1, 2, 3]);
+
+    let l: LinkedList<u32> = collection![1, 2, 3];
+    assert_eq!(l.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    let b: BTreeSet<u32> = collection![3, 1, 2, 1];
+    assert_eq!(b.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    let h: HashSet<u32> = collection![1, 2, 2];
+    assert_eq!(h.len(), 2);
+
+    let e: Vec<u32> = collection![];
+    assert!(e.is_empty());
+}
+
+#[test]
+fn test_map_literal() {
+    let b: BTreeMap<u32, &str> = map! {1 => "a", 2 => "b"};
+    assert_eq!(b.into_iter().collect::<Vec<_>>(), vec![(1, "a"), (2, "b")]);
+
+    let h: HashMap<u32, &str> = map! {
+        1 => "a",
+        2 => "b",
+    };
+    assert_eq!(h.get(&2), Some(&"b"));
+    assert!(h.capacity() >= 2);
+
+    fn generic<S, M: Map<S, KeyType = u32, ValueType = Vec<u32>> + Owned>() -> M {
+        map! {1 => collection![1], 2 => collection![2, 2]}
+    }
+
+    fn generic_seq<S: SequentialCollection<ElemType = u32> + Owned>() -> S {
+        collection![4, 5, 6]
+    }
+
+    let m: HashMap<_, _> = generic();
+    assert_eq!(m[&2], vec![2, 2]);
+    let m: BTreeMap<_, _> = generic();
+    assert_eq!(m[&1], vec![1]);
+    assert_eq!(generic_seq::<VecDeque<_>>(), vec![4, 5, 6]);
+}
@@ -0,0 +1,62 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std_collection_traits::{
+    elem::Owned,
+    iter::AssociatedIterableMut,
+    multimap::{BTreeMultimap, HashMultimap, Multimap},
+    AssociatedCollection,
+};
+
+fn group<M: Multimap<Key = bool, Value = u32>>(m: &mut M, values: &[u32]) {
+    for v in values {
+        m.insert(v % 2 == 0, *v);
+    }
+}
+
+fn flatten<C: AssociatedCollection<KeyType = bool, ValueType = u32> + Owned>(c: C) -> Vec<(bool, u32)> {
+    c.into_iter().collect()
+}
+
+#[test]
+fn test_map_of_buckets() {
+    let mut m: HashMap<bool, Vec<u32>> = HashMap::new();
+    group(&mut m, &[1, 2, 3, 3, 4]);
+    assert_eq!(m.get_all(&false), Some(&vec![1, 3, 3]));
+    assert_eq!((m.len_keys(), m.len_values()), (2, 5));
+
+    assert!(m.remove_one(&false, &3) && !m.remove_one(&false, &5));
+    assert_eq!(m.get_all(&false), Some(&vec![1, 3]));
+    assert!(m.remove_one(&true, &2) && m.remove_one(&true, &4));
+    assert_eq!(m.get_all(&true), None);
+    assert_eq!(m.len_keys(), 1);
+
+    let mut s: BTreeMap<bool, BTreeSet<u32>> = BTreeMap::new();
+    group(&mut s, &[3, 1, 2, 3]);
+    assert!(!Multimap::insert(&mut s, false, 1));
+    assert_eq!(
+        s.iter_flat().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+        vec![(false, 1), (false, 3), (true, 2)]
+    );
+    assert_eq!(s.remove_all(&false), Some(BTreeSet::from([1, 3])));
+    assert_eq!(s.len_values(), 1);
+}
+
+#[test]
+fn test_bucket_multimap() {
+    let mut m: BTreeMultimap<bool, u32> = BTreeMultimap::default();
+    group(&mut m, &[1, 2, 3, 4, 5]);
+    assert_eq!((m.len_keys(), m.len_values()), (2, 5));
+    assert!(m.remove_one(&true, &2));
+    assert_eq!(m.remove_all(&false), Some(vec![1, 3, 5]));
+    assert_eq!(m.len_values(), 1);
+
+    m.extend(vec![(false, 7), (true, 6)]);
+    m.iter_mut().for_each(|(_, v)| *v *= 10);
+    assert_eq!(flatten(m), vec![(false, 70), (true, 40), (true, 60)]);
+
+    let h: HashMultimap<bool, u32> = (0..10).map(|i| (i % 3 == 0, i)).collect();
+    assert_eq!(h.iter_flat().len(), 10);
+    let mut evens = h.get_all(&true).unwrap().clone();
+    evens.sort_unstable();
+    assert_eq!(evens, vec![0, 3, 6, 9]);
+    assert_eq!(flatten(h).len(), 10);
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std_collection_traits::{
+    elem::Owned,
+    exact_size::ExactSized,
+    iter::Iterable,
+    misc::{Contains, Erasable},
+    multiset::{BTreeMultiset, HashMultiset, Multiset, OrderedMultiset},
+    Collection,
+};
+
+fn sum<C: Collection<ElemType = u32>>(c: &C) -> u32 {
+    c.iter().sum()
+}
+
+fn sorted<C: Collection<ElemType = u32> + Owned>(c: C) -> Vec<u32> {
+    let mut values: Vec<_> = c.into_iter().collect();
+    values.sort_unstable();
+    values
+}
+
+#[test]
+fn test_map_of_counts() {
+    let mut m: HashMap<char, usize> = HashMap::new();
+    "mississippi".chars().for_each(|c| {
+        m.insert_n(c, 1);
+    });
+    assert_eq!((m.count(&'s'), m.count(&'x')), (4, 0));
+    assert_eq!((m.distinct_len(), m.total_len()), (4, 11));
+    assert_eq!(m.remove_n(&'p', 5), 2);
+    assert!(!m.contains_key(&'p') && !m.remove_one(&'p'));
+    assert_eq!(m.most_common(1).len(), 1);
+    assert_eq!(m.remove_all(&'s'), 4);
+
+    let other: HashMap<char, usize> = vec![('i', 2), ('m', 3)].into_iter().collect();
+    let mut union = m.clone();
+    union.union_with(&other);
+    assert_eq!((union.count(&'i'), union.count(&'m')), (4, 3));
+    m.intersect_with(&other);
+    assert_eq!(m, vec![('i', 2), ('m', 1)].into_iter().collect());
+    m.subtract(&other);
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_counted_multiset() {
+    let mut b: BTreeMultiset<u32> = vec![3, 1, 2, 3, 1, 3, 5].into_iter().collect();
+    assert_eq!((b.len(), b.distinct_len(), sum(&b)), (7, 4, 18));
+    assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![1, 1, 2, 3, 3, 3, 5]);
+    assert_eq!(b.most_common(2), vec![(&3, 3), (&1, 2)]);
+    assert_eq!(b.least_common(2), vec![(&2, 1), (&5, 1)]);
+    assert_eq!(b.first(), Some((&1, 2)));
+    assert_eq!(b.pop_last(), Some((5, 1)));
+    assert!(b.contains(&2) && !b.contains(&5));
+
+    let other: BTreeMultiset<u32> = vec![1, 3, 3, 3, 3, 4].into_iter().collect();
+    let mut intersection = b.clone();
+    intersection.intersect_with(&other);
+    assert_eq!(sorted(intersection), vec![1, 3, 3, 3]);
+    b.union_with(&other);
+    assert_eq!((b.len(), b.count(&3), b.count(&4)), (8, 4, 1));
+    b.subtract(&other);
+    assert_eq!(sorted(b.clone()), vec![1, 2]);
+    b.clear();
+    assert_eq!(b.total_len(), 0);
+
+    let h: HashMultiset<u32> = (0..20).map(|i| i % 4).collect();
+    assert_eq!((h.len(), h.count(&3)), (20, 5));
+    assert_eq!(sorted(h).len(), 20);
+}
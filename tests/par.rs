@@ -0,0 +1,63 @@
+#![cfg(feature = "rayon")]
+
+use rayon::iter::ParallelIterator;
+use std::{
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
+    iter::FromIterator,
+};
+use std_collection_traits::{
+    par::{AssociatedParIterable, AssociatedParIterableMut, ParDrainFull, ParExtendOwned, ParIterable},
+    Collection, ParAssociatedCollection, ParCollection, ParSequentialCollection,
+};
+
+fn sum<C: Collection<ElemType = u64> + ParIterable>(c: &C) -> u64 {
+    c.par_iter().sum()
+}
+
+#[test]
+fn test_par_iter() {
+    let v: Vec<u64> = (1..=100).collect();
+    assert_eq!(sum(&v), 5050);
+    assert_eq!(sum(&VecDeque::from_iter(1..=100u64)), 5050);
+    assert_eq!(sum(&LinkedList::from_iter(1..=100u64)), 5050);
+    assert_eq!(sum(&BinaryHeap::from_iter(1..=100u64)), 5050);
+    assert_eq!(sum(&HashSet::<u64>::from_iter(1..=100)), 5050);
+    assert_eq!(sum(&BTreeSet::from_iter(1..=100u64)), 5050);
+    assert_eq!(sum(&[1u64, 2, 3]), 6);
+
+    let mut m: HashMap<u64, u64> = (0..10).map(|i| (i, i)).collect();
+    m.par_iter_mut().for_each(|(k, v)| *v += k);
+    assert_eq!(AssociatedParIterable::par_iter(&m).map(|(_, v)| *v).sum::<u64>(), 90);
+}
+
+#[test]
+fn test_par_mut_drain_extend() {
+    fn double<C: ParSequentialCollection<ElemType = u64>>(c: &mut C) {
+        c.par_iter_mut().for_each(|x| *x *= 2);
+    }
+    let mut v: Vec<u64> = (1..=4).collect();
+    double(&mut v);
+    let mut l = LinkedList::from_iter(1..=4u64);
+    double(&mut l);
+    assert_eq!(v, Vec::from_iter(l));
+
+    let drained: u64 = v.par_drain().sum();
+    assert_eq!((drained, v.len()), (20, 0));
+    let mut s = HashSet::<u64>::from_iter(1..=4);
+    assert_eq!(s.par_drain().count(), 4);
+    assert!(s.is_empty());
+
+    v.par_extend(vec![1, 2, 3]);
+    s.par_extend(vec![1, 1, 2]);
+    assert_eq!((v.len(), s.len()), (3, 2));
+    let mut m = BTreeMap::new();
+    m.par_extend(vec![(1, 'a'), (2, 'b')]);
+    assert_eq!(m[&2], 'b');
+
+    fn par<C: ParCollection>() {}
+    par::<Vec<u64>>();
+    par::<BTreeSet<u64>>();
+    fn par_assoc<C: ParAssociatedCollection>() {}
+    par_assoc::<HashMap<u64, u64>>();
+    par_assoc::<BTreeMap<u64, u64>>();
+}
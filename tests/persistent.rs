@@ -0,0 +1,119 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasherDefault, Hasher},
+};
+use std_collection_traits::{
+    associated::AssociatedMap,
+    iter::{AssociatedIterable, Iterable},
+    persistent::{PMap, PVec, Persistent},
+    sequential::RandomAccess,
+    SequentialCollection,
+};
+
+fn history<C: Persistent + Clone>(init: C, elems: Vec<C::ElemType>) -> Vec<C> {
+    let mut versions = vec![init];
+    for elem in elems {
+        let next = versions.last().unwrap().with_inserted(elem);
+        versions.push(next);
+    }
+    versions
+}
+
+fn sum<C: SequentialCollection<ElemType = u64>>(c: &C) -> u64 {
+    c.iter().sum()
+}
+
+/// Hasher keeping only the lowest bits, so that keys collide.
+#[derive(Default)]
+struct Colliding(u64);
+
+impl Hasher for Colliding {
+    fn finish(&self) -> u64 {
+        self.0 & 3
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        bytes.iter().for_each(|b| self.0 = (self.0 << 8) | *b as u64)
+    }
+}
+
+#[test]
+fn test_pvec() {
+    let versions = history(PVec::new(), (0..100u64).collect());
+    for (len, version) in versions.iter().enumerate() {
+        assert_eq!(version.len(), len);
+        assert!(version.iter().copied().eq(0..len as u64));
+    }
+
+    let big: PVec<u64> = (0..5000).collect();
+    assert_eq!(sum(&big), 4999 * 5000 / 2);
+    assert!(big.iter().rev().copied().eq((0..5000).rev()));
+    assert_eq!(RandomAccess::get(&big, 1234), Some(&1234));
+    assert_eq!(big.partition_point(|x| *x < 4321), 4321);
+
+    // Iterating from both ends meets in the middle.
+    let mut iter = big.iter();
+    let (mut front, mut back) = (Vec::new(), Vec::new());
+    while let (Some(a), b) = (iter.next(), iter.next_back()) {
+        front.push(*a);
+        back.extend(b.copied());
+    }
+    front.extend(back.into_iter().rev());
+    assert!(front.into_iter().eq(0..5000));
+
+    let updated = big.with_updated(&40, |x| *x = 0).set(4999, 1);
+    assert_eq!((updated[40], updated[4999], big[40], big[4999]), (0, 1, 40, 4999));
+
+    let mut popped = big.clone();
+    for len in (0..5000usize).rev() {
+        popped = popped.pop_back().unwrap();
+        assert_eq!(
+            (popped.len(), popped.back().copied()),
+            (len, (len as u64).checked_sub(1))
+        );
+    }
+    assert!(popped.pop_back().is_none());
+    assert_eq!(big.len(), 5000);
+
+    let removed = big.without(&10);
+    assert_eq!((removed.len(), removed[9], removed[10]), (4999, 9, 11));
+    assert_eq!(big.without(&5000), big);
+}
+
+#[test]
+fn test_pmap() {
+    let versions = history(PMap::new(), (0..1000u32).map(|i| (i, i * 2)).collect());
+    let last = versions.last().unwrap();
+    assert_eq!(last.len(), 1000);
+    assert_eq!(versions[500].get(&499), Some(&998));
+    assert_eq!(versions[500].get(&500), None);
+    assert_eq!(AssociatedMap::get(last, &999), Some(&1998));
+
+    let entries: HashMap<u32, u32> = AssociatedIterable::iter(last).map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(entries.len(), 1000);
+    assert!(entries.iter().all(|(k, v)| *v == k * 2));
+
+    let mut map = last.clone();
+    for i in (0..1000).step_by(2) {
+        map = map.without(&i).with_updated(&(i + 1), |v| *v += 1);
+    }
+    assert_eq!(map.len(), 500);
+    assert!(map.iter().all(|(k, v)| k % 2 == 1 && *v == k * 2 + 1));
+    assert_eq!(last.get(&1), Some(&2));
+    assert!(map.without(&0).ptr_eq(&map));
+    assert_eq!(map, map.iter().map(|(k, v)| (*k, *v)).collect());
+}
+
+#[test]
+fn test_pmap_collisions() {
+    let map: PMap<u64, u64, BuildHasherDefault<Colliding>> = (0..100).map(|i| (i, i)).collect();
+    assert_eq!(map.len(), 100);
+    assert!((0..100).all(|i| map.get(&i) == Some(&i)));
+
+    let smaller = (0..100).filter(|i| i % 3 != 0).fold(map.clone(), |m, i| m.without(&i));
+    assert_eq!(smaller.len(), 34);
+    assert_eq!(smaller.iter().count(), 34);
+    assert!((0..100).all(|i| smaller.contains_key(&i) == (i % 3 == 0)));
+    assert_eq!(smaller.insert(3, 0).get(&3), Some(&0));
+    assert_eq!(map.get(&3), Some(&3));
+}
@@ -0,0 +1,69 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    iter::FromIterator,
+};
+use std_collection_traits::{
+    associated::{AssociatedSetAlgebra, SetAlgebra},
+    elem::Owned,
+    Set,
+};
+
+fn check_algebra<St, C: Set<St, ElemType = u32> + AssociatedSetAlgebra + Owned + Clone + FromIterator<u32>>() {
+    let a: C = vec![1, 2, 3, 4].into_iter().collect();
+    let b: C = vec![3, 4, 5].into_iter().collect();
+    let sorted = |c: C| {
+        let mut v = c.into_iter_owned().collect::<Vec<_>>();
+        v.sort_unstable();
+        v
+    };
+
+    assert_eq!(sorted(a.clone().union_owned(b.clone())), vec![1, 2, 3, 4, 5]);
+    assert_eq!(sorted(a.clone().intersection_owned(b.clone())), vec![3, 4]);
+    assert_eq!(sorted(a.clone().difference_owned(b.clone())), vec![1, 2]);
+    assert_eq!(sorted(a.clone().symmetric_difference_owned(b.clone())), vec![1, 2, 5]);
+
+    let mut c = a.clone();
+    c.union_with(&b);
+    assert_eq!(sorted(c), vec![1, 2, 3, 4, 5]);
+
+    let mut c = a.clone();
+    c.intersect_with(&b);
+    assert_eq!(sorted(c), vec![3, 4]);
+
+    let mut c = a.clone();
+    c.subtract(&b);
+    assert_eq!(sorted(c), vec![1, 2]);
+
+    let mut c = a;
+    c.symmetric_difference_with(&b);
+    assert_eq!(sorted(c), vec![1, 2, 5]);
+}
+
+#[test]
+fn test_set_algebra() {
+    check_algebra::<_, HashSet<u32>>();
+    check_algebra::<_, BTreeSet<u32>>();
+}
+
+#[test]
+fn test_set_algebra_operators() {
+    let a = SetAlgebra(vec![1u32, 2, 3].into_iter().collect::<BTreeSet<_>>());
+    let b = SetAlgebra(vec![2u32, 3, 4].into_iter().collect::<BTreeSet<_>>());
+
+    assert_eq!((a.clone() | b.clone()).into_inner(), vec![1, 2, 3, 4].into_iter().collect());
+    assert_eq!((a.clone() & b.clone()).into_inner(), vec![2, 3].into_iter().collect());
+    assert_eq!((a.clone() - b.clone()).into_inner(), vec![1].into_iter().collect());
+    assert_eq!((a.clone() ^ b.clone()).into_inner(), vec![1, 4].into_iter().collect());
+
+    let mut c = a.clone();
+    c |= &b;
+    assert_eq!(c.len(), 4);
+    c &= &a;
+    assert_eq!(*c, a.0);
+    c -= &b;
+    assert_eq!(c.into_inner(), vec![1].into_iter().collect());
+
+    let mut d = SetAlgebra(vec![1u32, 2].into_iter().collect::<HashSet<_>>());
+    d ^= &SetAlgebra(vec![2u32, 3].into_iter().collect());
+    assert_eq!(d.into_inner(), vec![1, 3].into_iter().collect());
+}
@@ -0,0 +1,77 @@
+use std::{collections::HashMap, sync::Arc, thread};
+use std_collection_traits::{
+    capacity::{CapacityAware, CapacityReserve, CapacityShrink},
+    sync::{ConcurrentMap, ShardedMap},
+    AssociatedCapacityAwareCollection, Map,
+};
+
+const THREADS: u64 = 8;
+const KEYS: u64 = 1000;
+
+fn spawn_all<F: Fn(u64) + Send + Sync + 'static>(f: F) {
+    let f = Arc::new(f);
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let f = f.clone();
+            thread::spawn(move || f(t))
+        })
+        .collect();
+    handles.into_iter().for_each(|h| h.join().unwrap());
+}
+
+#[test]
+fn test_sharded_stress() {
+    let map = Arc::new(ShardedMap::with_shards(4));
+    let m = map.clone();
+    spawn_all(move |t| {
+        for i in 0..KEYS {
+            m.upsert(i, || 1, |n| *n += 1);
+            m.insert(KEYS * (t + 1) + i, t);
+        }
+        for i in 0..KEYS / 2 {
+            m.remove(&(KEYS * (t + 1) + i));
+        }
+    });
+    assert_eq!(map.len(), (KEYS + THREADS * KEYS / 2) as usize);
+    assert!((0..KEYS).all(|i| map.get_cloned(&i) == Some(THREADS)));
+
+    let m = map.clone();
+    spawn_all(move |_| {
+        for i in 0..KEYS {
+            m.update(&i, |n| *n += 1);
+            assert!(m.compute_if_absent(i, |_| 0) > THREADS);
+        }
+    });
+    assert_eq!(map.get_cloned(&0), Some(2 * THREADS));
+
+    map.retain(|k, _| *k < KEYS);
+    assert_eq!(ConcurrentMap::len(&*map), KEYS as usize);
+}
+
+#[test]
+fn test_sharded_shards() {
+    fn total<S, M: Map<S, KeyType = u32, ValueType = u32>>(m: &M) -> u32 {
+        m.values().sum()
+    }
+    fn reserve<M: AssociatedCapacityAwareCollection<SizeType = usize, CapacityType = usize>>(m: &mut M) {
+        m.reserve(100);
+        assert!(m.capacity() >= m.len() + 100);
+    }
+
+    let mut map: ShardedMap<u32, u32> = (0..100).map(|i| (i, i)).collect();
+    assert_eq!(map.read_shards().map(|shard| total(&*shard)).sum::<u32>(), 4950);
+    assert!(map.read_shards().filter(|shard| !shard.is_empty()).count() > 1);
+    (0..map.shard_amount()).for_each(|i| map.with_shard_write(i, reserve));
+    assert_eq!(
+        map.with_shard_read(map.shard_index(&7), |shard| shard.get(&7).copied()),
+        Some(7)
+    );
+
+    CapacityReserve::reserve(&mut map, 1000);
+    assert!(map.capacity() >= 1100);
+    map.shrink_to_fit();
+    assert!(map.capacity() < 1100);
+
+    let merged: HashMap<_, _> = map.into_map().into_iter().collect();
+    assert_eq!(merged.len(), 100);
+}
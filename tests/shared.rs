@@ -0,0 +1,64 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std_collection_traits::{
+    associated::{AssociatedMap, AssociatedSet},
+    elem::Owned,
+    misc::Erasable,
+    shared::Shared,
+    DoubleEndedCollection, Map, RandomAccessCollection, Set,
+};
+
+fn push_all<C: DoubleEndedCollection<ElemType = u32> + Owned>(c: &mut C) {
+    c.push_back(3);
+    c.push_front(1);
+}
+
+fn set_defaults<S, M: Map<S, KeyType = &'static str, ValueType = u32> + Owned>(m: &mut M) {
+    m.insert("retries", 3);
+    if let Some(timeout) = m.get_mut(&"timeout") {
+        *timeout *= 2;
+    }
+}
+
+fn is_random_access<C: RandomAccessCollection>(_: &C) {}
+fn is_set<S, C: Set<S>>(_: &C) {}
+
+#[test]
+fn test_snapshot_isolation() {
+    let mut config: Shared<HashMap<&'static str, u32>> = Shared::default();
+    config.insert("timeout", 10);
+    let snapshot = config.snapshot();
+    assert!(snapshot.ptr_eq(&config) && config.share_count() == 2);
+
+    set_defaults(&mut config);
+    assert!(!snapshot.ptr_eq(&config));
+    assert_eq!(config.get(&"timeout"), Some(&20));
+    assert_eq!(config.get(&"retries"), Some(&3));
+    assert_eq!(snapshot.get(&"timeout"), Some(&10));
+    assert_eq!(snapshot.len(), 1);
+
+    // Removing a missing key doesn't clone the shared map.
+    let mut reader = snapshot.clone();
+    assert_eq!(reader.remove(&"retries"), None);
+    assert!(reader.ptr_eq(&snapshot));
+
+    reader.clear();
+    assert!(reader.is_empty() && snapshot.len() == 1);
+    assert_eq!(snapshot.into_inner(), HashMap::from([("timeout", 10)]));
+}
+
+#[test]
+fn test_umbrella_traits() {
+    let mut deque = Shared::new(VecDeque::from(vec![2]));
+    let before = deque.clone();
+    push_all(&mut deque);
+    assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(before.into_iter().collect::<Vec<_>>(), vec![2]);
+    is_random_access(&deque);
+
+    let mut set: Shared<BTreeSet<u32>> = (0..5).collect();
+    let old = set.snapshot();
+    assert!(!set.remove(&10) && set.ptr_eq(&old));
+    assert!(set.insert(10));
+    assert!(old.is_subset(&set) && !set.is_subset(&old));
+    is_set::<(), _>(&set);
+}
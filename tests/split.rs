@@ -0,0 +1,65 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    iter::FromIterator,
+    ops::Bound,
+};
+use std_collection_traits::split::{par_fold, BTreeMapRange, SlicePair, SplitView, SplitViewMut, Splittable};
+
+fn sum<'a, S: Splittable + Send + IntoIterator<Item = &'a u64>>(view: S) -> u64 {
+    par_fold(view, 4, |view| view.into_iter().sum::<u64>(), |a, b| a + b)
+}
+
+#[test]
+fn test_split_at_mid() {
+    let v: Vec<u32> = (0..5).collect();
+    assert_eq!(v.as_slice().split_at_mid(), (&v[..2], &v[2..]));
+
+    let mut d = VecDeque::from_iter(2..5);
+    d.push_front(1);
+    d.push_front(0);
+    let (left, right) = SlicePair::from(&d).split_at_mid();
+    assert_eq!((left.len_hint(), right.len_hint()), (2, 3));
+    assert_eq!(
+        left.iter().chain(right.iter()).copied().collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+
+    let m: BTreeMap<u32, char> = (0..10).map(|i| (i, 'x')).collect();
+    let (left, right) = BTreeMapRange::new(&m, Bound::Excluded(&2), Bound::Unbounded).split_at_mid();
+    assert_eq!(left.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![3, 4, 5]);
+    assert_eq!(right.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![6, 7, 8, 9]);
+    assert_eq!(right.len_hint(), 4);
+}
+
+#[test]
+fn test_par_fold() {
+    let v: Vec<u64> = (1..=1000).collect();
+    assert_eq!(sum(v.split_view()), 500500);
+    assert_eq!(sum(VecDeque::from_iter(1..=1000u64).split_view()), 500500);
+    assert_eq!(sum(BTreeSet::from_iter(1..=1000u64).split_view()), 500500);
+    assert_eq!(sum([1u64, 2, 3].split_view()), 6);
+
+    let m: BTreeMap<u64, u64> = (1..=100).map(|i| (i, i * 2)).collect();
+    let max = par_fold(
+        m.split_view(),
+        1,
+        |view| view.into_iter().map(|(_, v)| *v).max(),
+        Ord::max,
+    );
+    assert_eq!(max, Some(200));
+
+    // Results are combined in order.
+    let s: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+    let joined = par_fold(s.as_slice(), 3, |view| view.concat(), |a, b| a + &b);
+    assert_eq!(joined, s.concat());
+
+    let mut d = VecDeque::from_iter(0..100u64);
+    d.rotate_left(30);
+    par_fold(
+        d.split_view_mut(),
+        8,
+        |view| view.into_iter().for_each(|x| *x += 1),
+        |_, _| (),
+    );
+    assert_eq!(d.iter().sum::<u64>(), 5050);
+}
@@ -0,0 +1,82 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+    thread,
+};
+use std_collection_traits::{
+    sync::{ConcurrentMap, SyncCollection, SyncMap},
+    Map,
+};
+
+fn total<S, M: Map<S, KeyType = u32, ValueType = u32>>(m: &M) -> u32 {
+    m.values().sum()
+}
+
+fn count_words<M: ConcurrentMap<KeyType = String, ValueType = usize>>(map: &M, text: &str) {
+    for word in text.split_whitespace() {
+        map.upsert(word.to_string(), || 1, |n| *n += 1);
+    }
+}
+
+#[test]
+fn test_sync_map() {
+    let map = Arc::new(SyncMap::new(HashMap::new()));
+    let handles: Vec<_> = (0..4u32)
+        .map(|t| {
+            let map = map.clone();
+            thread::spawn(move || {
+                for i in 0..100 {
+                    map.insert(t * 100 + i, i);
+                }
+                map.remove(&(t * 100));
+            })
+        })
+        .collect();
+    handles.into_iter().for_each(|h| h.join().unwrap());
+    assert_eq!(map.len(), 396);
+    assert!(map.contains_key(&101) && !map.contains_key(&100));
+    assert_eq!(map.with_read(total), 4 * 4950);
+
+    let words = Arc::new(SyncMap::new(BTreeMap::new()));
+    let handles: Vec<_> = vec!["a b a", "b a c"]
+        .into_iter()
+        .map(|text| {
+            let words = words.clone();
+            thread::spawn(move || count_words(&*words, text))
+        })
+        .collect();
+    handles.into_iter().for_each(|h| h.join().unwrap());
+    assert_eq!(words.get_cloned(&"a".to_string()), Some(3));
+    assert_eq!(words.len(), 3);
+}
+
+#[test]
+fn test_compute_if_absent() {
+    let map = Arc::new(SyncMap::new(HashMap::new()));
+    let calls = Arc::new(SyncCollection::new(Vec::new()));
+    let handles: Vec<_> = (0..8)
+        .map(|t| {
+            let (map, calls) = (map.clone(), calls.clone());
+            thread::spawn(move || {
+                map.compute_if_absent(1u8, |k| {
+                    calls.with_write(|c| c.push(t));
+                    *k as usize * 10
+                })
+            })
+        })
+        .collect();
+    assert!(handles.into_iter().all(|h| h.join().unwrap() == 10));
+    assert_eq!(calls.len(), 1);
+    assert_eq!(ConcurrentMap::len(&*map), 1);
+}
+
+#[test]
+fn test_sync_collection() {
+    let set = SyncCollection::new(HashSet::new());
+    set.extend(vec![1, 2, 2]);
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&1));
+    assert!(set.with_write(|s| s.insert(3)));
+    set.clear();
+    assert!(set.is_empty());
+}
@@ -0,0 +1,52 @@
+#![feature(allocator_api)]
+use std_collection_traits::{
+    allocator::AllocatorConstructible,
+    capacity::CapacityTryReserve,
+    testing::alloc::{self, CountingAllocator, FailingAllocator},
+};
+use std::collections::{BinaryHeap, VecDeque};
+
+#[test]
+fn test_counting() {
+    let a = CountingAllocator::new();
+    let mut v: Vec<u64, _> = Vec::new_in(a.clone());
+    for i in 0..100 {
+        v.push(i);
+    }
+    assert!(a.stats().allocations() >= 1);
+    assert!(a.stats().reallocations() >= 1);
+    assert_eq!(a.stats().live_bytes(), v.capacity() * 8);
+    assert_eq!(alloc::stats_of(&v).live_bytes(), a.stats().live_bytes());
+    drop(v);
+    assert_eq!(a.stats().live_bytes(), 0);
+    assert_eq!(a.stats().deallocations(), 1);
+}
+
+#[test]
+fn test_failing() {
+    let a = FailingAllocator::fail_nth(1);
+    let mut v: Vec<u64, _> = AllocatorConstructible::new_in(a.clone());
+    assert!(CapacityTryReserve::try_reserve(&mut v, 4).is_ok());
+    assert!(CapacityTryReserve::try_reserve(&mut v, 8).is_err());
+    assert!(CapacityTryReserve::try_reserve(&mut v, 8).is_ok());
+    assert_eq!(a.requests(), 3);
+}
+
+#[test]
+fn test_assertions() {
+    alloc::assert_reserve_without_realloc::<Vec<u32, _>, _>(100, |i| i as u32);
+    alloc::assert_reserve_without_realloc::<VecDeque<String, _>, _>(100, |i| i.to_string());
+    alloc::assert_reserve_without_realloc::<BinaryHeap<u32, _>, _>(100, |i| i as u32);
+
+    alloc::assert_shrink_to::<Vec<u32, _>, _>(10, 20, |i| i as u32);
+    alloc::assert_shrink_to::<VecDeque<u32, _>, _>(10, 0, |i| i as u32);
+    alloc::assert_shrink_to::<BinaryHeap<u32, _>, _>(10, 5, |i| i as u32);
+
+    alloc::assert_try_reserve_fails::<Vec<u32, _>>(10);
+    alloc::assert_try_reserve_fails::<VecDeque<u32, _>>(10);
+    alloc::assert_try_reserve_fails::<BinaryHeap<u32, _>>(10);
+
+    alloc::assert_try_reserve_overflow::<Vec<u32, _>>();
+    alloc::assert_try_reserve_overflow::<VecDeque<u32, _>>();
+    alloc::assert_try_reserve_overflow::<BinaryHeap<u32, _>>();
+}
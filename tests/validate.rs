@@ -6,7 +6,12 @@ use std::{
     collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
     convert::TryFrom,
 };
-use std_collection_traits::{elem::Owned, exact_size::ExactSized, *};
+use std_collection_traits::{
+    compact::{Deque32, Vec32},
+    elem::Owned,
+    exact_size::ExactSized,
+    *,
+};
 
 #[test]
 fn test_collection() {
@@ -75,6 +80,7 @@ fn test_sequential() {
     contiguous::<&mut [usize]>();
     contiguous::<[usize; 42]>();
     contiguous::<Vec<usize>>();
+    contiguous::<Vec32<usize>>();
 
     fn seq<C: SequentialCollection>() {}
     seq::<&mut [usize]>();
@@ -82,29 +88,37 @@ fn test_sequential() {
     seq::<Vec<usize>>();
     seq::<VecDeque<usize>>();
     seq::<LinkedList<usize>>();
+    seq::<Vec32<usize>>();
+    seq::<Deque32<usize>>();
 
     fn own_seq<C: SequentialCollection + Owned>() {}
     own_seq::<Vec<usize>>();
     own_seq::<VecDeque<usize>>();
     own_seq::<LinkedList<usize>>();
+    own_seq::<Vec32<usize>>();
+    own_seq::<Deque32<usize>>();
 
     fn double<C: DoubleEndedCollection>() {}
     double::<VecDeque<usize>>();
     double::<LinkedList<usize>>();
+    double::<Deque32<usize>>();
 
     fn own_double<C: DoubleEndedCollection + Owned>() {}
     own_double::<VecDeque<usize>>();
     own_double::<LinkedList<usize>>();
+    own_double::<Deque32<usize>>();
 
     fn random<C: RandomAccessCollection>() {}
     random::<&mut [usize]>();
     random::<[usize; 42]>();
     random::<Vec<usize>>();
     random::<VecDeque<usize>>();
+    random::<Vec32<usize>>();
 
     fn own_random<C: RandomAccessCollection + Owned>() {}
     own_random::<Vec<usize>>();
     own_random::<VecDeque<usize>>();
+    own_random::<Vec32<usize>>();
 }
 
 #[test]
@@ -124,10 +138,72 @@ fn test_set_map() {
     order_map::<_, BTreeMap<usize, isize>>();
 }
 
+#[derive(Default, Clone, Copy)]
+struct Forward;
+
+unsafe impl std::alloc::Allocator for Forward {
+    fn allocate(&self, layout: std::alloc::Layout) -> Result<std::ptr::NonNull<[u8]>, std::alloc::AllocError> {
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: std::ptr::NonNull<u8>, layout: std::alloc::Layout) {
+        Global.deallocate(ptr, layout)
+    }
+}
+
 #[test]
 fn test_allocator() {
     fn allocator<C: AllocatorAwareCollection>() {}
     allocator::<Vec<usize, Global>>();
+    allocator::<Vec<usize, Forward>>();
+    allocator::<VecDeque<usize, Forward>>();
+    allocator::<BinaryHeap<usize, Forward>>();
+
+    fn cloneable<C: allocator::AllocatorCloneable>() {}
+    cloneable::<Box<[usize], Forward>>();
+    cloneable::<Vec<usize, Forward>>();
+    cloneable::<VecDeque<usize, Forward>>();
+    cloneable::<BinaryHeap<usize, Forward>>();
+
+    fn contiguous<C: sequential::Contiguous + sequential::RandomAccess + allocator::AllocatorAware>() {}
+    contiguous::<Box<[usize], Forward>>();
+
+    fn own_seq<C: SequentialCollection + Owned>() {}
+    own_seq::<Vec<usize, Forward>>();
+    own_seq::<VecDeque<usize, Forward>>();
+    own_seq::<LinkedList<usize, Forward>>();
+
+    fn own_random<C: RandomAccessCollection + Owned>() {}
+    own_random::<Vec<usize, Forward>>();
+    own_random::<VecDeque<usize, Forward>>();
+
+    fn cap<C: CapacityAwareCollection>() {}
+    cap::<Vec<usize, Forward>>();
+    cap::<VecDeque<usize, Forward>>();
+
+    fn order_set<S, C: OrderedSet<S>>() {}
+    order_set::<_, BTreeSet<usize, Forward>>();
+
+    fn order_map<S, C: OrderedMap<S>>() {}
+    order_map::<_, BTreeMap<usize, isize, Forward>>();
+
+    let v = vec![1usize, 2, 3];
+    let mut d = VecDeque::new_in(Forward);
+    d.extend(v.iter().copied());
+    let mut cloned = allocator::AllocatorCloneable::clone_in(&d, Forward);
+    let tail = sequential::Sequential::split_off(&mut cloned, 1);
+    assert!(cloned.iter().eq(&[1]));
+    assert!(tail.iter().eq(&[2, 3]));
+    assert!(d.iter().eq(&v));
+
+    let mut l = LinkedList::new_in(Forward);
+    let mut other = LinkedList::new_in(Forward);
+    other.extend(v.iter().copied());
+    sequential::Sequential::append(&mut l, &mut other);
+    other.extend(v.iter().copied());
+    sequential::Sequential::append(&mut l, &mut other);
+    assert!(l.iter().eq(v.iter().chain(&v)));
+    assert!(other.is_empty());
 }
 
 #[test]
@@ -135,10 +211,82 @@ fn test_capacity() {
     fn cap<C: CapacityAwareCollection>() {}
     cap::<Vec<usize>>();
     cap::<VecDeque<usize>>();
-    // no `reserve` here
-    // cap::<usize, BinaryHeap<_>>();
+    cap::<BinaryHeap<usize>>();
     cap::<HashSet<usize>>();
+    cap::<LinkedList<usize>>();
+    cap::<BTreeSet<usize>>();
+    cap::<Vec32<usize>>();
+    cap::<Deque32<usize>>();
 
     fn assoc_cap<C: AssociatedCapacityAwareCollection>() {}
     assoc_cap::<HashMap<usize, isize>>();
+    assoc_cap::<BTreeMap<usize, isize>>();
+
+    fn try_reserve<C: capacity::CapacityTryReserve>() {}
+    try_reserve::<Vec<usize>>();
+    try_reserve::<BinaryHeap<usize>>();
+    try_reserve::<HashMap<usize, isize>>();
+    try_reserve::<LinkedList<usize>>();
+    try_reserve::<Vec32<usize>>();
+    try_reserve::<Deque32<usize>>();
+
+    fn try_reserve_exact<C: capacity::CapacityReserveExact>() {}
+    try_reserve_exact::<Vec<usize>>();
+    try_reserve_exact::<VecDeque<usize>>();
+    try_reserve_exact::<Vec32<usize>>();
+    try_reserve_exact::<Deque32<usize>>();
+
+    fn hint<C: capacity::CapacityHint>(mut c: C) {
+        c.reserve_hint(10);
+    }
+    hint(vec![1usize]);
+    hint(BinaryHeap::<usize>::new());
+    hint(BTreeMap::<usize, isize>::new());
+    hint([1usize, 2]);
+    hint(vec![1usize].as_slice());
+
+    let mut l = LinkedList::new();
+    capacity::CapacityReserve::reserve(&mut l, 10);
+    l.push_back(1usize);
+    assert_eq!(capacity::CapacityAware::capacity(&l), usize::MAX);
+}
+
+#[test]
+fn test_hasher() {
+    #[derive(Default, Clone)]
+    struct FixedState;
+
+    impl std::hash::BuildHasher for FixedState {
+        type Hasher = std::collections::hash_map::DefaultHasher;
+
+        fn build_hasher(&self) -> Self::Hasher {
+            Default::default()
+        }
+    }
+
+    fn hasher<C: hasher::HasherAware<Hasher = FixedState>>() {
+        let c = C::with_capacity_and_hasher(8, FixedState);
+        let _ = c.hasher();
+        let _ = C::with_hasher(FixedState);
+    }
+    hasher::<HashSet<usize, FixedState>>();
+    hasher::<HashMap<usize, isize, FixedState>>();
+
+    fn set<S, C: Set<S>>() {}
+    set::<_, HashSet<usize, FixedState>>();
+
+    fn map<S, C: Map<S>>() {}
+    map::<_, HashMap<usize, isize, FixedState>>();
+
+    fn cap<C: CapacityAwareCollection>() {}
+    cap::<HashSet<usize, FixedState>>();
+
+    fn assoc_cap<C: AssociatedCapacityAwareCollection>() {}
+    assoc_cap::<HashMap<usize, isize, FixedState>>();
+
+    fn assoc_iter<C: iter::AssociatedIterable>() {}
+    assoc_iter::<HashSet<usize, FixedState>>();
+
+    fn drain<C: iter::DrainFull>() {}
+    drain::<HashSet<usize, FixedState>>();
 }